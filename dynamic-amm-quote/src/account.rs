@@ -0,0 +1,103 @@
+//! Deserialization of the accounts required by the quote from raw account data
+use crate::QuoteData;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use anyhow::Context;
+use prog_dynamic_amm::state::{Config, LockEscrow, Pool};
+use prog_dynamic_vault::state::{Strategy, Vault};
+use std::collections::HashMap;
+
+/// Deserialize pool account data
+pub fn deserialize_pool(data: &[u8]) -> anyhow::Result<Pool> {
+    Pool::try_deserialize(&mut &data[..]).context("Fail to deserialize pool")
+}
+
+/// Deserialize vault account data
+pub fn deserialize_vault(data: &[u8]) -> anyhow::Result<Vault> {
+    Vault::try_deserialize(&mut &data[..]).context("Fail to deserialize vault")
+}
+
+/// Deserialize strategy account data
+pub fn deserialize_strategy(data: &[u8]) -> anyhow::Result<Strategy> {
+    Strategy::try_deserialize(&mut &data[..]).context("Fail to deserialize strategy")
+}
+
+/// Deserialize lock escrow account data
+pub fn deserialize_lock_escrow(data: &[u8]) -> anyhow::Result<LockEscrow> {
+    LockEscrow::try_deserialize(&mut &data[..]).context("Fail to deserialize lock escrow")
+}
+
+/// Deserialize config account data
+pub fn deserialize_config(data: &[u8]) -> anyhow::Result<Config> {
+    Config::try_deserialize(&mut &data[..]).context("Fail to deserialize config")
+}
+
+/// Deserialize token account data
+pub fn deserialize_token_account(data: &[u8]) -> anyhow::Result<TokenAccount> {
+    TokenAccount::try_deserialize(&mut &data[..]).context("Fail to deserialize token account")
+}
+
+/// Deserialize mint account data
+pub fn deserialize_mint(data: &[u8]) -> anyhow::Result<Mint> {
+    Mint::try_deserialize(&mut &data[..]).context("Fail to deserialize mint")
+}
+
+/// Deserialize clock sysvar account data
+pub fn deserialize_clock(data: &[u8]) -> anyhow::Result<Clock> {
+    bincode::deserialize(data).context("Fail to deserialize clock")
+}
+
+/// Raw account data of the accounts required to build [QuoteData]
+pub struct QuoteAccounts<'a> {
+    /// Pool account data
+    pub pool: &'a [u8],
+    /// Vault A account data
+    pub vault_a: &'a [u8],
+    /// Vault B account data
+    pub vault_b: &'a [u8],
+    /// Pool vault A LP token account data
+    pub pool_vault_a_lp_token: &'a [u8],
+    /// Pool vault B LP token account data
+    pub pool_vault_b_lp_token: &'a [u8],
+    /// Lp mint account data of vault A
+    pub vault_a_lp_mint: &'a [u8],
+    /// Lp mint account data of vault B
+    pub vault_b_lp_mint: &'a [u8],
+    /// Token account data of vault A
+    pub vault_a_token: &'a [u8],
+    /// Token account data of vault B
+    pub vault_b_token: &'a [u8],
+    /// Clock sysvar account data
+    pub clock: &'a [u8],
+    /// Account data of the stake account of the depeg pool, as returned by [crate::depeg::get_stake_account]. Only for depeg pools
+    pub stake: Option<&'a [u8]>,
+}
+
+impl TryFrom<QuoteAccounts<'_>> for QuoteData {
+    type Error = anyhow::Error;
+
+    fn try_from(accounts: QuoteAccounts<'_>) -> anyhow::Result<Self> {
+        let pool = deserialize_pool(accounts.pool)?;
+
+        let mut stake_data = HashMap::new();
+        if let (Some(stake), Some(stake_account)) =
+            (accounts.stake, crate::depeg::get_stake_account(&pool))
+        {
+            stake_data.insert(stake_account, stake.to_vec());
+        }
+
+        Ok(QuoteData {
+            pool,
+            vault_a: deserialize_vault(accounts.vault_a)?,
+            vault_b: deserialize_vault(accounts.vault_b)?,
+            pool_vault_a_lp_token: deserialize_token_account(accounts.pool_vault_a_lp_token)?,
+            pool_vault_b_lp_token: deserialize_token_account(accounts.pool_vault_b_lp_token)?,
+            vault_a_lp_mint: deserialize_mint(accounts.vault_a_lp_mint)?,
+            vault_b_lp_mint: deserialize_mint(accounts.vault_b_lp_mint)?,
+            vault_a_token: deserialize_token_account(accounts.vault_a_token)?,
+            vault_b_token: deserialize_token_account(accounts.vault_b_token)?,
+            clock: deserialize_clock(accounts.clock)?,
+            stake_data,
+        })
+    }
+}
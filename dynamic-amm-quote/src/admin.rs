@@ -0,0 +1,219 @@
+//! Pre-flight validation of the admin instructions, and the pool fields they change
+use crate::diagnostic::get_current_point;
+use anchor_lang::prelude::Clock;
+use anyhow::{bail, ensure, Context};
+use prog_dynamic_amm::constants::activation::{
+    FIVE_MINUTES_SLOT_BUFFER, FIVE_MINUTES_TIME_BUFFER, MAX_ACTIVATION_SLOT_DURATION,
+    MAX_ACTIVATION_TIME_DURATION,
+};
+use prog_dynamic_amm::constants::fee::{
+    FEE_DENOMINATOR, MAX_BASIS_POINT, MAX_FEE_BPS, MAX_PARTNER_FEE_NUMERATOR,
+    MEME_MAX_FEE_NUMERATOR, MEME_MIN_FEE_NUMERATOR, MEME_MIN_FEE_UPDATE_WINDOW_DURATION,
+};
+use prog_dynamic_amm::constants::stable_curve::{MAX_AMP, MAX_A_CHANGE};
+use prog_dynamic_amm::constants::MIN_CHANGE_AMP_DURATION;
+use prog_dynamic_amm::state::{ActivationType, CurveType, Pool, PoolFees};
+
+/// Check the new fees of `set_pool_fees`. Meme pools, created with a config or customizable parameters, have narrower fee bounds and a minimum duration between fee updates.
+pub fn validate_pool_fees(
+    pool: &Pool,
+    fees: &PoolFees,
+    new_partner_fee_numerator: u64,
+    is_meme_pool: bool,
+    clock: &Clock,
+) -> anyhow::Result<()> {
+    ensure!(
+        fees.trade_fee_denominator > 0 && fees.protocol_trade_fee_denominator > 0,
+        "Fee denominator is zero"
+    );
+    // trade_fee_numerator / trade_fee_denominator <= MAX_FEE_BPS / MAX_BASIS_POINT
+    ensure!(
+        u128::from(fees.trade_fee_numerator) * u128::from(MAX_BASIS_POINT)
+            <= u128::from(MAX_FEE_BPS) * u128::from(fees.trade_fee_denominator),
+        "Trade fee {}/{} above {} bps",
+        fees.trade_fee_numerator,
+        fees.trade_fee_denominator,
+        MAX_FEE_BPS
+    );
+    ensure!(
+        fees.protocol_trade_fee_numerator <= fees.protocol_trade_fee_denominator,
+        "Protocol trade fee numerator {} above denominator {}",
+        fees.protocol_trade_fee_numerator,
+        fees.protocol_trade_fee_denominator
+    );
+    ensure!(
+        new_partner_fee_numerator <= MAX_PARTNER_FEE_NUMERATOR,
+        "Partner fee numerator {} above {}",
+        new_partner_fee_numerator,
+        MAX_PARTNER_FEE_NUMERATOR
+    );
+
+    if is_meme_pool {
+        ensure!(
+            fees.trade_fee_denominator == FEE_DENOMINATOR,
+            "Meme pool trade fee denominator must be {}",
+            FEE_DENOMINATOR
+        );
+        ensure!(
+            (MEME_MIN_FEE_NUMERATOR..=MEME_MAX_FEE_NUMERATOR).contains(&fees.trade_fee_numerator),
+            "Meme pool trade fee numerator {} out of [{}, {}]",
+            fees.trade_fee_numerator,
+            MEME_MIN_FEE_NUMERATOR,
+            MEME_MAX_FEE_NUMERATOR
+        );
+        let next_update_time = pool
+            .fee_last_updated_at
+            .saturating_add(MEME_MIN_FEE_UPDATE_WINDOW_DURATION as u64);
+        ensure!(
+            clock.unix_timestamp as u64 >= next_update_time,
+            "Meme pool fees last updated at {}, can only be updated from {}",
+            pool.fee_last_updated_at,
+            next_update_time
+        );
+    }
+
+    Ok(())
+}
+
+/// Check the new amp of `override_curve_param`, and return the curve type to send
+pub fn validate_amp_change(pool: &Pool, new_amp: u64, clock: &Clock) -> anyhow::Result<CurveType> {
+    let CurveType::Stable {
+        amp,
+        token_multiplier,
+        depeg,
+        last_amp_updated_timestamp,
+    } = pool.curve_type
+    else {
+        bail!("Only the amp of stable pools can be overridden");
+    };
+
+    ensure!(
+        new_amp > 0 && new_amp <= MAX_AMP,
+        "Amp {} out of [1, {}]",
+        new_amp,
+        MAX_AMP
+    );
+    ensure!(
+        amp.abs_diff(new_amp) <= MAX_A_CHANGE,
+        "Amp change from {} to {} above {}",
+        amp,
+        new_amp,
+        MAX_A_CHANGE
+    );
+    let next_change_time = last_amp_updated_timestamp.saturating_add(MIN_CHANGE_AMP_DURATION);
+    ensure!(
+        clock.unix_timestamp as u64 >= next_change_time,
+        "Amp last updated at {}, can only be changed from {}",
+        last_amp_updated_timestamp,
+        next_change_time
+    );
+
+    Ok(CurveType::Stable {
+        amp: new_amp,
+        token_multiplier,
+        depeg,
+        last_amp_updated_timestamp,
+    })
+}
+
+/// Check the new activation point of `update_activation_point`. Only a pool not activated yet can be rescheduled, at least five minutes ahead, and within the maximum activation duration.
+pub fn validate_activation_point(
+    pool: &Pool,
+    new_activation_point: u64,
+    clock: &Clock,
+) -> anyhow::Result<()> {
+    let (activation_type, current_point) =
+        get_current_point(pool, clock).context("Invalid activation type")?;
+    let (buffer, max_duration) = match activation_type {
+        ActivationType::Slot => (FIVE_MINUTES_SLOT_BUFFER, MAX_ACTIVATION_SLOT_DURATION),
+        ActivationType::Timestamp => (FIVE_MINUTES_TIME_BUFFER, MAX_ACTIVATION_TIME_DURATION),
+    };
+
+    ensure!(
+        current_point < pool.bootstrapping.activation_point,
+        "Pool already activated at {:?} {}, current {}",
+        activation_type,
+        pool.bootstrapping.activation_point,
+        current_point
+    );
+    let earliest = current_point.saturating_add(buffer);
+    let latest = current_point.saturating_add(max_duration);
+    ensure!(
+        (earliest..=latest).contains(&new_activation_point),
+        "Activation point {} out of [{}, {}], current {:?} {}",
+        new_activation_point,
+        earliest,
+        latest,
+        activation_type,
+        current_point
+    );
+
+    Ok(())
+}
+
+/// Pool field changed by an admin instruction
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+fn get_admin_fields(pool: &Pool) -> Vec<(&'static str, String)> {
+    let (amp, last_amp_updated_timestamp) = match pool.curve_type {
+        CurveType::Stable {
+            amp,
+            last_amp_updated_timestamp,
+            ..
+        } => (amp.to_string(), last_amp_updated_timestamp.to_string()),
+        CurveType::ConstantProduct => ("-".to_string(), "-".to_string()),
+    };
+    vec![
+        ("enabled", pool.enabled.to_string()),
+        (
+            "fees.trade_fee_numerator",
+            pool.fees.trade_fee_numerator.to_string(),
+        ),
+        (
+            "fees.trade_fee_denominator",
+            pool.fees.trade_fee_denominator.to_string(),
+        ),
+        (
+            "fees.protocol_trade_fee_numerator",
+            pool.fees.protocol_trade_fee_numerator.to_string(),
+        ),
+        (
+            "fees.protocol_trade_fee_denominator",
+            pool.fees.protocol_trade_fee_denominator.to_string(),
+        ),
+        ("fee_last_updated_at", pool.fee_last_updated_at.to_string()),
+        (
+            "partner_info.fee_numerator",
+            pool.partner_info.fee_numerator.to_string(),
+        ),
+        ("curve_type.amp", amp),
+        (
+            "curve_type.last_amp_updated_timestamp",
+            last_amp_updated_timestamp,
+        ),
+        (
+            "bootstrapping.activation_point",
+            pool.bootstrapping.activation_point.to_string(),
+        ),
+    ]
+}
+
+/// Pool fields changed by the admin instructions, between `before` and `after`
+pub fn diff_pool(before: &Pool, after: &Pool) -> Vec<FieldChange> {
+    get_admin_fields(before)
+        .into_iter()
+        .zip(get_admin_fields(after))
+        .filter(|((_, before), (_, after))| before != after)
+        .map(|((field, before), (_, after))| FieldChange {
+            field,
+            before,
+            after,
+        })
+        .collect()
+}
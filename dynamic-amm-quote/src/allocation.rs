@@ -0,0 +1,88 @@
+//! Allocation of the vault liquidity across strategies
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context};
+use prog_dynamic_vault::state::{Strategy, StrategyType, Vault};
+
+#[derive(Debug, Clone)]
+pub struct StrategyAllocation {
+    /// Strategy address
+    pub strategy: Pubkey,
+    /// Lending protocol of the strategy
+    pub strategy_type: StrategyType,
+    /// Liquidity deployed to the strategy
+    pub current_liquidity: u64,
+    /// Whether the strategy is disabled
+    pub is_disabled: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct VaultAllocation {
+    /// Vault address
+    pub vault: Pubkey,
+    /// Total liquidity of the vault
+    pub total_amount: u64,
+    /// Liquidity not deployed to any strategy
+    pub idle_amount: u64,
+    /// Allocation of each strategy of the vault, including disabled strategies
+    pub strategies: Vec<StrategyAllocation>,
+}
+
+impl VaultAllocation {
+    /// Liquidity deployed per strategy type, in the order the strategy type first appears in the vault
+    pub fn get_amount_by_strategy_type(&self) -> Vec<(StrategyType, u64)> {
+        let mut amounts: Vec<(StrategyType, u64)> = vec![];
+        for allocation in self.strategies.iter() {
+            match amounts
+                .iter_mut()
+                .find(|(strategy_type, _)| *strategy_type == allocation.strategy_type)
+            {
+                Some((_, amount)) => *amount = amount.saturating_add(allocation.current_liquidity),
+                None => amounts.push((allocation.strategy_type, allocation.current_liquidity)),
+            }
+        }
+        amounts
+    }
+
+    /// Share of `amount` in the total liquidity of the vault
+    pub fn get_share(&self, amount: u64) -> f64 {
+        if self.total_amount == 0 {
+            return 0.0;
+        }
+        amount as f64 / self.total_amount as f64
+    }
+}
+
+/// Compute the allocation of the vault liquidity. `strategies` are the addresses and states of all non default [Vault::strategies]
+pub fn compute_vault_allocation(
+    vault: Pubkey,
+    vault_state: &Vault,
+    strategies: &[(Pubkey, Strategy)],
+) -> anyhow::Result<VaultAllocation> {
+    for (strategy, strategy_state) in strategies.iter() {
+        ensure!(
+            strategy_state.vault == vault,
+            "Strategy {} doesn't belong to vault {}",
+            strategy,
+            vault
+        );
+    }
+
+    let liquidity = vault_state
+        .get_liquidity(strategies.iter().map(|(_, strategy_state)| strategy_state))
+        .context("Fail to get vault liquidity")?;
+
+    Ok(VaultAllocation {
+        vault,
+        total_amount: vault_state.total_amount,
+        idle_amount: liquidity.idle_amount,
+        strategies: strategies
+            .iter()
+            .map(|(strategy, strategy_state)| StrategyAllocation {
+                strategy: *strategy,
+                strategy_type: strategy_state.strategy_type,
+                current_liquidity: strategy_state.current_liquidity,
+                is_disabled: strategy_state.is_disable != 0,
+            })
+            .collect(),
+    })
+}
@@ -0,0 +1,117 @@
+//! Planning of an amplification coefficient ramp of a stable pool, in steps compliant with `override_curve_param`
+use crate::slippage::compute_price_impact_bps;
+use crate::{compute_quote, QuoteData};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, ensure};
+use prog_dynamic_amm::constants::stable_curve::{MAX_AMP, MAX_A_CHANGE};
+use prog_dynamic_amm::constants::MIN_CHANGE_AMP_DURATION;
+use prog_dynamic_amm::state::CurveType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AmpRampStep {
+    /// Amp of the pool after the step
+    pub amp: u64,
+    /// Earliest timestamp the step can be executed, the previous amp update being done on time
+    pub earliest_time: u64,
+}
+
+/// Earliest timestamp of the next step, `interval` seconds after the last amp update, and never before the minimum amp change duration
+pub fn get_next_step_time(last_amp_updated_timestamp: u64, interval: u64) -> u64 {
+    last_amp_updated_timestamp.saturating_add(interval.max(MIN_CHANGE_AMP_DURATION))
+}
+
+/// Steps from `current_amp` to `target_amp`, changing the amp by at most `max_amp_change` every `interval` seconds.
+/// Step times follow [get_next_step_time], the schedule the ramp job waits on.
+pub fn plan_amp_ramp(
+    current_amp: u64,
+    target_amp: u64,
+    max_amp_change: u64,
+    last_amp_updated_timestamp: u64,
+    interval: u64,
+) -> anyhow::Result<Vec<AmpRampStep>> {
+    ensure!(
+        target_amp > 0 && target_amp <= MAX_AMP,
+        "Target amp {} out of [1, {}]",
+        target_amp,
+        MAX_AMP
+    );
+    ensure!(
+        max_amp_change > 0 && max_amp_change <= MAX_A_CHANGE,
+        "Max amp change {} out of [1, {}]",
+        max_amp_change,
+        MAX_A_CHANGE
+    );
+    ensure!(
+        interval >= MIN_CHANGE_AMP_DURATION,
+        "Interval {} below the minimum amp change duration {}",
+        interval,
+        MIN_CHANGE_AMP_DURATION
+    );
+
+    let mut steps = vec![];
+    let mut amp = current_amp;
+    let mut earliest_time = last_amp_updated_timestamp;
+    while amp != target_amp {
+        earliest_time = get_next_step_time(earliest_time, interval);
+        amp = if target_amp > amp {
+            target_amp.min(amp + max_amp_change)
+        } else {
+            target_amp.max(amp.saturating_sub(max_amp_change))
+        };
+        steps.push(AmpRampStep { amp, earliest_time });
+    }
+    Ok(steps)
+}
+
+/// Pool curve type with the amp replaced, only for stable pools
+pub fn with_amp(curve_type: CurveType, new_amp: u64) -> anyhow::Result<CurveType> {
+    match curve_type {
+        CurveType::Stable {
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+            ..
+        } => Ok(CurveType::Stable {
+            amp: new_amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp,
+        }),
+        CurveType::ConstantProduct => bail!("Only stable pools have an amp"),
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AmpPriceImpact {
+    pub amp: u64,
+    /// Out amount of the reference swap
+    pub out_amount: u64,
+    /// Price of the reference swap, relative to the marginal price of the pool at the same amp, in bps
+    pub price_impact_bps: f64,
+}
+
+/// Price impact of swapping `in_amount` of `in_token_mint`, with the pool amp set to `amp`.
+/// See [compute_price_impact_bps].
+pub fn compute_amp_price_impact(
+    quote_data: &QuoteData,
+    amp: u64,
+    in_token_mint: Pubkey,
+    in_amount: u64,
+) -> anyhow::Result<AmpPriceImpact> {
+    let mut quote_data = quote_data.clone();
+    quote_data.pool.curve_type = with_amp(quote_data.pool.curve_type, amp)?;
+
+    let quote = compute_quote(in_token_mint, in_amount, quote_data.clone())?;
+    Ok(AmpPriceImpact {
+        amp,
+        out_amount: quote.out_amount,
+        price_impact_bps: compute_price_impact_bps(
+            &quote_data,
+            in_token_mint,
+            in_amount,
+            quote.out_amount,
+        )?,
+    })
+}
@@ -0,0 +1,122 @@
+//! APY of the pool LP, split between the lending yield of the vaults and the trading fee of the pool
+use crate::QuoteData;
+use prog_dynamic_vault::state::{estimate_apy, estimate_apy_by_prices, VaultSnapshot};
+
+/// Snapshot of the pool and its vaults, used to estimate the pool LP yield
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolSnapshot {
+    /// Timestamp (in seconds) of the snapshot
+    pub timestamp: u64,
+    /// Pool lp mint supply
+    pub pool_lp_supply: u64,
+    /// Amount of vault A lp hold by the pool
+    pub pool_vault_a_lp_amount: u64,
+    /// Amount of vault B lp hold by the pool
+    pub pool_vault_b_lp_amount: u64,
+    /// Unlocked amount of vault A
+    pub vault_a_unlocked_amount: u64,
+    /// Vault A lp mint supply
+    pub vault_a_lp_supply: u64,
+    /// Unlocked amount of vault B
+    pub vault_b_unlocked_amount: u64,
+    /// Vault B lp mint supply
+    pub vault_b_lp_supply: u64,
+}
+
+impl PoolSnapshot {
+    /// Take the snapshot at the clock of `quote_data`
+    pub fn from_quote_data(quote_data: &QuoteData, pool_lp_supply: u64) -> Option<Self> {
+        let timestamp = u64::try_from(quote_data.clock.unix_timestamp).ok()?;
+        Some(PoolSnapshot {
+            timestamp,
+            pool_lp_supply,
+            pool_vault_a_lp_amount: quote_data.pool_vault_a_lp_token.amount,
+            pool_vault_b_lp_amount: quote_data.pool_vault_b_lp_token.amount,
+            vault_a_unlocked_amount: quote_data.vault_a.get_unlocked_amount(timestamp)?,
+            vault_a_lp_supply: quote_data.vault_a_lp_mint.supply,
+            vault_b_unlocked_amount: quote_data.vault_b.get_unlocked_amount(timestamp)?,
+            vault_b_lp_supply: quote_data.vault_b_lp_mint.supply,
+        })
+    }
+
+    pub fn get_vault_a_snapshot(&self) -> VaultSnapshot {
+        VaultSnapshot {
+            timestamp: self.timestamp,
+            unlocked_amount: self.vault_a_unlocked_amount,
+            lp_supply: self.vault_a_lp_supply,
+        }
+    }
+
+    pub fn get_vault_b_snapshot(&self) -> VaultSnapshot {
+        VaultSnapshot {
+            timestamp: self.timestamp,
+            unlocked_amount: self.vault_b_unlocked_amount,
+            lp_supply: self.vault_b_lp_supply,
+        }
+    }
+
+    /// Vault lp backing one pool lp, as the geometric mean of vault A and B lp. Swaps move the pool between A and B without
+    /// changing it, so it only grows from the trading fee kept by the pool.
+    pub fn get_vault_lp_per_pool_lp(&self) -> Option<f64> {
+        if self.pool_lp_supply == 0 {
+            return None;
+        }
+        let vault_lp_amount =
+            (self.pool_vault_a_lp_amount as f64 * self.pool_vault_b_lp_amount as f64).sqrt();
+        Some(vault_lp_amount / self.pool_lp_supply as f64)
+    }
+
+    /// Token backing one vault lp, as the geometric mean of vault A and B lp price. It only grows from the lending yield of the vaults.
+    pub fn get_vault_lp_price(&self) -> Option<f64> {
+        let lp_price_a = self.get_vault_a_snapshot().get_lp_price()?;
+        let lp_price_b = self.get_vault_b_snapshot().get_lp_price()?;
+        Some((lp_price_a * lp_price_b).sqrt())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PoolApy {
+    /// APY of vault A lp
+    pub vault_a_apy: f64,
+    /// APY of vault B lp
+    pub vault_b_apy: f64,
+    /// Part of the pool lp APY from the lending yield of both vaults
+    pub lending_apy: f64,
+    /// Part of the pool lp APY from the trading fee
+    pub trading_fee_apy: f64,
+    /// APY of the pool lp. (1 + total) = (1 + lending) * (1 + trading fee)
+    pub total_apy: f64,
+}
+
+/// Estimate the APY of the pool lp, and how much of it comes from lending and the trading fee. Snapshots must be sorted by timestamp.
+pub fn estimate_pool_apy(snapshots: &[PoolSnapshot]) -> Option<PoolApy> {
+    let get_prices = |get_price: fn(&PoolSnapshot) -> Option<f64>| {
+        snapshots
+            .iter()
+            .filter_map(|snapshot| Some((snapshot.timestamp, get_price(snapshot)?)))
+            .collect::<Vec<_>>()
+    };
+
+    let vault_a_snapshots = snapshots
+        .iter()
+        .map(PoolSnapshot::get_vault_a_snapshot)
+        .collect::<Vec<_>>();
+    let vault_b_snapshots = snapshots
+        .iter()
+        .map(PoolSnapshot::get_vault_b_snapshot)
+        .collect::<Vec<_>>();
+
+    let lending_apy = estimate_apy_by_prices(&get_prices(PoolSnapshot::get_vault_lp_price))?;
+    let trading_fee_apy =
+        estimate_apy_by_prices(&get_prices(PoolSnapshot::get_vault_lp_per_pool_lp))?;
+
+    Some(PoolApy {
+        vault_a_apy: estimate_apy(&vault_a_snapshots)?,
+        vault_b_apy: estimate_apy(&vault_b_snapshots)?,
+        lending_apy,
+        trading_fee_apy,
+        total_apy: (1.0 + lending_apy) * (1.0 + trading_fee_apy) - 1.0,
+    })
+}
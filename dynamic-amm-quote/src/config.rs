@@ -0,0 +1,74 @@
+//! Validation of the config and customizable pool parameters, before sending them to the program
+use anyhow::ensure;
+use prog_dynamic_amm::constants::activation::{
+    MAX_ACTIVATION_SLOT_DURATION, MAX_ACTIVATION_TIME_DURATION,
+};
+use prog_dynamic_amm::constants::fee::{
+    FEE_DENOMINATOR, MAX_PARTNER_FEE_NUMERATOR, MEME_MAX_FEE_NUMERATOR, MEME_MIN_FEE_NUMERATOR,
+};
+use prog_dynamic_amm::instructions::{ConfigParameters, CustomizableParams};
+use prog_dynamic_amm::state::ActivationType;
+
+fn validate_trade_fee_numerator(trade_fee_numerator: u64) -> anyhow::Result<()> {
+    ensure!(
+        (MEME_MIN_FEE_NUMERATOR..=MEME_MAX_FEE_NUMERATOR).contains(&trade_fee_numerator),
+        "Trade fee numerator {} out of [{}, {}], fee denominator is {}",
+        trade_fee_numerator,
+        MEME_MIN_FEE_NUMERATOR,
+        MEME_MAX_FEE_NUMERATOR,
+        FEE_DENOMINATOR
+    );
+    Ok(())
+}
+
+fn parse_activation_type(activation_type: u8) -> anyhow::Result<ActivationType> {
+    ActivationType::try_from(activation_type)
+        .map_err(|_| anyhow::anyhow!("Invalid activation type {}", activation_type))
+}
+
+/// Check the config parameters against the bounds enforced by the program
+pub fn validate_config_parameters(config_parameters: &ConfigParameters) -> anyhow::Result<()> {
+    let ConfigParameters {
+        trade_fee_numerator,
+        protocol_trade_fee_numerator,
+        activation_duration,
+        activation_type,
+        partner_fee_numerator,
+        ..
+    } = *config_parameters;
+
+    validate_trade_fee_numerator(trade_fee_numerator)?;
+    ensure!(
+        protocol_trade_fee_numerator <= FEE_DENOMINATOR,
+        "Protocol trade fee numerator {} above fee denominator {}",
+        protocol_trade_fee_numerator,
+        FEE_DENOMINATOR
+    );
+
+    let max_activation_duration = match parse_activation_type(activation_type)? {
+        ActivationType::Slot => MAX_ACTIVATION_SLOT_DURATION,
+        ActivationType::Timestamp => MAX_ACTIVATION_TIME_DURATION,
+    };
+    ensure!(
+        activation_duration <= max_activation_duration,
+        "Activation duration {} above {}",
+        activation_duration,
+        max_activation_duration
+    );
+
+    ensure!(
+        partner_fee_numerator <= MAX_PARTNER_FEE_NUMERATOR,
+        "Partner fee numerator {} above {}",
+        partner_fee_numerator,
+        MAX_PARTNER_FEE_NUMERATOR
+    );
+
+    Ok(())
+}
+
+/// Check the parameters of a customizable permissionless constant product pool against the bounds enforced by the program
+pub fn validate_customizable_params(params: &CustomizableParams) -> anyhow::Result<()> {
+    validate_trade_fee_numerator(params.trade_fee_numerator.into())?;
+    parse_activation_type(params.activation_type)?;
+    Ok(())
+}
@@ -1 +1,2 @@
 pub mod curve_type;
+pub mod stable;
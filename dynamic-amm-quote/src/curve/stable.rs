@@ -0,0 +1,98 @@
+//! Stable curve type of a new pool
+use crate::depeg::{marinade, solido, spl_stake};
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::constants::stable_curve::MAX_AMP;
+use prog_dynamic_amm::state::{CurveType, Depeg, DepegType, TokenMultiplier};
+
+/// Liquid staking token of a depeg pool. The LST is token B of the pool, its virtual price is read from the stake account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LstType {
+    Marinade,
+    Lido,
+    SplStake { stake_pool: Pubkey },
+}
+
+impl LstType {
+    pub fn get_depeg_type(&self) -> DepegType {
+        match self {
+            LstType::Marinade => DepegType::Marinade,
+            LstType::Lido => DepegType::Lido,
+            LstType::SplStake { .. } => DepegType::SplStake,
+        }
+    }
+
+    /// Stake account the virtual price of the LST is read from
+    pub fn get_stake_account(&self) -> Pubkey {
+        match self {
+            LstType::Marinade => marinade::stake::ID,
+            LstType::Lido => solido::stake::ID,
+            LstType::SplStake { stake_pool } => *stake_pool,
+        }
+    }
+
+    /// Check `lst_mint`, token B of the pool, is the token of the staking program. `stake_account_data` is the data of the
+    /// [LstType::get_stake_account], only read for SPL stake pools, the Marinade and Lido mints are fixed.
+    pub fn verify_lst_mint(
+        &self,
+        lst_mint: Pubkey,
+        stake_account_data: &[u8],
+    ) -> anyhow::Result<()> {
+        let expected_mint = match self {
+            LstType::Marinade => marinade::mint::ID,
+            LstType::Lido => solido::mint::ID,
+            LstType::SplStake { .. } => spl_stake::get_pool_mint(stake_account_data)
+                .context("Invalid SPL stake pool account")?,
+        };
+        ensure!(
+            lst_mint == expected_mint,
+            "Token B mint {} is not the LST of {:?}, expected {}",
+            lst_mint,
+            self,
+            expected_mint
+        );
+        Ok(())
+    }
+
+    /// Stake account to append to the pool initialization instruction
+    pub fn get_remaining_accounts(&self) -> Vec<AccountMeta> {
+        vec![AccountMeta::new_readonly(self.get_stake_account(), false)]
+    }
+}
+
+/// Upscale both tokens to the precision of the token with the most decimals
+pub fn compute_token_multiplier(token_a_decimals: u8, token_b_decimals: u8) -> TokenMultiplier {
+    let precision_factor = token_a_decimals.max(token_b_decimals);
+    TokenMultiplier {
+        token_a_multiplier: 10u64.pow((precision_factor - token_a_decimals).into()),
+        token_b_multiplier: 10u64.pow((precision_factor - token_b_decimals).into()),
+        precision_factor,
+    }
+}
+
+/// Stable curve type of a new pool. The depeg base virtual price is set by the program on initialization.
+pub fn generate_stable_curve_type(
+    amp: u64,
+    token_a_decimals: u8,
+    token_b_decimals: u8,
+    lst_type: Option<LstType>,
+) -> anyhow::Result<CurveType> {
+    ensure!(
+        amp > 0 && amp <= MAX_AMP,
+        "Amp {} out of [1, {}]",
+        amp,
+        MAX_AMP
+    );
+    Ok(CurveType::Stable {
+        amp,
+        token_multiplier: compute_token_multiplier(token_a_decimals, token_b_decimals),
+        depeg: Depeg {
+            base_virtual_price: 0,
+            base_cache_updated: 0,
+            depeg_type: lst_type
+                .map(|lst_type| lst_type.get_depeg_type())
+                .unwrap_or(DepegType::None),
+        },
+        last_amp_updated_timestamp: 0,
+    })
+}
@@ -0,0 +1,1152 @@
+//! Decoding of the dynamic amm instructions, including the instructions invoked through CPI
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, CompiledInstruction};
+use anchor_lang::{AnchorDeserialize, Discriminator, ToAccountMetas};
+use anyhow::{ensure, Context};
+use prog_dynamic_amm::instructions::{ConfigParameters, CustomizableParams};
+use prog_dynamic_amm::state::{CurveType, PoolFees};
+use prog_dynamic_amm::{accounts, instruction};
+
+/// Decoded dynamic amm instruction, with its arguments and named accounts
+pub enum DynamicAmmInstruction {
+    /// Initialize a new permissionless pool.
+    InitializePermissionlessPool {
+        curve_type: CurveType,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        accounts: accounts::InitializePermissionlessPool,
+    },
+    /// Initialize permissionless pool with customizable params
+    InitializeCustomizablePermissionlessConstantProductPool {
+        token_a_amount: u64,
+        token_b_amount: u64,
+        params: CustomizableParams,
+        accounts: accounts::InitializeCustomizablePermissionlessConstantProductPool,
+    },
+    /// Initialize a new permissionless pool with customized fee tier
+    InitializePermissionlessPoolWithFeeTier {
+        curve_type: CurveType,
+        trade_fee_bps: u64,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        accounts: accounts::InitializePermissionlessPoolWithFeeTier,
+    },
+    /// Partner claim fee
+    PartnerClaimFee {
+        max_amount_a: u64,
+        max_amount_b: u64,
+        accounts: accounts::PartnerClaimFees,
+    },
+    /// Withdraw only single token from the pool. Only supported by pool with stable swap curve.
+    RemoveLiquiditySingleSide {
+        pool_token_amount: u64,
+        minimum_out_amount: u64,
+        accounts: accounts::RemoveLiquiditySingleSide,
+    },
+    /// Deposit tokens to the pool in an imbalance ratio. Only supported by pool with stable swap curve.
+    AddImbalanceLiquidity {
+        minimum_pool_token_amount: u64,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        accounts: accounts::AddOrRemoveBalanceLiquidity,
+    },
+    /// Swap token A to B, or vice versa. An amount of trading fee will be charged for liquidity provider, and the admin of the pool.
+    Swap {
+        in_amount: u64,
+        minimum_out_amount: u64,
+        accounts: accounts::Swap,
+    },
+    /// Withdraw tokens from the pool in a balanced ratio. User will still able to withdraw from pool even the pool is disabled. This allow user to exit their liquidity when there's some unforeseen event happen.
+    RemoveBalanceLiquidity {
+        pool_token_amount: u64,
+        minimum_a_token_out: u64,
+        minimum_b_token_out: u64,
+        accounts: accounts::AddOrRemoveBalanceLiquidity,
+    },
+    /// Deposit tokens to the pool in a balanced ratio.
+    AddBalanceLiquidity {
+        pool_token_amount: u64,
+        maximum_token_a_amount: u64,
+        maximum_token_b_amount: u64,
+        accounts: accounts::AddOrRemoveBalanceLiquidity,
+    },
+    /// Get the general information of the pool.
+    GetPoolInfo { accounts: accounts::GetPoolInfo },
+    /// Bootstrap the pool when liquidity is depleted.
+    BootstrapLiquidity {
+        token_a_amount: u64,
+        token_b_amount: u64,
+        accounts: accounts::BootstrapLiquidity,
+    },
+    /// Create lock account
+    CreateLockEscrow {
+        accounts: accounts::CreateLockEscrow,
+    },
+    /// Lock Lp token
+    Lock {
+        amount: u64,
+        accounts: accounts::Lock,
+    },
+    /// Claim fee
+    ClaimFee {
+        max_amount: u64,
+        accounts: accounts::ClaimFee,
+    },
+    /// Initialize a new permissioned pool.
+    InitializePermissionedPool {
+        curve_type: CurveType,
+        accounts: accounts::InitializePermissionedPool,
+    },
+    /// Enable or disable a pool. A disabled pool allow only remove balanced liquidity operation.
+    EnableOrDisablePool {
+        enable: bool,
+        accounts: accounts::EnableOrDisablePool,
+    },
+    /// Update trading fee charged for liquidity provider, and admin.
+    SetPoolFees {
+        fees: PoolFees,
+        new_partner_fee_numerator: u64,
+        accounts: accounts::SetPoolFees,
+    },
+    /// Update swap curve parameters. This function do not allow update of curve type. For example: stable swap curve to constant product curve. Only supported by pool with stable swap curve.
+    /// Only amp is allowed to be override. The other attributes of stable swap curve will be ignored.
+    OverrideCurveParam {
+        curve_type: CurveType,
+        accounts: accounts::OverrideCurveParam,
+    },
+    /// Create mint metadata account for old pools
+    CreateMintMetadata {
+        accounts: accounts::CreateMintMetadata,
+    },
+    /// Create config
+    CreateConfig {
+        config_parameters: ConfigParameters,
+        accounts: accounts::CreateConfig,
+    },
+    /// Close config
+    CloseConfig { accounts: accounts::CloseConfig },
+    /// Update activation point
+    UpdateActivationPoint {
+        new_activation_point: u64,
+        accounts: accounts::UpdateActivationPoint,
+    },
+    /// Initialize permissionless pool with config
+    InitializePermissionlessConstantProductPoolWithConfig {
+        token_a_amount: u64,
+        token_b_amount: u64,
+        accounts: accounts::InitializePermissionlessConstantProductPoolWithConfig,
+    },
+    /// Initialize permissionless pool with config 2
+    InitializePermissionlessConstantProductPoolWithConfig2 {
+        token_a_amount: u64,
+        token_b_amount: u64,
+        activation_point: Option<u64>,
+        accounts: accounts::InitializePermissionlessConstantProductPoolWithConfig,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct InnerInstructions {
+    /// Index of the transaction instruction invoking the inner instructions
+    pub index: u8,
+    /// Inner instructions, in the order they are invoked
+    pub instructions: Vec<CompiledInstruction>,
+}
+
+#[derive(Debug)]
+pub struct DecodedInstruction {
+    /// Index of the transaction instruction
+    pub instruction_index: usize,
+    /// Index in the inner instructions of the transaction instruction. None for a transaction instruction.
+    pub inner_instruction_index: Option<usize>,
+    /// Decoded instruction
+    pub instruction: DynamicAmmInstruction,
+    /// Accounts passed after the named accounts. Eg: stake accounts of depeg pools.
+    pub remaining_accounts: Vec<Pubkey>,
+}
+
+fn decode_args<T: AnchorDeserialize + Discriminator>(
+    discriminator: &[u8],
+    data: &[u8],
+) -> anyhow::Result<Option<T>> {
+    if discriminator != T::discriminator() {
+        return Ok(None);
+    }
+    let args = T::deserialize(&mut &data[..]).context("Fail to deserialize instruction args")?;
+    Ok(Some(args))
+}
+
+impl std::fmt::Debug for DynamicAmmInstruction {
+    /// The anchor client accounts don't implement Debug, they are printed as the account keys in instruction order
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        macro_rules! debug_variants {
+            ($($variant:ident { $($arg:ident),* }),* $(,)?) => {
+                match self {
+                    $(Self::$variant { $($arg,)* .. } => f
+                        .debug_struct(stringify!($variant))
+                        $(.field(stringify!($arg), $arg))*
+                        .field("accounts", &self.account_metas().iter().map(|meta| meta.pubkey).collect::<Vec<_>>())
+                        .finish(),)*
+                }
+            };
+        }
+        debug_variants!(
+            InitializePermissionlessPool {
+                curve_type,
+                token_a_amount,
+                token_b_amount
+            },
+            InitializeCustomizablePermissionlessConstantProductPool {
+                token_a_amount,
+                token_b_amount,
+                params
+            },
+            InitializePermissionlessPoolWithFeeTier {
+                curve_type,
+                trade_fee_bps,
+                token_a_amount,
+                token_b_amount
+            },
+            PartnerClaimFee {
+                max_amount_a,
+                max_amount_b
+            },
+            RemoveLiquiditySingleSide {
+                pool_token_amount,
+                minimum_out_amount
+            },
+            AddImbalanceLiquidity {
+                minimum_pool_token_amount,
+                token_a_amount,
+                token_b_amount
+            },
+            Swap {
+                in_amount,
+                minimum_out_amount
+            },
+            RemoveBalanceLiquidity {
+                pool_token_amount,
+                minimum_a_token_out,
+                minimum_b_token_out
+            },
+            AddBalanceLiquidity {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount
+            },
+            GetPoolInfo {},
+            BootstrapLiquidity {
+                token_a_amount,
+                token_b_amount
+            },
+            CreateLockEscrow {},
+            Lock { amount },
+            ClaimFee { max_amount },
+            InitializePermissionedPool { curve_type },
+            EnableOrDisablePool { enable },
+            SetPoolFees {
+                fees,
+                new_partner_fee_numerator
+            },
+            OverrideCurveParam { curve_type },
+            CreateMintMetadata {},
+            CreateConfig { config_parameters },
+            CloseConfig {},
+            UpdateActivationPoint {
+                new_activation_point
+            },
+            InitializePermissionlessConstantProductPoolWithConfig {
+                token_a_amount,
+                token_b_amount
+            },
+            InitializePermissionlessConstantProductPoolWithConfig2 {
+                token_a_amount,
+                token_b_amount,
+                activation_point
+            },
+        )
+    }
+}
+
+impl DynamicAmmInstruction {
+    /// Named accounts of the instruction, in the order the program expects them. Remaining accounts are not included.
+    pub fn account_metas(&self) -> Vec<AccountMeta> {
+        match self {
+            Self::InitializePermissionlessPool { accounts, .. } => accounts.to_account_metas(None),
+            Self::InitializeCustomizablePermissionlessConstantProductPool { accounts, .. } => {
+                accounts.to_account_metas(None)
+            }
+            Self::InitializePermissionlessPoolWithFeeTier { accounts, .. } => {
+                accounts.to_account_metas(None)
+            }
+            Self::PartnerClaimFee { accounts, .. } => accounts.to_account_metas(None),
+            Self::RemoveLiquiditySingleSide { accounts, .. } => accounts.to_account_metas(None),
+            Self::AddImbalanceLiquidity { accounts, .. } => accounts.to_account_metas(None),
+            Self::Swap { accounts, .. } => accounts.to_account_metas(None),
+            Self::RemoveBalanceLiquidity { accounts, .. } => accounts.to_account_metas(None),
+            Self::AddBalanceLiquidity { accounts, .. } => accounts.to_account_metas(None),
+            Self::GetPoolInfo { accounts, .. } => accounts.to_account_metas(None),
+            Self::BootstrapLiquidity { accounts, .. } => accounts.to_account_metas(None),
+            Self::CreateLockEscrow { accounts, .. } => accounts.to_account_metas(None),
+            Self::Lock { accounts, .. } => accounts.to_account_metas(None),
+            Self::ClaimFee { accounts, .. } => accounts.to_account_metas(None),
+            Self::InitializePermissionedPool { accounts, .. } => accounts.to_account_metas(None),
+            Self::EnableOrDisablePool { accounts, .. } => accounts.to_account_metas(None),
+            Self::SetPoolFees { accounts, .. } => accounts.to_account_metas(None),
+            Self::OverrideCurveParam { accounts, .. } => accounts.to_account_metas(None),
+            Self::CreateMintMetadata { accounts, .. } => accounts.to_account_metas(None),
+            Self::CreateConfig { accounts, .. } => accounts.to_account_metas(None),
+            Self::CloseConfig { accounts, .. } => accounts.to_account_metas(None),
+            Self::UpdateActivationPoint { accounts, .. } => accounts.to_account_metas(None),
+            Self::InitializePermissionlessConstantProductPoolWithConfig { accounts, .. } => {
+                accounts.to_account_metas(None)
+            }
+            Self::InitializePermissionlessConstantProductPoolWithConfig2 { accounts, .. } => {
+                accounts.to_account_metas(None)
+            }
+        }
+    }
+
+    /// Instruction name, as declared in the program
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::InitializePermissionlessPool { .. } => "initialize_permissionless_pool",
+            Self::InitializeCustomizablePermissionlessConstantProductPool { .. } => {
+                "initialize_customizable_permissionless_constant_product_pool"
+            }
+            Self::InitializePermissionlessPoolWithFeeTier { .. } => {
+                "initialize_permissionless_pool_with_fee_tier"
+            }
+            Self::PartnerClaimFee { .. } => "partner_claim_fee",
+            Self::RemoveLiquiditySingleSide { .. } => "remove_liquidity_single_side",
+            Self::AddImbalanceLiquidity { .. } => "add_imbalance_liquidity",
+            Self::Swap { .. } => "swap",
+            Self::RemoveBalanceLiquidity { .. } => "remove_balance_liquidity",
+            Self::AddBalanceLiquidity { .. } => "add_balance_liquidity",
+            Self::GetPoolInfo { .. } => "get_pool_info",
+            Self::BootstrapLiquidity { .. } => "bootstrap_liquidity",
+            Self::CreateLockEscrow { .. } => "create_lock_escrow",
+            Self::Lock { .. } => "lock",
+            Self::ClaimFee { .. } => "claim_fee",
+            Self::InitializePermissionedPool { .. } => "initialize_permissioned_pool",
+            Self::EnableOrDisablePool { .. } => "enable_or_disable_pool",
+            Self::SetPoolFees { .. } => "set_pool_fees",
+            Self::OverrideCurveParam { .. } => "override_curve_param",
+            Self::CreateMintMetadata { .. } => "create_mint_metadata",
+            Self::CreateConfig { .. } => "create_config",
+            Self::CloseConfig { .. } => "close_config",
+            Self::UpdateActivationPoint { .. } => "update_activation_point",
+            Self::InitializePermissionlessConstantProductPoolWithConfig { .. } => {
+                "initialize_permissionless_constant_product_pool_with_config"
+            }
+            Self::InitializePermissionlessConstantProductPoolWithConfig2 { .. } => {
+                "initialize_permissionless_constant_product_pool_with_config2"
+            }
+        }
+    }
+
+    /// Decode the instruction data, prefixed by the 8 bytes instruction discriminator, and its accounts. Return None when the data doesn't match any dynamic amm instruction, and the remaining accounts.
+    pub fn decode(data: &[u8], accounts: &[Pubkey]) -> anyhow::Result<Option<(Self, Vec<Pubkey>)>> {
+        if data.len() < 8 {
+            return Ok(None);
+        }
+        let (discriminator, data) = data.split_at(8);
+
+        if let Some(instruction::InitializePermissionlessPool {
+            curve_type,
+            token_a_amount,
+            token_b_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 26,
+                "Not enough accounts for initialize_permissionless_pool"
+            );
+            let instruction = Self::InitializePermissionlessPool {
+                curve_type,
+                token_a_amount,
+                token_b_amount,
+                accounts: accounts::InitializePermissionlessPool {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    token_a_mint: accounts[2],
+                    token_b_mint: accounts[3],
+                    a_vault: accounts[4],
+                    b_vault: accounts[5],
+                    a_token_vault: accounts[6],
+                    b_token_vault: accounts[7],
+                    a_vault_lp_mint: accounts[8],
+                    b_vault_lp_mint: accounts[9],
+                    a_vault_lp: accounts[10],
+                    b_vault_lp: accounts[11],
+                    payer_token_a: accounts[12],
+                    payer_token_b: accounts[13],
+                    payer_pool_lp: accounts[14],
+                    protocol_token_a_fee: accounts[15],
+                    protocol_token_b_fee: accounts[16],
+                    payer: accounts[17],
+                    fee_owner: accounts[18],
+                    rent: accounts[19],
+                    mint_metadata: accounts[20],
+                    metadata_program: accounts[21],
+                    vault_program: accounts[22],
+                    token_program: accounts[23],
+                    associated_token_program: accounts[24],
+                    system_program: accounts[25],
+                },
+            };
+            return Ok(Some((instruction, accounts[26..].to_vec())));
+        }
+
+        if let Some(instruction::InitializeCustomizablePermissionlessConstantProductPool {
+            token_a_amount,
+            token_b_amount,
+            params,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(accounts.len() >= 25, "Not enough accounts for initialize_customizable_permissionless_constant_product_pool");
+            let instruction = Self::InitializeCustomizablePermissionlessConstantProductPool {
+                token_a_amount,
+                token_b_amount,
+                params,
+                accounts: accounts::InitializeCustomizablePermissionlessConstantProductPool {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    token_a_mint: accounts[2],
+                    token_b_mint: accounts[3],
+                    a_vault: accounts[4],
+                    b_vault: accounts[5],
+                    a_token_vault: accounts[6],
+                    b_token_vault: accounts[7],
+                    a_vault_lp_mint: accounts[8],
+                    b_vault_lp_mint: accounts[9],
+                    a_vault_lp: accounts[10],
+                    b_vault_lp: accounts[11],
+                    payer_token_a: accounts[12],
+                    payer_token_b: accounts[13],
+                    payer_pool_lp: accounts[14],
+                    protocol_token_a_fee: accounts[15],
+                    protocol_token_b_fee: accounts[16],
+                    payer: accounts[17],
+                    rent: accounts[18],
+                    mint_metadata: accounts[19],
+                    metadata_program: accounts[20],
+                    vault_program: accounts[21],
+                    token_program: accounts[22],
+                    associated_token_program: accounts[23],
+                    system_program: accounts[24],
+                },
+            };
+            return Ok(Some((instruction, accounts[25..].to_vec())));
+        }
+
+        if let Some(instruction::InitializePermissionlessPoolWithFeeTier {
+            curve_type,
+            trade_fee_bps,
+            token_a_amount,
+            token_b_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 26,
+                "Not enough accounts for initialize_permissionless_pool_with_fee_tier"
+            );
+            let instruction = Self::InitializePermissionlessPoolWithFeeTier {
+                curve_type,
+                trade_fee_bps,
+                token_a_amount,
+                token_b_amount,
+                accounts: accounts::InitializePermissionlessPoolWithFeeTier {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    token_a_mint: accounts[2],
+                    token_b_mint: accounts[3],
+                    a_vault: accounts[4],
+                    b_vault: accounts[5],
+                    a_token_vault: accounts[6],
+                    b_token_vault: accounts[7],
+                    a_vault_lp_mint: accounts[8],
+                    b_vault_lp_mint: accounts[9],
+                    a_vault_lp: accounts[10],
+                    b_vault_lp: accounts[11],
+                    payer_token_a: accounts[12],
+                    payer_token_b: accounts[13],
+                    payer_pool_lp: accounts[14],
+                    protocol_token_a_fee: accounts[15],
+                    protocol_token_b_fee: accounts[16],
+                    payer: accounts[17],
+                    fee_owner: accounts[18],
+                    rent: accounts[19],
+                    mint_metadata: accounts[20],
+                    metadata_program: accounts[21],
+                    vault_program: accounts[22],
+                    token_program: accounts[23],
+                    associated_token_program: accounts[24],
+                    system_program: accounts[25],
+                },
+            };
+            return Ok(Some((instruction, accounts[26..].to_vec())));
+        }
+
+        if let Some(instruction::PartnerClaimFee {
+            max_amount_a,
+            max_amount_b,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 8,
+                "Not enough accounts for partner_claim_fee"
+            );
+            let instruction = Self::PartnerClaimFee {
+                max_amount_a,
+                max_amount_b,
+                accounts: accounts::PartnerClaimFees {
+                    pool: accounts[0],
+                    a_vault_lp: accounts[1],
+                    protocol_token_a_fee: accounts[2],
+                    protocol_token_b_fee: accounts[3],
+                    partner_token_a: accounts[4],
+                    partner_token_b: accounts[5],
+                    token_program: accounts[6],
+                    partner_authority: accounts[7],
+                },
+            };
+            return Ok(Some((instruction, accounts[8..].to_vec())));
+        }
+
+        if let Some(instruction::RemoveLiquiditySingleSide {
+            pool_token_amount,
+            minimum_out_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 15,
+                "Not enough accounts for remove_liquidity_single_side"
+            );
+            let instruction = Self::RemoveLiquiditySingleSide {
+                pool_token_amount,
+                minimum_out_amount,
+                accounts: accounts::RemoveLiquiditySingleSide {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    user_pool_lp: accounts[2],
+                    a_vault_lp: accounts[3],
+                    b_vault_lp: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_vault_lp_mint: accounts[7],
+                    b_vault_lp_mint: accounts[8],
+                    a_token_vault: accounts[9],
+                    b_token_vault: accounts[10],
+                    user_destination_token: accounts[11],
+                    user: accounts[12],
+                    vault_program: accounts[13],
+                    token_program: accounts[14],
+                },
+            };
+            return Ok(Some((instruction, accounts[15..].to_vec())));
+        }
+
+        if let Some(instruction::AddImbalanceLiquidity {
+            minimum_pool_token_amount,
+            token_a_amount,
+            token_b_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 16,
+                "Not enough accounts for add_imbalance_liquidity"
+            );
+            let instruction = Self::AddImbalanceLiquidity {
+                minimum_pool_token_amount,
+                token_a_amount,
+                token_b_amount,
+                accounts: accounts::AddOrRemoveBalanceLiquidity {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    user_pool_lp: accounts[2],
+                    a_vault_lp: accounts[3],
+                    b_vault_lp: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_vault_lp_mint: accounts[7],
+                    b_vault_lp_mint: accounts[8],
+                    a_token_vault: accounts[9],
+                    b_token_vault: accounts[10],
+                    user_a_token: accounts[11],
+                    user_b_token: accounts[12],
+                    user: accounts[13],
+                    vault_program: accounts[14],
+                    token_program: accounts[15],
+                },
+            };
+            return Ok(Some((instruction, accounts[16..].to_vec())));
+        }
+
+        if let Some(instruction::Swap {
+            in_amount,
+            minimum_out_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(accounts.len() >= 15, "Not enough accounts for swap");
+            let instruction = Self::Swap {
+                in_amount,
+                minimum_out_amount,
+                accounts: accounts::Swap {
+                    pool: accounts[0],
+                    user_source_token: accounts[1],
+                    user_destination_token: accounts[2],
+                    a_vault: accounts[3],
+                    b_vault: accounts[4],
+                    a_token_vault: accounts[5],
+                    b_token_vault: accounts[6],
+                    a_vault_lp_mint: accounts[7],
+                    b_vault_lp_mint: accounts[8],
+                    a_vault_lp: accounts[9],
+                    b_vault_lp: accounts[10],
+                    protocol_token_fee: accounts[11],
+                    user: accounts[12],
+                    vault_program: accounts[13],
+                    token_program: accounts[14],
+                },
+            };
+            return Ok(Some((instruction, accounts[15..].to_vec())));
+        }
+
+        if let Some(instruction::RemoveBalanceLiquidity {
+            pool_token_amount,
+            minimum_a_token_out,
+            minimum_b_token_out,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 16,
+                "Not enough accounts for remove_balance_liquidity"
+            );
+            let instruction = Self::RemoveBalanceLiquidity {
+                pool_token_amount,
+                minimum_a_token_out,
+                minimum_b_token_out,
+                accounts: accounts::AddOrRemoveBalanceLiquidity {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    user_pool_lp: accounts[2],
+                    a_vault_lp: accounts[3],
+                    b_vault_lp: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_vault_lp_mint: accounts[7],
+                    b_vault_lp_mint: accounts[8],
+                    a_token_vault: accounts[9],
+                    b_token_vault: accounts[10],
+                    user_a_token: accounts[11],
+                    user_b_token: accounts[12],
+                    user: accounts[13],
+                    vault_program: accounts[14],
+                    token_program: accounts[15],
+                },
+            };
+            return Ok(Some((instruction, accounts[16..].to_vec())));
+        }
+
+        if let Some(instruction::AddBalanceLiquidity {
+            pool_token_amount,
+            maximum_token_a_amount,
+            maximum_token_b_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 16,
+                "Not enough accounts for add_balance_liquidity"
+            );
+            let instruction = Self::AddBalanceLiquidity {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+                accounts: accounts::AddOrRemoveBalanceLiquidity {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    user_pool_lp: accounts[2],
+                    a_vault_lp: accounts[3],
+                    b_vault_lp: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_vault_lp_mint: accounts[7],
+                    b_vault_lp_mint: accounts[8],
+                    a_token_vault: accounts[9],
+                    b_token_vault: accounts[10],
+                    user_a_token: accounts[11],
+                    user_b_token: accounts[12],
+                    user: accounts[13],
+                    vault_program: accounts[14],
+                    token_program: accounts[15],
+                },
+            };
+            return Ok(Some((instruction, accounts[16..].to_vec())));
+        }
+
+        if let Some(instruction::GetPoolInfo {}) = decode_args(discriminator, data)? {
+            ensure!(accounts.len() >= 8, "Not enough accounts for get_pool_info");
+            let instruction = Self::GetPoolInfo {
+                accounts: accounts::GetPoolInfo {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    a_vault_lp: accounts[2],
+                    b_vault_lp: accounts[3],
+                    a_vault: accounts[4],
+                    b_vault: accounts[5],
+                    a_vault_lp_mint: accounts[6],
+                    b_vault_lp_mint: accounts[7],
+                },
+            };
+            return Ok(Some((instruction, accounts[8..].to_vec())));
+        }
+
+        if let Some(instruction::BootstrapLiquidity {
+            token_a_amount,
+            token_b_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 16,
+                "Not enough accounts for bootstrap_liquidity"
+            );
+            let instruction = Self::BootstrapLiquidity {
+                token_a_amount,
+                token_b_amount,
+                accounts: accounts::BootstrapLiquidity {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    user_pool_lp: accounts[2],
+                    a_vault_lp: accounts[3],
+                    b_vault_lp: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_vault_lp_mint: accounts[7],
+                    b_vault_lp_mint: accounts[8],
+                    a_token_vault: accounts[9],
+                    b_token_vault: accounts[10],
+                    user_a_token: accounts[11],
+                    user_b_token: accounts[12],
+                    user: accounts[13],
+                    vault_program: accounts[14],
+                    token_program: accounts[15],
+                },
+            };
+            return Ok(Some((instruction, accounts[16..].to_vec())));
+        }
+
+        if let Some(instruction::CreateLockEscrow {}) = decode_args(discriminator, data)? {
+            ensure!(
+                accounts.len() >= 6,
+                "Not enough accounts for create_lock_escrow"
+            );
+            let instruction = Self::CreateLockEscrow {
+                accounts: accounts::CreateLockEscrow {
+                    pool: accounts[0],
+                    lock_escrow: accounts[1],
+                    owner: accounts[2],
+                    lp_mint: accounts[3],
+                    payer: accounts[4],
+                    system_program: accounts[5],
+                },
+            };
+            return Ok(Some((instruction, accounts[6..].to_vec())));
+        }
+
+        if let Some(instruction::Lock { amount }) = decode_args(discriminator, data)? {
+            ensure!(accounts.len() >= 13, "Not enough accounts for lock");
+            let instruction = Self::Lock {
+                amount,
+                accounts: accounts::Lock {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    lock_escrow: accounts[2],
+                    owner: accounts[3],
+                    source_tokens: accounts[4],
+                    escrow_vault: accounts[5],
+                    token_program: accounts[6],
+                    a_vault: accounts[7],
+                    b_vault: accounts[8],
+                    a_vault_lp: accounts[9],
+                    b_vault_lp: accounts[10],
+                    a_vault_lp_mint: accounts[11],
+                    b_vault_lp_mint: accounts[12],
+                },
+            };
+            return Ok(Some((instruction, accounts[13..].to_vec())));
+        }
+
+        if let Some(instruction::ClaimFee { max_amount }) = decode_args(discriminator, data)? {
+            ensure!(accounts.len() >= 18, "Not enough accounts for claim_fee");
+            let instruction = Self::ClaimFee {
+                max_amount,
+                accounts: accounts::ClaimFee {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    lock_escrow: accounts[2],
+                    owner: accounts[3],
+                    source_tokens: accounts[4],
+                    escrow_vault: accounts[5],
+                    token_program: accounts[6],
+                    a_token_vault: accounts[7],
+                    b_token_vault: accounts[8],
+                    a_vault: accounts[9],
+                    b_vault: accounts[10],
+                    a_vault_lp: accounts[11],
+                    b_vault_lp: accounts[12],
+                    a_vault_lp_mint: accounts[13],
+                    b_vault_lp_mint: accounts[14],
+                    user_a_token: accounts[15],
+                    user_b_token: accounts[16],
+                    vault_program: accounts[17],
+                },
+            };
+            return Ok(Some((instruction, accounts[18..].to_vec())));
+        }
+
+        if let Some(instruction::InitializePermissionedPool { curve_type }) =
+            decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 24,
+                "Not enough accounts for initialize_permissioned_pool"
+            );
+            let instruction = Self::InitializePermissionedPool {
+                curve_type,
+                accounts: accounts::InitializePermissionedPool {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    token_a_mint: accounts[2],
+                    token_b_mint: accounts[3],
+                    a_vault: accounts[4],
+                    b_vault: accounts[5],
+                    a_vault_lp_mint: accounts[6],
+                    b_vault_lp_mint: accounts[7],
+                    a_vault_lp: accounts[8],
+                    b_vault_lp: accounts[9],
+                    admin_token_a: accounts[10],
+                    admin_token_b: accounts[11],
+                    admin_pool_lp: accounts[12],
+                    protocol_token_a_fee: accounts[13],
+                    protocol_token_b_fee: accounts[14],
+                    admin: accounts[15],
+                    fee_owner: accounts[16],
+                    rent: accounts[17],
+                    mint_metadata: accounts[18],
+                    metadata_program: accounts[19],
+                    vault_program: accounts[20],
+                    token_program: accounts[21],
+                    associated_token_program: accounts[22],
+                    system_program: accounts[23],
+                },
+            };
+            return Ok(Some((instruction, accounts[24..].to_vec())));
+        }
+
+        if let Some(instruction::EnableOrDisablePool { enable }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 2,
+                "Not enough accounts for enable_or_disable_pool"
+            );
+            let instruction = Self::EnableOrDisablePool {
+                enable,
+                accounts: accounts::EnableOrDisablePool {
+                    pool: accounts[0],
+                    admin: accounts[1],
+                },
+            };
+            return Ok(Some((instruction, accounts[2..].to_vec())));
+        }
+
+        if let Some(instruction::SetPoolFees {
+            fees,
+            new_partner_fee_numerator,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(accounts.len() >= 2, "Not enough accounts for set_pool_fees");
+            let instruction = Self::SetPoolFees {
+                fees,
+                new_partner_fee_numerator,
+                accounts: accounts::SetPoolFees {
+                    pool: accounts[0],
+                    fee_operator: accounts[1],
+                },
+            };
+            return Ok(Some((instruction, accounts[2..].to_vec())));
+        }
+
+        if let Some(instruction::OverrideCurveParam { curve_type }) =
+            decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 2,
+                "Not enough accounts for override_curve_param"
+            );
+            let instruction = Self::OverrideCurveParam {
+                curve_type,
+                accounts: accounts::OverrideCurveParam {
+                    pool: accounts[0],
+                    admin: accounts[1],
+                },
+            };
+            return Ok(Some((instruction, accounts[2..].to_vec())));
+        }
+
+        if let Some(instruction::CreateMintMetadata {}) = decode_args(discriminator, data)? {
+            ensure!(
+                accounts.len() >= 7,
+                "Not enough accounts for create_mint_metadata"
+            );
+            let instruction = Self::CreateMintMetadata {
+                accounts: accounts::CreateMintMetadata {
+                    pool: accounts[0],
+                    lp_mint: accounts[1],
+                    a_vault_lp: accounts[2],
+                    mint_metadata: accounts[3],
+                    metadata_program: accounts[4],
+                    system_program: accounts[5],
+                    payer: accounts[6],
+                },
+            };
+            return Ok(Some((instruction, accounts[7..].to_vec())));
+        }
+
+        if let Some(instruction::CreateConfig { config_parameters }) =
+            decode_args(discriminator, data)?
+        {
+            ensure!(accounts.len() >= 3, "Not enough accounts for create_config");
+            let instruction = Self::CreateConfig {
+                config_parameters,
+                accounts: accounts::CreateConfig {
+                    config: accounts[0],
+                    admin: accounts[1],
+                    system_program: accounts[2],
+                },
+            };
+            return Ok(Some((instruction, accounts[3..].to_vec())));
+        }
+
+        if let Some(instruction::CloseConfig {}) = decode_args(discriminator, data)? {
+            ensure!(accounts.len() >= 3, "Not enough accounts for close_config");
+            let instruction = Self::CloseConfig {
+                accounts: accounts::CloseConfig {
+                    config: accounts[0],
+                    admin: accounts[1],
+                    rent_receiver: accounts[2],
+                },
+            };
+            return Ok(Some((instruction, accounts[3..].to_vec())));
+        }
+
+        if let Some(instruction::UpdateActivationPoint {
+            new_activation_point,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(
+                accounts.len() >= 2,
+                "Not enough accounts for update_activation_point"
+            );
+            let instruction = Self::UpdateActivationPoint {
+                new_activation_point,
+                accounts: accounts::UpdateActivationPoint {
+                    pool: accounts[0],
+                    admin: accounts[1],
+                },
+            };
+            return Ok(Some((instruction, accounts[2..].to_vec())));
+        }
+
+        if let Some(instruction::InitializePermissionlessConstantProductPoolWithConfig {
+            token_a_amount,
+            token_b_amount,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(accounts.len() >= 26, "Not enough accounts for initialize_permissionless_constant_product_pool_with_config");
+            let instruction = Self::InitializePermissionlessConstantProductPoolWithConfig {
+                token_a_amount,
+                token_b_amount,
+                accounts: accounts::InitializePermissionlessConstantProductPoolWithConfig {
+                    pool: accounts[0],
+                    config: accounts[1],
+                    lp_mint: accounts[2],
+                    token_a_mint: accounts[3],
+                    token_b_mint: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_token_vault: accounts[7],
+                    b_token_vault: accounts[8],
+                    a_vault_lp_mint: accounts[9],
+                    b_vault_lp_mint: accounts[10],
+                    a_vault_lp: accounts[11],
+                    b_vault_lp: accounts[12],
+                    payer_token_a: accounts[13],
+                    payer_token_b: accounts[14],
+                    payer_pool_lp: accounts[15],
+                    protocol_token_a_fee: accounts[16],
+                    protocol_token_b_fee: accounts[17],
+                    payer: accounts[18],
+                    rent: accounts[19],
+                    mint_metadata: accounts[20],
+                    metadata_program: accounts[21],
+                    vault_program: accounts[22],
+                    token_program: accounts[23],
+                    associated_token_program: accounts[24],
+                    system_program: accounts[25],
+                },
+            };
+            return Ok(Some((instruction, accounts[26..].to_vec())));
+        }
+
+        if let Some(instruction::InitializePermissionlessConstantProductPoolWithConfig2 {
+            token_a_amount,
+            token_b_amount,
+            activation_point,
+        }) = decode_args(discriminator, data)?
+        {
+            ensure!(accounts.len() >= 26, "Not enough accounts for initialize_permissionless_constant_product_pool_with_config2");
+            let instruction = Self::InitializePermissionlessConstantProductPoolWithConfig2 {
+                token_a_amount,
+                token_b_amount,
+                activation_point,
+                accounts: accounts::InitializePermissionlessConstantProductPoolWithConfig {
+                    pool: accounts[0],
+                    config: accounts[1],
+                    lp_mint: accounts[2],
+                    token_a_mint: accounts[3],
+                    token_b_mint: accounts[4],
+                    a_vault: accounts[5],
+                    b_vault: accounts[6],
+                    a_token_vault: accounts[7],
+                    b_token_vault: accounts[8],
+                    a_vault_lp_mint: accounts[9],
+                    b_vault_lp_mint: accounts[10],
+                    a_vault_lp: accounts[11],
+                    b_vault_lp: accounts[12],
+                    payer_token_a: accounts[13],
+                    payer_token_b: accounts[14],
+                    payer_pool_lp: accounts[15],
+                    protocol_token_a_fee: accounts[16],
+                    protocol_token_b_fee: accounts[17],
+                    payer: accounts[18],
+                    rent: accounts[19],
+                    mint_metadata: accounts[20],
+                    metadata_program: accounts[21],
+                    vault_program: accounts[22],
+                    token_program: accounts[23],
+                    associated_token_program: accounts[24],
+                    system_program: accounts[25],
+                },
+            };
+            return Ok(Some((instruction, accounts[26..].to_vec())));
+        }
+
+        Ok(None)
+    }
+}
+
+fn decode_compiled_instruction(
+    program_id: &Pubkey,
+    account_keys: &[Pubkey],
+    instruction: &CompiledInstruction,
+) -> anyhow::Result<Option<(DynamicAmmInstruction, Vec<Pubkey>)>> {
+    let instruction_program_id = account_keys
+        .get(usize::from(instruction.program_id_index))
+        .context("Program id index out of bound")?;
+    if instruction_program_id != program_id {
+        return Ok(None);
+    }
+    let accounts = instruction
+        .accounts
+        .iter()
+        .map(|index| {
+            account_keys
+                .get(usize::from(*index))
+                .copied()
+                .context("Account index out of bound")
+        })
+        .collect::<anyhow::Result<Vec<Pubkey>>>()?;
+    DynamicAmmInstruction::decode(&instruction.data, &accounts)
+}
+
+/// Error decoding a dynamic amm instruction of a transaction, eg: truncated data or missing accounts
+#[derive(Debug)]
+pub struct DecodeError {
+    /// Index of the transaction instruction
+    pub instruction_index: usize,
+    /// Index in the inner instructions of the transaction instruction. None for a transaction instruction.
+    pub inner_instruction_index: Option<usize>,
+    /// Cause of the failure
+    pub error: anyhow::Error,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.inner_instruction_index {
+            Some(inner_instruction_index) => write!(
+                f,
+                "Fail to decode inner instruction {} of instruction {}: {:#}",
+                inner_instruction_index, self.instruction_index, self.error
+            ),
+            None => write!(
+                f,
+                "Fail to decode instruction {}: {:#}",
+                self.instruction_index, self.error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Decode all the `program_id` instructions of a transaction, including inner instructions, in the order they are executed.
+/// `account_keys` are the static account keys of the message, followed by the writable and the readonly addresses loaded from lookup tables.
+/// An instruction failing to decode is returned as an error in place, and doesn't prevent decoding the other instructions.
+pub fn decode_transaction(
+    program_id: &Pubkey,
+    account_keys: &[Pubkey],
+    instructions: &[CompiledInstruction],
+    inner_instructions: &[InnerInstructions],
+) -> Vec<Result<DecodedInstruction, DecodeError>> {
+    let mut decoded_instructions = vec![];
+
+    let mut decode = |instruction_index: usize,
+                      inner_instruction_index: Option<usize>,
+                      instruction: &CompiledInstruction| {
+        let decoded = match decode_compiled_instruction(program_id, account_keys, instruction) {
+            Ok(Some((instruction, remaining_accounts))) => Ok(DecodedInstruction {
+                instruction_index,
+                inner_instruction_index,
+                instruction,
+                remaining_accounts,
+            }),
+            Ok(None) => return,
+            Err(error) => Err(DecodeError {
+                instruction_index,
+                inner_instruction_index,
+                error,
+            }),
+        };
+        decoded_instructions.push(decoded);
+    };
+
+    for (instruction_index, instruction) in instructions.iter().enumerate() {
+        decode(instruction_index, None, instruction);
+
+        let inner_instructions = inner_instructions
+            .iter()
+            .filter(|inner| usize::from(inner.index) == instruction_index)
+            .flat_map(|inner| inner.instructions.iter());
+
+        for (inner_instruction_index, instruction) in inner_instructions.enumerate() {
+            decode(
+                instruction_index,
+                Some(inner_instruction_index),
+                instruction,
+            );
+        }
+    }
+
+    decoded_instructions
+}
@@ -17,3 +17,9 @@ pub mod stake {
     use super::*;
     declare_id!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC");
 }
+
+/// mSOL mint
+pub mod mint {
+    use super::*;
+    declare_id!("mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So");
+}
@@ -14,6 +14,19 @@ pub mod solido;
 /// SPL stake pool module consists of functions to support SPL stake pool based depeg pool operation
 pub mod spl_stake;
 
+/// Stake account the virtual price of the depeg pool is read from. None when the pool is not a depeg pool.
+pub fn get_stake_account(pool: &Pool) -> Option<Pubkey> {
+    match pool.curve_type {
+        CurveType::ConstantProduct => None,
+        CurveType::Stable { depeg, .. } => match depeg.depeg_type {
+            DepegType::Lido => Some(solido::stake::ID),
+            DepegType::Marinade => Some(marinade::stake::ID),
+            DepegType::SplStake => Some(pool.stake),
+            DepegType::None => None,
+        },
+    }
+}
+
 fn get_stake_pool_virtual_price(
     depeg_type: DepegType,
     spl_stake_pool: Pubkey,
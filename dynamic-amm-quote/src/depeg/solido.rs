@@ -22,3 +22,9 @@ pub mod stake {
     use anchor_lang::prelude::declare_id;
     declare_id!("49Yi1TKkNyYjPAFdR9LBvoHcUjuPX4Df5T5yv39w2XTn");
 }
+
+/// stSOL mint
+pub mod mint {
+    use anchor_lang::prelude::declare_id;
+    declare_id!("7dHbWXmci3dT8UFYWYZweBLXgycu7Y3iL6trKn1Y4ARj");
+}
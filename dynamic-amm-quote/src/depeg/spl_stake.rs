@@ -1,3 +1,4 @@
+use anchor_lang::prelude::Pubkey;
 use anchor_lang::solana_program::borsh0_10;
 use prog_dynamic_amm::constants::depeg;
 use spl_stake_pool::state::StakePool;
@@ -12,3 +13,9 @@ pub fn get_virtual_price(bytes: &[u8]) -> Option<u64> {
 
     virtual_price.try_into().ok()
 }
+
+/// Mint of the token staked by the stake pool
+pub fn get_pool_mint(bytes: &[u8]) -> Option<Pubkey> {
+    let stake: StakePool = borsh0_10::try_from_slice_unchecked(bytes).ok()?;
+    Some(stake.pool_mint)
+}
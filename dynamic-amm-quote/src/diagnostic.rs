@@ -0,0 +1,119 @@
+//! Mapping of the program error codes back to [PoolError], and diagnostics of the common failures
+use crate::{compute_quote, QuoteData};
+use anchor_lang::error::ERROR_CODE_OFFSET;
+use anchor_lang::prelude::*;
+use prog_dynamic_amm::error::PoolError;
+use prog_dynamic_amm::state::{ActivationType, Pool};
+
+/// [PoolError] variants, in the order of their error code
+const POOL_ERRORS: [PoolError; 53] = [
+    PoolError::MathOverflow,
+    PoolError::InvalidFee,
+    PoolError::InvalidInvariant,
+    PoolError::FeeCalculationFailure,
+    PoolError::ExceededSlippage,
+    PoolError::InvalidCalculation,
+    PoolError::ZeroTradingTokens,
+    PoolError::ConversionError,
+    PoolError::FaultyLpMint,
+    PoolError::MismatchedTokenMint,
+    PoolError::MismatchedLpMint,
+    PoolError::MismatchedOwner,
+    PoolError::InvalidVaultAccount,
+    PoolError::InvalidVaultLpAccount,
+    PoolError::InvalidPoolLpMintAccount,
+    PoolError::PoolDisabled,
+    PoolError::InvalidAdminAccount,
+    PoolError::InvalidProtocolFeeAccount,
+    PoolError::SameAdminAccount,
+    PoolError::IdenticalSourceDestination,
+    PoolError::ApyCalculationError,
+    PoolError::InsufficientSnapshot,
+    PoolError::NonUpdatableCurve,
+    PoolError::MisMatchedCurve,
+    PoolError::InvalidAmplification,
+    PoolError::UnsupportedOperation,
+    PoolError::ExceedMaxAChanges,
+    PoolError::InvalidRemainingAccountsLen,
+    PoolError::InvalidRemainingAccounts,
+    PoolError::MismatchedDepegMint,
+    PoolError::InvalidApyAccount,
+    PoolError::InvalidTokenMultiplier,
+    PoolError::InvalidDepegInformation,
+    PoolError::UpdateTimeConstraint,
+    PoolError::ExceedMaxFeeBps,
+    PoolError::InvalidAdmin,
+    PoolError::PoolIsNotPermissioned,
+    PoolError::InvalidDepositAmount,
+    PoolError::InvalidFeeOwner,
+    PoolError::NonDepletedPool,
+    PoolError::AmountNotPeg,
+    PoolError::AmountIsZero,
+    PoolError::TypeCastFailed,
+    PoolError::AmountIsNotEnough,
+    PoolError::InvalidActivationDuration,
+    PoolError::PoolIsNotLaunchPool,
+    PoolError::UnableToModifyActivationPoint,
+    PoolError::InvalidAuthorityToCreateThePool,
+    PoolError::InvalidActivationType,
+    PoolError::InvalidActivationPoint,
+    PoolError::PreActivationSwapStarted,
+    PoolError::InvalidPoolType,
+    PoolError::InvalidQuoteMint,
+];
+
+/// Map the custom program error code to [PoolError]
+pub fn get_pool_error(code: u32) -> Option<PoolError> {
+    let index = code.checked_sub(ERROR_CODE_OFFSET)?;
+    POOL_ERRORS.get(usize::try_from(index).ok()?).copied()
+}
+
+/// Current activation point of the pool, in slot or timestamp depending on the activation type
+pub fn get_current_point(pool: &Pool, clock: &Clock) -> Option<(ActivationType, u64)> {
+    let activation_type = ActivationType::try_from(pool.bootstrapping.activation_type).ok()?;
+    let current_point = match activation_type {
+        ActivationType::Slot => clock.slot,
+        ActivationType::Timestamp => clock.unix_timestamp.try_into().ok()?,
+    };
+    Some((activation_type, current_point))
+}
+
+/// Explain `error` with the pool state
+pub fn diagnose_pool_error(error: PoolError, pool: &Pool, clock: &Clock) -> String {
+    match error {
+        PoolError::PoolDisabled => {
+            "Pool is disabled by the admin. Only balanced withdraw is allowed".to_string()
+        }
+        PoolError::InvalidActivationPoint
+        | PoolError::UnableToModifyActivationPoint
+        | PoolError::PreActivationSwapStarted
+        | PoolError::InvalidActivationDuration => match get_current_point(pool, clock) {
+            Some((activation_type, current_point)) => format!(
+                "{}. Current {:?} {}, activation point {}",
+                error, activation_type, current_point, pool.bootstrapping.activation_point
+            ),
+            None => error.to_string(),
+        },
+        _ => error.to_string(),
+    }
+}
+
+/// Explain the swap `error`, by quoting the swap with the current pool state
+pub fn diagnose_swap_error(
+    error: PoolError,
+    in_token_mint: Pubkey,
+    in_amount: u64,
+    minimum_out_amount: u64,
+    quote_data: QuoteData,
+) -> String {
+    match error {
+        PoolError::ExceededSlippage => match compute_quote(in_token_mint, in_amount, quote_data) {
+            Ok(quote) => format!(
+                "{}. Quoted out amount {}, minimum out amount {}",
+                error, quote.out_amount, minimum_out_amount
+            ),
+            Err(e) => format!("{}. Fail to quote: {}", error, e),
+        },
+        _ => diagnose_pool_error(error, &quote_data.pool, &quote_data.clock),
+    }
+}
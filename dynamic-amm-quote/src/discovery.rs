@@ -0,0 +1,101 @@
+//! Memcmp filters of getProgramAccounts, to search the dynamic amm accounts by field, and the matching of the pools to their config
+use crate::pda::derive_constant_product_pool_with_config;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorSerialize, Discriminator};
+use prog_dynamic_amm::state::PoolType;
+use std::collections::BTreeMap;
+
+/// Offset of `Pool::token_a_mint`, after the 8 bytes discriminator and `Pool::lp_mint`
+pub const POOL_TOKEN_A_MINT_OFFSET: usize = 8 + 32;
+/// Offset of `Pool::token_b_mint`
+pub const POOL_TOKEN_B_MINT_OFFSET: usize = POOL_TOKEN_A_MINT_OFFSET + 32;
+/// Offset of `Pool::pool_type`, after the pool addresses and mints, `a_vault_lp_bump`, `enabled`, protocol fee accounts, `fee_last_updated_at`, `_padding0` and `fees`
+pub const POOL_TYPE_OFFSET: usize = 8 + 32 * 7 + 1 + 1 + 32 * 2 + 8 + 24 + 32;
+/// Offset of `Pool::partner_info.partner_authority`. Pool addresses and mints, `a_vault_lp_bump`, `enabled`, protocol fee accounts, `fee_last_updated_at`, `_padding0`, `fees`, `pool_type`, `stake`, `total_locked_lp`, `bootstrapping` and `partner_info.fee_numerator` come before it
+pub const POOL_PARTNER_AUTHORITY_OFFSET: usize =
+    8 + 32 * 7 + 1 + 1 + 32 * 2 + 8 + 24 + 32 + 1 + 32 + 8 + (8 + 32 + 32 + 1) + 8;
+/// Offset of `Config::pool_creator_authority`, after the 8 bytes discriminator, `Config::pool_fees`, `Config::activation_duration` and `Config::vault_config_key`
+pub const CONFIG_POOL_CREATOR_AUTHORITY_OFFSET: usize = 8 + 32 + 8 + 32;
+
+/// Match the accounts having `bytes` at `offset` of their data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemcmpFilter {
+    pub offset: usize,
+    pub bytes: Vec<u8>,
+}
+
+impl MemcmpFilter {
+    pub fn new(offset: usize, bytes: Vec<u8>) -> Self {
+        MemcmpFilter { offset, bytes }
+    }
+
+    /// Match the `T` accounts
+    pub fn discriminator<T: Discriminator>() -> Self {
+        MemcmpFilter::new(0, T::discriminator().to_vec())
+    }
+
+    /// Whether the account data matches the filter. Data too short to hold the bytes doesn't match, same as the RPC node.
+    pub fn matches(&self, data: &[u8]) -> bool {
+        self.offset
+            .checked_add(self.bytes.len())
+            .and_then(|end| data.get(self.offset..end))
+            == Some(self.bytes.as_slice())
+    }
+}
+
+/// Filters matching the pools having `token_mint` as token A, and as token B. A single getProgramAccounts can't OR them, each is a separate query.
+pub fn get_pool_token_mint_filters(token_mint: &Pubkey) -> [MemcmpFilter; 2] {
+    [POOL_TOKEN_A_MINT_OFFSET, POOL_TOKEN_B_MINT_OFFSET]
+        .map(|offset| MemcmpFilter::new(offset, token_mint.to_bytes().to_vec()))
+}
+
+/// Filter matching the pools of `pool_type`
+pub fn get_pool_type_filter(pool_type: PoolType) -> anyhow::Result<MemcmpFilter> {
+    Ok(MemcmpFilter::new(POOL_TYPE_OFFSET, pool_type.try_to_vec()?))
+}
+
+/// Filter matching the pools where `partner_authority` is the partner
+pub fn get_pool_partner_authority_filter(partner_authority: &Pubkey) -> MemcmpFilter {
+    MemcmpFilter::new(
+        POOL_PARTNER_AUTHORITY_OFFSET,
+        partner_authority.to_bytes().to_vec(),
+    )
+}
+
+/// Filter matching the configs restricted to `pool_creator_authority`
+pub fn get_config_pool_creator_authority_filter(pool_creator_authority: &Pubkey) -> MemcmpFilter {
+    MemcmpFilter::new(
+        CONFIG_POOL_CREATOR_AUTHORITY_OFFSET,
+        pool_creator_authority.to_bytes().to_vec(),
+    )
+}
+
+/// Find the config the pool was created with, among `configs`. Pools don't store their config, a pool belongs to the config
+/// when its address is derived from the config. The pool fees are not compared, they can be changed after the pool creation.
+/// None for a pool created without config, or from a config missing in `configs`.
+pub fn find_pool_config(
+    pool_key: Pubkey,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    configs: &[Pubkey],
+) -> Option<Pubkey> {
+    configs.iter().copied().find(|config| {
+        derive_constant_product_pool_with_config(token_a_mint, token_b_mint, *config) == pool_key
+    })
+}
+
+/// Group the pools by the config they were created with, among `configs`, as matched by [find_pool_config]. `pools` are the
+/// pool addresses with their token A and B mints.
+pub fn group_pools_by_config(
+    pools: &[(Pubkey, Pubkey, Pubkey)],
+    configs: &[Pubkey],
+) -> BTreeMap<Pubkey, Vec<Pubkey>> {
+    let mut result: BTreeMap<Pubkey, Vec<Pubkey>> =
+        configs.iter().map(|config| (*config, vec![])).collect();
+    for (pool_key, token_a_mint, token_b_mint) in pools.iter() {
+        if let Some(config) = find_pool_config(*pool_key, *token_a_mint, *token_b_mint, configs) {
+            result.entry(config).or_default().push(*pool_key);
+        }
+    }
+    result
+}
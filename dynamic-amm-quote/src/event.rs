@@ -0,0 +1,199 @@
+//! Decoding of the events emitted by the dynamic amm program
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use prog_dynamic_amm::event::*;
+use std::str::FromStr;
+
+/// Prefix of the log holding the event data
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+#[derive(Debug, Clone)]
+pub enum DynamicAmmEvent {
+    /// Add liquidity event
+    AddLiquidity(AddLiquidity),
+    /// Remove liquidity event
+    RemoveLiquidity(RemoveLiquidity),
+    /// Bootstrap liquidity event
+    BootstrapLiquidity(BootstrapLiquidity),
+    /// Swap event
+    Swap(Swap),
+    /// Set pool fees event
+    SetPoolFees(SetPoolFees),
+    /// Pool info event
+    PoolInfo(PoolInfo),
+    /// Transfer admin event
+    TransferAdmin(TransferAdmin),
+    /// Override curve param event
+    OverrideCurveParam(OverrideCurveParam),
+    /// New pool created event
+    PoolCreated(PoolCreated),
+    /// Pool enabled state change event
+    PoolEnabled(PoolEnabled),
+    /// Migrate fee account event
+    MigrateFeeAccount(MigrateFeeAccount),
+    /// Create lock escrow
+    CreateLockEscrow(CreateLockEscrow),
+    /// Lock
+    Lock(Lock),
+    /// Claim fee
+    ClaimFee(ClaimFee),
+    /// Create config
+    CreateConfig(CreateConfig),
+    /// Close config
+    CloseConfig(CloseConfig),
+    /// Withdraw protocol fees
+    WithdrawProtocolFees(WithdrawProtocolFees),
+    /// Partner claim fees
+    PartnerClaimFees(PartnerClaimFees),
+}
+
+fn decode_event<T: AnchorDeserialize + Discriminator>(
+    discriminator: &[u8],
+    data: &[u8],
+) -> Option<T> {
+    if discriminator != T::discriminator() {
+        return None;
+    }
+    T::try_from_slice(data).ok()
+}
+
+impl DynamicAmmEvent {
+    /// Decode the event data, prefixed by the 8 bytes event discriminator
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, data) = data.split_at(8);
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::AddLiquidity(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::RemoveLiquidity(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::BootstrapLiquidity(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::Swap(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::SetPoolFees(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::PoolInfo(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::TransferAdmin(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::OverrideCurveParam(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::PoolCreated(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::PoolEnabled(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::MigrateFeeAccount(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::CreateLockEscrow(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::Lock(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::ClaimFee(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::CreateConfig(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::CloseConfig(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::WithdrawProtocolFees(event));
+        }
+        if let Some(event) = decode_event(discriminator, data) {
+            return Some(Self::PartnerClaimFees(event));
+        }
+        None
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedEvent {
+    /// Decoded event
+    pub event: DynamicAmmEvent,
+    /// Invoke depth of the program emitting the event. 1 is the top level instruction, 2 and above are CPI.
+    pub cpi_depth: usize,
+    /// Index of the event log in the transaction logs
+    pub log_index: usize,
+}
+
+enum ProgramLogStatus {
+    Invoke,
+    Exit,
+}
+
+/// Parse `Program <program_id> invoke [<depth>]`, `Program <program_id> success` and `Program <program_id> failed: <error>` logs.
+/// Other logs, including program logs starting with "Program" like "Program log: ...", return None.
+fn parse_program_log(log: &str) -> Option<(Pubkey, ProgramLogStatus)> {
+    let mut tokens = log.split_whitespace();
+    if tokens.next()? != "Program" {
+        return None;
+    }
+    let program_id = Pubkey::from_str(tokens.next()?).ok()?;
+    let status = match (tokens.next()?, tokens.next()) {
+        ("invoke", Some(depth)) => {
+            depth
+                .strip_prefix('[')?
+                .strip_suffix(']')?
+                .parse::<usize>()
+                .ok()?;
+            ProgramLogStatus::Invoke
+        }
+        ("success", None) => ProgramLogStatus::Exit,
+        ("failed:", Some(_)) => ProgramLogStatus::Exit,
+        _ => return None,
+    };
+    Some((program_id, status))
+}
+
+/// Parse all the events emitted by `program_id` from the transaction logs, in the order they are emitted
+pub fn parse_events(logs: &[String], program_id: Pubkey) -> Vec<ParsedEvent> {
+    let mut execution_stack: Vec<Pubkey> = vec![];
+    let mut events = vec![];
+
+    for (log_index, log) in logs.iter().enumerate() {
+        if let Some(log_info) = log.strip_prefix(PROGRAM_DATA_PREFIX) {
+            if execution_stack.last() != Some(&program_id) {
+                continue;
+            }
+            let Ok(data) = anchor_lang::__private::base64::decode(log_info.as_bytes()) else {
+                continue;
+            };
+            if let Some(event) = DynamicAmmEvent::decode(&data) {
+                events.push(ParsedEvent {
+                    event,
+                    cpi_depth: execution_stack.len(),
+                    log_index,
+                });
+            }
+            continue;
+        }
+
+        let Some((id, status)) = parse_program_log(log) else {
+            continue;
+        };
+        match status {
+            ProgramLogStatus::Invoke => execution_stack.push(id),
+            ProgramLogStatus::Exit => {
+                execution_stack.pop();
+            }
+        }
+    }
+
+    events
+}
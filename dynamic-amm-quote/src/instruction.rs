@@ -0,0 +1,898 @@
+//! Instruction builders of the dynamic amm program. The builders only resolve the accounts from the loaded states, they don't read any keypair nor call any RPC.
+//! User token accounts are the associated token accounts of the user.
+use crate::depeg::get_stake_account;
+use crate::pda::*;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::{system_instruction, sysvar};
+use anchor_lang::{system_program, InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::{self, get_associated_token_address};
+use anchor_spl::token;
+use anchor_spl::token::spl_token::native_mint;
+use anyhow::ensure;
+use prog_dynamic_amm::instructions::{ConfigParameters, CustomizableParams};
+use prog_dynamic_amm::state::{CurveType, Pool, PoolFees};
+use prog_dynamic_vault::state::Vault;
+
+/// Vault initialization, required before creating a pool with a token not having a vault yet
+pub fn initialize_vault(token_mint: Pubkey, payer: Pubkey) -> Instruction {
+    let vault = derive_vault_address(&token_mint);
+    Instruction {
+        program_id: prog_dynamic_vault::ID,
+        accounts: prog_dynamic_vault::accounts::Initialize {
+            vault,
+            token_vault: derive_token_vault_address(&vault),
+            token_mint,
+            token_program: token::ID,
+            lp_mint: derive_vault_lp_mint_address(&vault),
+            rent: sysvar::rent::ID,
+            system_program: system_program::ID,
+            payer,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_vault::instruction::Initialize {}.data(),
+    }
+}
+
+pub fn create_config(admin: Pubkey, config_parameters: ConfigParameters) -> Instruction {
+    Instruction {
+        program_id: prog_dynamic_amm::ID,
+        accounts: prog_dynamic_amm::accounts::CreateConfig {
+            config: derive_config_address(config_parameters.index),
+            admin,
+            system_program: system_program::ID,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::CreateConfig { config_parameters }.data(),
+    }
+}
+
+pub fn close_config(admin: Pubkey, config: Pubkey, rent_receiver: Pubkey) -> Instruction {
+    Instruction {
+        program_id: prog_dynamic_amm::ID,
+        accounts: prog_dynamic_amm::accounts::CloseConfig {
+            config,
+            admin,
+            rent_receiver,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::CloseConfig {}.data(),
+    }
+}
+
+/// Claim the partner fee, signed by the pool partner authority. Unlike the other pool instructions, the vault states are not needed.
+pub fn partner_claim_fee(
+    pool_key: Pubkey,
+    pool: &Pool,
+    max_amount_a: u64,
+    max_amount_b: u64,
+) -> Instruction {
+    let partner_authority = pool.partner_info.partner_authority;
+    Instruction {
+        program_id: prog_dynamic_amm::ID,
+        accounts: prog_dynamic_amm::accounts::PartnerClaimFees {
+            pool: pool_key,
+            a_vault_lp: pool.a_vault_lp,
+            protocol_token_a_fee: pool.protocol_token_a_fee,
+            protocol_token_b_fee: pool.protocol_token_b_fee,
+            partner_token_a: get_associated_token_address(&partner_authority, &pool.token_a_mint),
+            partner_token_b: get_associated_token_address(&partner_authority, &pool.token_b_mint),
+            token_program: token::ID,
+            partner_authority,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_amm::instruction::PartnerClaimFee {
+            max_amount_a,
+            max_amount_b,
+        }
+        .data(),
+    }
+}
+
+/// Create the associated token account of `owner` for `mint`. Does nothing when the account exists
+pub fn create_associated_token_account_idempotent(
+    payer: Pubkey,
+    owner: Pubkey,
+    mint: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: associated_token::ID,
+        accounts: vec![
+            AccountMeta::new(payer, true),
+            AccountMeta::new(get_associated_token_address(&owner, &mint), false),
+            AccountMeta::new_readonly(owner, false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        // AssociatedTokenAccountInstruction::CreateIdempotent
+        data: vec![1],
+    }
+}
+
+/// Wrap `amount` lamports into the wSOL associated token account of `owner`, created when missing. Mirrors `wrapSOLInstruction` of the TS client
+pub fn wrap_sol(owner: Pubkey, amount: u64) -> Vec<Instruction> {
+    let user_wsol = get_associated_token_address(&owner, &native_mint::ID);
+    vec![
+        create_associated_token_account_idempotent(owner, owner, native_mint::ID),
+        system_instruction::transfer(&owner, &user_wsol, amount),
+        token::spl_token::instruction::sync_native(&token::ID, &user_wsol)
+            .expect("Invalid sync native instruction"),
+    ]
+}
+
+/// Close the wSOL associated token account of `owner`, unwrapping all its balance to `owner`. Mirrors `unwrapSOLInstruction` of the TS client
+pub fn unwrap_sol(owner: Pubkey) -> Instruction {
+    let user_wsol = get_associated_token_address(&owner, &native_mint::ID);
+    token::spl_token::instruction::close_account(&token::ID, &user_wsol, &owner, &owner, &[])
+        .expect("Invalid close account instruction")
+}
+
+/// Mints, and vaults, of a pool to be created
+#[derive(Clone, Copy)]
+pub struct NewPool<'a> {
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    /// Vault state of token A. None when the vault is not initialized yet, its lp mint is then derived.
+    pub a_vault: Option<&'a Vault>,
+    /// Vault state of token B. None when the vault is not initialized yet, its lp mint is then derived.
+    pub b_vault: Option<&'a Vault>,
+}
+
+/// Accounts shared by every pool creation instruction
+struct NewPoolKeys {
+    pool: Pubkey,
+    lp_mint: Pubkey,
+    a_vault: Pubkey,
+    b_vault: Pubkey,
+    a_token_vault: Pubkey,
+    b_token_vault: Pubkey,
+    a_vault_lp_mint: Pubkey,
+    b_vault_lp_mint: Pubkey,
+    a_vault_lp: Pubkey,
+    b_vault_lp: Pubkey,
+    protocol_token_a_fee: Pubkey,
+    protocol_token_b_fee: Pubkey,
+    mint_metadata: Pubkey,
+}
+
+impl NewPool<'_> {
+    fn get_keys(&self, pool: Pubkey) -> NewPoolKeys {
+        let a_vault = derive_vault_address(&self.token_a_mint);
+        let b_vault = derive_vault_address(&self.token_b_mint);
+        let lp_mint = derive_pool_lp_mint_address(pool);
+
+        NewPoolKeys {
+            pool,
+            lp_mint,
+            a_vault,
+            b_vault,
+            a_token_vault: self
+                .a_vault
+                .map(|vault| vault.token_vault)
+                .unwrap_or_else(|| derive_token_vault_address(&a_vault)),
+            b_token_vault: self
+                .b_vault
+                .map(|vault| vault.token_vault)
+                .unwrap_or_else(|| derive_token_vault_address(&b_vault)),
+            a_vault_lp_mint: self
+                .a_vault
+                .map(|vault| vault.lp_mint)
+                .unwrap_or_else(|| derive_vault_lp_mint_address(&a_vault)),
+            b_vault_lp_mint: self
+                .b_vault
+                .map(|vault| vault.lp_mint)
+                .unwrap_or_else(|| derive_vault_lp_mint_address(&b_vault)),
+            a_vault_lp: derive_vault_lp_token_address(a_vault, pool),
+            b_vault_lp: derive_vault_lp_token_address(b_vault, pool),
+            protocol_token_a_fee: derive_pool_fee_token_address(self.token_a_mint, pool),
+            protocol_token_b_fee: derive_pool_fee_token_address(self.token_b_mint, pool),
+            mint_metadata: derive_mint_metadata_address(lp_mint),
+        }
+    }
+
+    /// Vault initialization of the tokens not having a vault yet
+    pub fn initialize_missing_vaults(&self, payer: Pubkey) -> Vec<Instruction> {
+        let mut ixs = vec![];
+        if self.a_vault.is_none() {
+            ixs.push(initialize_vault(self.token_a_mint, payer));
+        }
+        if self.b_vault.is_none() {
+            ixs.push(initialize_vault(self.token_b_mint, payer));
+        }
+        ixs
+    }
+
+    pub fn initialize_permissionless_pool(
+        &self,
+        payer: Pubkey,
+        curve_type: CurveType,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        let default_trade_fee_bps = 25;
+        let pool = derive_permissionless_pool_address(
+            curve_type,
+            self.token_a_mint,
+            self.token_b_mint,
+            default_trade_fee_bps,
+        );
+        let keys = self.get_keys(pool);
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::InitializePermissionlessPool {
+                pool: keys.pool,
+                lp_mint: keys.lp_mint,
+                token_a_mint: self.token_a_mint,
+                token_b_mint: self.token_b_mint,
+                a_vault: keys.a_vault,
+                b_vault: keys.b_vault,
+                a_token_vault: keys.a_token_vault,
+                b_token_vault: keys.b_token_vault,
+                a_vault_lp_mint: keys.a_vault_lp_mint,
+                b_vault_lp_mint: keys.b_vault_lp_mint,
+                a_vault_lp: keys.a_vault_lp,
+                b_vault_lp: keys.b_vault_lp,
+                payer_token_a: get_associated_token_address(&payer, &self.token_a_mint),
+                payer_token_b: get_associated_token_address(&payer, &self.token_b_mint),
+                payer_pool_lp: get_associated_token_address(&payer, &keys.lp_mint),
+                protocol_token_a_fee: keys.protocol_token_a_fee,
+                protocol_token_b_fee: keys.protocol_token_b_fee,
+                payer,
+                fee_owner: Pubkey::default(),
+                rent: sysvar::rent::ID,
+                mint_metadata: keys.mint_metadata,
+                metadata_program: METADATA_PROGRAM_ID,
+                vault_program: prog_dynamic_vault::ID,
+                token_program: token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::InitializePermissionlessPool {
+                curve_type,
+                token_a_amount,
+                token_b_amount,
+            }
+            .data(),
+        }
+    }
+
+    pub fn initialize_permissionless_pool_with_fee_tier(
+        &self,
+        payer: Pubkey,
+        curve_type: CurveType,
+        trade_fee_bps: u64,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        let pool = derive_permissionless_pool_address(
+            curve_type,
+            self.token_a_mint,
+            self.token_b_mint,
+            trade_fee_bps,
+        );
+        let keys = self.get_keys(pool);
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::InitializePermissionlessPoolWithFeeTier {
+                pool: keys.pool,
+                lp_mint: keys.lp_mint,
+                token_a_mint: self.token_a_mint,
+                token_b_mint: self.token_b_mint,
+                a_vault: keys.a_vault,
+                b_vault: keys.b_vault,
+                a_token_vault: keys.a_token_vault,
+                b_token_vault: keys.b_token_vault,
+                a_vault_lp_mint: keys.a_vault_lp_mint,
+                b_vault_lp_mint: keys.b_vault_lp_mint,
+                a_vault_lp: keys.a_vault_lp,
+                b_vault_lp: keys.b_vault_lp,
+                payer_token_a: get_associated_token_address(&payer, &self.token_a_mint),
+                payer_token_b: get_associated_token_address(&payer, &self.token_b_mint),
+                payer_pool_lp: get_associated_token_address(&payer, &keys.lp_mint),
+                protocol_token_a_fee: keys.protocol_token_a_fee,
+                protocol_token_b_fee: keys.protocol_token_b_fee,
+                payer,
+                fee_owner: Pubkey::default(),
+                rent: sysvar::rent::ID,
+                mint_metadata: keys.mint_metadata,
+                metadata_program: METADATA_PROGRAM_ID,
+                vault_program: prog_dynamic_vault::ID,
+                token_program: token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::InitializePermissionlessPoolWithFeeTier {
+                curve_type,
+                trade_fee_bps,
+                token_a_amount,
+                token_b_amount,
+            }
+            .data(),
+        }
+    }
+
+    pub fn initialize_customizable_permissionless_constant_product_pool(
+        &self,
+        payer: Pubkey,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        params: CustomizableParams,
+    ) -> Instruction {
+        let pool = derive_customizable_permissionless_constant_product_pool_address(
+            self.token_a_mint,
+            self.token_b_mint,
+        );
+        let keys = self.get_keys(pool);
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts:
+                prog_dynamic_amm::accounts::InitializeCustomizablePermissionlessConstantProductPool {
+                    pool: keys.pool,
+                    lp_mint: keys.lp_mint,
+                    token_a_mint: self.token_a_mint,
+                    token_b_mint: self.token_b_mint,
+                    a_vault: keys.a_vault,
+                    b_vault: keys.b_vault,
+                    a_token_vault: keys.a_token_vault,
+                    b_token_vault: keys.b_token_vault,
+                    a_vault_lp_mint: keys.a_vault_lp_mint,
+                    b_vault_lp_mint: keys.b_vault_lp_mint,
+                    a_vault_lp: keys.a_vault_lp,
+                    b_vault_lp: keys.b_vault_lp,
+                    payer_token_a: get_associated_token_address(&payer, &self.token_a_mint),
+                    payer_token_b: get_associated_token_address(&payer, &self.token_b_mint),
+                    payer_pool_lp: get_associated_token_address(&payer, &keys.lp_mint),
+                    protocol_token_a_fee: keys.protocol_token_a_fee,
+                    protocol_token_b_fee: keys.protocol_token_b_fee,
+                    payer,
+                    rent: sysvar::rent::ID,
+                    mint_metadata: keys.mint_metadata,
+                    metadata_program: METADATA_PROGRAM_ID,
+                    vault_program: prog_dynamic_vault::ID,
+                    token_program: token::ID,
+                    associated_token_program: associated_token::ID,
+                    system_program: system_program::ID,
+                }
+                .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::InitializeCustomizablePermissionlessConstantProductPool {
+                token_a_amount,
+                token_b_amount,
+                params,
+            }
+            .data(),
+        }
+    }
+
+    fn get_initialize_with_config_accounts(
+        &self,
+        payer: Pubkey,
+        config: Pubkey,
+    ) -> prog_dynamic_amm::accounts::InitializePermissionlessConstantProductPoolWithConfig {
+        let pool =
+            derive_constant_product_pool_with_config(self.token_a_mint, self.token_b_mint, config);
+        let keys = self.get_keys(pool);
+        prog_dynamic_amm::accounts::InitializePermissionlessConstantProductPoolWithConfig {
+            pool: keys.pool,
+            config,
+            lp_mint: keys.lp_mint,
+            token_a_mint: self.token_a_mint,
+            token_b_mint: self.token_b_mint,
+            a_vault: keys.a_vault,
+            b_vault: keys.b_vault,
+            a_token_vault: keys.a_token_vault,
+            b_token_vault: keys.b_token_vault,
+            a_vault_lp_mint: keys.a_vault_lp_mint,
+            b_vault_lp_mint: keys.b_vault_lp_mint,
+            a_vault_lp: keys.a_vault_lp,
+            b_vault_lp: keys.b_vault_lp,
+            payer_token_a: get_associated_token_address(&payer, &self.token_a_mint),
+            payer_token_b: get_associated_token_address(&payer, &self.token_b_mint),
+            payer_pool_lp: get_associated_token_address(&payer, &keys.lp_mint),
+            protocol_token_a_fee: keys.protocol_token_a_fee,
+            protocol_token_b_fee: keys.protocol_token_b_fee,
+            payer,
+            rent: sysvar::rent::ID,
+            mint_metadata: keys.mint_metadata,
+            metadata_program: METADATA_PROGRAM_ID,
+            vault_program: prog_dynamic_vault::ID,
+            token_program: token::ID,
+            associated_token_program: associated_token::ID,
+            system_program: system_program::ID,
+        }
+    }
+
+    pub fn initialize_permissionless_constant_product_pool_with_config(
+        &self,
+        payer: Pubkey,
+        config: Pubkey,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: self
+                .get_initialize_with_config_accounts(payer, config)
+                .to_account_metas(None),
+            data:
+                prog_dynamic_amm::instruction::InitializePermissionlessConstantProductPoolWithConfig {
+                    token_a_amount,
+                    token_b_amount,
+                }
+                .data(),
+        }
+    }
+
+    pub fn initialize_permissionless_constant_product_pool_with_config2(
+        &self,
+        payer: Pubkey,
+        config: Pubkey,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        activation_point: Option<u64>,
+    ) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: self
+                .get_initialize_with_config_accounts(payer, config)
+                .to_account_metas(None),
+            data:
+                prog_dynamic_amm::instruction::InitializePermissionlessConstantProductPoolWithConfig2 {
+                    token_a_amount,
+                    token_b_amount,
+                    activation_point,
+                }
+                .data(),
+        }
+    }
+
+    /// Permissioned pool address is arbitrary, `pool` must sign the transaction
+    pub fn initialize_permissioned_pool(
+        &self,
+        admin: Pubkey,
+        pool: Pubkey,
+        curve_type: CurveType,
+    ) -> Instruction {
+        let keys = self.get_keys(pool);
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::InitializePermissionedPool {
+                pool: keys.pool,
+                lp_mint: keys.lp_mint,
+                token_a_mint: self.token_a_mint,
+                token_b_mint: self.token_b_mint,
+                a_vault: keys.a_vault,
+                b_vault: keys.b_vault,
+                a_vault_lp_mint: keys.a_vault_lp_mint,
+                b_vault_lp_mint: keys.b_vault_lp_mint,
+                a_vault_lp: keys.a_vault_lp,
+                b_vault_lp: keys.b_vault_lp,
+                admin_token_a: get_associated_token_address(&admin, &self.token_a_mint),
+                admin_token_b: get_associated_token_address(&admin, &self.token_b_mint),
+                admin_pool_lp: get_associated_token_address(&admin, &keys.lp_mint),
+                protocol_token_a_fee: keys.protocol_token_a_fee,
+                protocol_token_b_fee: keys.protocol_token_b_fee,
+                admin,
+                fee_owner: Pubkey::default(),
+                rent: sysvar::rent::ID,
+                mint_metadata: keys.mint_metadata,
+                metadata_program: METADATA_PROGRAM_ID,
+                vault_program: prog_dynamic_vault::ID,
+                token_program: token::ID,
+                associated_token_program: associated_token::ID,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::InitializePermissionedPool { curve_type }.data(),
+        }
+    }
+}
+
+/// Loaded pool, and the state of its vaults
+#[derive(Clone, Copy)]
+pub struct PoolInstructionBuilder<'a> {
+    pub pool_key: Pubkey,
+    pub pool: &'a Pool,
+    pub a_vault: &'a Vault,
+    pub b_vault: &'a Vault,
+}
+
+impl PoolInstructionBuilder<'_> {
+    /// Stake account of depeg pools, read by the program to update the base virtual price. Empty for the other pools.
+    pub fn get_remaining_accounts(&self) -> Vec<AccountMeta> {
+        get_stake_account(self.pool)
+            .map(|stake| vec![AccountMeta::new_readonly(stake, false)])
+            .unwrap_or_default()
+    }
+
+    fn get_liquidity_account_metas(&self, user: Pubkey) -> Vec<AccountMeta> {
+        let mut accounts = self.get_liquidity_accounts(user).to_account_metas(None);
+        accounts.extend(self.get_remaining_accounts());
+        accounts
+    }
+
+    fn get_liquidity_accounts(
+        &self,
+        user: Pubkey,
+    ) -> prog_dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
+        prog_dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
+            pool: self.pool_key,
+            lp_mint: self.pool.lp_mint,
+            user_pool_lp: get_associated_token_address(&user, &self.pool.lp_mint),
+            a_vault_lp: self.pool.a_vault_lp,
+            b_vault_lp: self.pool.b_vault_lp,
+            a_vault: self.pool.a_vault,
+            b_vault: self.pool.b_vault,
+            a_vault_lp_mint: self.a_vault.lp_mint,
+            b_vault_lp_mint: self.b_vault.lp_mint,
+            a_token_vault: self.a_vault.token_vault,
+            b_token_vault: self.b_vault.token_vault,
+            user_a_token: get_associated_token_address(&user, &self.pool.token_a_mint),
+            user_b_token: get_associated_token_address(&user, &self.pool.token_b_mint),
+            user,
+            vault_program: prog_dynamic_vault::ID,
+            token_program: token::ID,
+        }
+    }
+
+    pub fn swap(
+        &self,
+        user: Pubkey,
+        in_token_mint: Pubkey,
+        in_amount: u64,
+        minimum_out_amount: u64,
+    ) -> anyhow::Result<Instruction> {
+        let (out_token_mint, protocol_token_fee) = if in_token_mint == self.pool.token_a_mint {
+            (self.pool.token_b_mint, self.pool.protocol_token_a_fee)
+        } else {
+            ensure!(
+                in_token_mint == self.pool.token_b_mint,
+                "In token mint is not a pool token"
+            );
+            (self.pool.token_a_mint, self.pool.protocol_token_b_fee)
+        };
+
+        Ok(Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::Swap {
+                pool: self.pool_key,
+                user_source_token: get_associated_token_address(&user, &in_token_mint),
+                user_destination_token: get_associated_token_address(&user, &out_token_mint),
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_token_vault: self.a_vault.token_vault,
+                b_token_vault: self.b_vault.token_vault,
+                a_vault_lp_mint: self.a_vault.lp_mint,
+                b_vault_lp_mint: self.b_vault.lp_mint,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                protocol_token_fee,
+                user,
+                vault_program: prog_dynamic_vault::ID,
+                token_program: token::ID,
+            }
+            .to_account_metas(None)
+            .into_iter()
+            .chain(self.get_remaining_accounts())
+            .collect(),
+            data: prog_dynamic_amm::instruction::Swap {
+                in_amount,
+                minimum_out_amount,
+            }
+            .data(),
+        })
+    }
+
+    pub fn add_balance_liquidity(
+        &self,
+        user: Pubkey,
+        pool_token_amount: u64,
+        maximum_token_a_amount: u64,
+        maximum_token_b_amount: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: self.get_liquidity_account_metas(user),
+            data: prog_dynamic_amm::instruction::AddBalanceLiquidity {
+                pool_token_amount,
+                maximum_token_a_amount,
+                maximum_token_b_amount,
+            }
+            .data(),
+        }
+    }
+
+    /// Only supported by stable pools
+    pub fn add_imbalance_liquidity(
+        &self,
+        user: Pubkey,
+        minimum_pool_token_amount: u64,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: self.get_liquidity_account_metas(user),
+            data: prog_dynamic_amm::instruction::AddImbalanceLiquidity {
+                minimum_pool_token_amount,
+                token_a_amount,
+                token_b_amount,
+            }
+            .data(),
+        }
+    }
+
+    pub fn remove_balance_liquidity(
+        &self,
+        user: Pubkey,
+        pool_token_amount: u64,
+        minimum_a_token_out: u64,
+        minimum_b_token_out: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: self.get_liquidity_account_metas(user),
+            data: prog_dynamic_amm::instruction::RemoveBalanceLiquidity {
+                pool_token_amount,
+                minimum_a_token_out,
+                minimum_b_token_out,
+            }
+            .data(),
+        }
+    }
+
+    /// Only supported by stable pools
+    pub fn remove_liquidity_single_side(
+        &self,
+        user: Pubkey,
+        out_token_mint: Pubkey,
+        pool_token_amount: u64,
+        minimum_out_amount: u64,
+    ) -> anyhow::Result<Instruction> {
+        ensure!(
+            out_token_mint == self.pool.token_a_mint || out_token_mint == self.pool.token_b_mint,
+            "Out token mint is not a pool token"
+        );
+
+        Ok(Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::RemoveLiquiditySingleSide {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                user_pool_lp: get_associated_token_address(&user, &self.pool.lp_mint),
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp_mint: self.a_vault.lp_mint,
+                b_vault_lp_mint: self.b_vault.lp_mint,
+                a_token_vault: self.a_vault.token_vault,
+                b_token_vault: self.b_vault.token_vault,
+                user_destination_token: get_associated_token_address(&user, &out_token_mint),
+                user,
+                vault_program: prog_dynamic_vault::ID,
+                token_program: token::ID,
+            }
+            .to_account_metas(None)
+            .into_iter()
+            .chain(self.get_remaining_accounts())
+            .collect(),
+            data: prog_dynamic_amm::instruction::RemoveLiquiditySingleSide {
+                pool_token_amount,
+                minimum_out_amount,
+            }
+            .data(),
+        })
+    }
+
+    /// Only allowed when the pool liquidity is depleted
+    pub fn bootstrap_liquidity(
+        &self,
+        user: Pubkey,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Instruction {
+        let accounts = self.get_liquidity_accounts(user);
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::BootstrapLiquidity {
+                pool: accounts.pool,
+                lp_mint: accounts.lp_mint,
+                user_pool_lp: accounts.user_pool_lp,
+                a_vault_lp: accounts.a_vault_lp,
+                b_vault_lp: accounts.b_vault_lp,
+                a_vault: accounts.a_vault,
+                b_vault: accounts.b_vault,
+                a_vault_lp_mint: accounts.a_vault_lp_mint,
+                b_vault_lp_mint: accounts.b_vault_lp_mint,
+                a_token_vault: accounts.a_token_vault,
+                b_token_vault: accounts.b_token_vault,
+                user_a_token: accounts.user_a_token,
+                user_b_token: accounts.user_b_token,
+                user,
+                vault_program: prog_dynamic_vault::ID,
+                token_program: token::ID,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::BootstrapLiquidity {
+                token_a_amount,
+                token_b_amount,
+            }
+            .data(),
+        }
+    }
+
+    pub fn get_pool_info(&self) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::GetPoolInfo {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp_mint: self.a_vault.lp_mint,
+                b_vault_lp_mint: self.b_vault.lp_mint,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::GetPoolInfo {}.data(),
+        }
+    }
+
+    pub fn create_lock_escrow(&self, owner: Pubkey, payer: Pubkey) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::CreateLockEscrow {
+                pool: self.pool_key,
+                lock_escrow: derive_lock_escrow_address(self.pool_key, owner),
+                owner,
+                lp_mint: self.pool.lp_mint,
+                payer,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::CreateLockEscrow {}.data(),
+        }
+    }
+
+    /// Lp token account of the lock escrow, holding the locked lp. It must be created before locking.
+    pub fn get_escrow_vault(&self, owner: Pubkey) -> Pubkey {
+        let lock_escrow = derive_lock_escrow_address(self.pool_key, owner);
+        get_associated_token_address(&lock_escrow, &self.pool.lp_mint)
+    }
+
+    /// Lock `amount` of the owner lp into its lock escrow
+    pub fn lock(&self, owner: Pubkey, amount: u64) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::Lock {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                lock_escrow: derive_lock_escrow_address(self.pool_key, owner),
+                owner,
+                source_tokens: get_associated_token_address(&owner, &self.pool.lp_mint),
+                escrow_vault: self.get_escrow_vault(owner),
+                token_program: token::ID,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault_lp_mint: self.a_vault.lp_mint,
+                b_vault_lp_mint: self.b_vault.lp_mint,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::Lock { amount }.data(),
+        }
+    }
+
+    /// Claim the fee accumulated by the locked lp of the owner
+    pub fn claim_fee(&self, owner: Pubkey, max_amount: u64) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::ClaimFee {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                lock_escrow: derive_lock_escrow_address(self.pool_key, owner),
+                owner,
+                source_tokens: get_associated_token_address(&owner, &self.pool.lp_mint),
+                escrow_vault: self.get_escrow_vault(owner),
+                token_program: token::ID,
+                a_token_vault: self.a_vault.token_vault,
+                b_token_vault: self.b_vault.token_vault,
+                a_vault: self.pool.a_vault,
+                b_vault: self.pool.b_vault,
+                a_vault_lp: self.pool.a_vault_lp,
+                b_vault_lp: self.pool.b_vault_lp,
+                a_vault_lp_mint: self.a_vault.lp_mint,
+                b_vault_lp_mint: self.b_vault.lp_mint,
+                user_a_token: get_associated_token_address(&owner, &self.pool.token_a_mint),
+                user_b_token: get_associated_token_address(&owner, &self.pool.token_b_mint),
+                vault_program: prog_dynamic_vault::ID,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::ClaimFee { max_amount }.data(),
+        }
+    }
+
+    /// Create the metadata of the pool lp mint, for pools created before the metadata were introduced
+    pub fn create_mint_metadata(&self, payer: Pubkey) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::CreateMintMetadata {
+                pool: self.pool_key,
+                lp_mint: self.pool.lp_mint,
+                a_vault_lp: self.pool.a_vault_lp,
+                mint_metadata: derive_mint_metadata_address(self.pool.lp_mint),
+                metadata_program: METADATA_PROGRAM_ID,
+                system_program: system_program::ID,
+                payer,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::CreateMintMetadata {}.data(),
+        }
+    }
+
+    pub fn enable_or_disable_pool(&self, admin: Pubkey, enable: bool) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::EnableOrDisablePool {
+                pool: self.pool_key,
+                admin,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::EnableOrDisablePool { enable }.data(),
+        }
+    }
+
+    pub fn set_pool_fees(
+        &self,
+        fee_operator: Pubkey,
+        fees: PoolFees,
+        new_partner_fee_numerator: u64,
+    ) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::SetPoolFees {
+                pool: self.pool_key,
+                fee_operator,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::SetPoolFees {
+                fees,
+                new_partner_fee_numerator,
+            }
+            .data(),
+        }
+    }
+
+    /// Only the amp of stable pools can be overridden
+    pub fn override_curve_param(&self, admin: Pubkey, curve_type: CurveType) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::OverrideCurveParam {
+                pool: self.pool_key,
+                admin,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::OverrideCurveParam { curve_type }.data(),
+        }
+    }
+
+    pub fn update_activation_point(&self, admin: Pubkey, new_activation_point: u64) -> Instruction {
+        Instruction {
+            program_id: prog_dynamic_amm::ID,
+            accounts: prog_dynamic_amm::accounts::UpdateActivationPoint {
+                pool: self.pool_key,
+                admin,
+            }
+            .to_account_metas(None),
+            data: prog_dynamic_amm::instruction::UpdateActivationPoint {
+                new_activation_point,
+            }
+            .data(),
+        }
+    }
+}
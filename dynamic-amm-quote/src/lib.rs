@@ -1,11 +1,33 @@
+pub mod account;
+pub mod admin;
+pub mod allocation;
+pub mod amp_ramp;
+pub mod apy;
+pub mod config;
 pub mod curve;
+pub mod decoder;
 pub mod depeg;
+pub mod diagnostic;
+pub mod discovery;
+pub mod event;
+pub mod instruction;
+pub mod liquidity;
 pub mod math;
+pub mod pda;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod slippage;
+pub mod ui_amount;
+#[cfg(feature = "serde")]
+pub mod view;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 use crate::depeg::update_base_virtual_price;
 use crate::math::*;
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, TokenAccount};
 use anyhow::{anyhow, ensure, Context};
+use prog_dynamic_amm::constants::fee::FEE_DENOMINATOR;
 use prog_dynamic_amm::error::PoolError;
 use prog_dynamic_amm::state::{ActivationType, Pool};
 use prog_dynamic_vault::state::Vault;
@@ -47,14 +69,66 @@ pub struct QuoteData {
     pub stake_data: HashMap<Pubkey, Vec<u8>>,
 }
 
+/// Quote failure caused by a too large in amount. Any other failure doesn't depend on the in amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmountTooLarge {
+    /// Math overflow of the step
+    MathOverflow(&'static str),
+    /// Out amount above the liquidity of the out vault
+    ExceedReserve,
+}
+
+impl std::fmt::Display for AmountTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmountTooLarge::MathOverflow(step) => write!(f, "{}", step),
+            AmountTooLarge::ExceedReserve => write!(f, "Out amount > vault reserve"),
+        }
+    }
+}
+
+impl std::error::Error for AmountTooLarge {}
+
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct QuoteResult {
     /// Swap out amount
     pub out_amount: u64,
     /// Total fee amount. Fee is charged based on in token mint.
     pub fee: u64,
+    /// Protocol fee amount, cut from the trade fee before `fee` is charged. Includes `partner_fee`.
+    pub protocol_fee: u64,
+    /// Partner share of the protocol fee
+    pub partner_fee: u64,
+    /// Liquidity of the out vault remaining in token_vault. Swap out amount is only served from it.
+    pub out_vault_idle_amount: u64,
+    /// Liquidity of the out vault deployed to strategies
+    pub out_vault_deployed_amount: u64,
 }
 
+/// Check the pool can swap `in_token_mint` at the current point, whatever the in amount
+fn ensure_swappable(pool: &Pool, clock: &Clock, in_token_mint: Pubkey) -> anyhow::Result<()> {
+    let activation_type =
+        ActivationType::try_from(pool.bootstrapping.activation_type).map_err(|e| anyhow!(e))?;
+
+    let current_point = match activation_type {
+        ActivationType::Slot => clock.slot,
+        ActivationType::Timestamp => clock.unix_timestamp as u64,
+    };
+
+    ensure!(pool.enabled, "Pool disabled");
+    ensure!(
+        current_point >= pool.bootstrapping.activation_point,
+        "Swap is disabled"
+    );
+    ensure!(
+        in_token_mint == pool.token_a_mint || in_token_mint == pool.token_b_mint,
+        "In token mint not matches with pool token mints"
+    );
+    Ok(())
+}
+
+/// Quote swapping `in_amount` of `in_token_mint`. Fails with [AmountTooLarge] when the in amount is too large to be swapped.
 pub fn compute_quote(
     in_token_mint: Pubkey,
     in_amount: u64,
@@ -74,29 +148,12 @@ pub fn compute_quote(
         stake_data,
     } = quote_data;
 
-    let activation_type =
-        ActivationType::try_from(pool.bootstrapping.activation_type).map_err(|e| anyhow!(e))?;
-
-    let current_point = match activation_type {
-        ActivationType::Slot => clock.slot,
-        ActivationType::Timestamp => clock.unix_timestamp as u64,
-    };
-
-    ensure!(pool.enabled, "Pool disabled");
-    ensure!(
-        current_point >= pool.bootstrapping.activation_point,
-        "Swap is disabled"
-    );
+    ensure_swappable(&pool, &clock, in_token_mint)?;
 
     update_base_virtual_price(&mut pool, &clock, stake_data)?;
 
     let current_time: u64 = clock.unix_timestamp.try_into()?;
 
-    ensure!(
-        in_token_mint == pool.token_a_mint || in_token_mint == pool.token_b_mint,
-        "In token mint not matches with pool token mints"
-    );
-
     let token_a_amount = vault_a
         .get_amount_by_share(
             current_time,
@@ -178,25 +235,29 @@ pub fn compute_quote(
             in_amount_after_protocol_fee,
             in_vault_lp_mint.supply,
         )
-        .context("Fail to get in_vault_lp")?;
+        .context(AmountTooLarge::MathOverflow("Fail to get in_vault_lp"))?;
 
     in_vault.total_amount = in_vault
         .total_amount
         .checked_add(in_amount_after_protocol_fee)
-        .context("Fail to add in_vault.total_amount")?;
+        .context(AmountTooLarge::MathOverflow(
+            "Fail to add in_vault.total_amount",
+        ))?;
 
-    let after_in_token_total_amount = in_vault
-        .get_amount_by_share(
-            current_time,
-            in_lp
-                .checked_add(in_vault_lp.amount)
-                .context("Fail to get new in_vault_lp")?,
-            in_vault_lp_mint
-                .supply
-                .checked_add(in_lp)
-                .context("Fail to get new in_vault_lp_mint")?,
-        )
-        .context("Fail to get after_in_token_total_amount")?;
+    let after_in_token_total_amount =
+        in_vault
+            .get_amount_by_share(
+                current_time,
+                in_lp
+                    .checked_add(in_vault_lp.amount)
+                    .context(AmountTooLarge::MathOverflow("Fail to get new in_vault_lp"))?,
+                in_vault_lp_mint.supply.checked_add(in_lp).context(
+                    AmountTooLarge::MathOverflow("Fail to get new in_vault_lp_mint"),
+                )?,
+            )
+            .context(AmountTooLarge::MathOverflow(
+                "Fail to get after_in_token_total_amount",
+            ))?;
 
     let actual_in_amount = after_in_token_total_amount
         .checked_sub(before_in_token_total_amount)
@@ -218,28 +279,94 @@ pub fn compute_quote(
             out_token_total_amount,
             trade_direction,
         )
-        .context("Fail to get swap result")?;
+        .context(AmountTooLarge::MathOverflow("Fail to get swap result"))?;
 
     let out_vault_lp = out_vault
         .get_unmint_amount(
             current_time,
-            destination_amount_swapped.try_into()?,
+            destination_amount_swapped
+                .try_into()
+                .map_err(|_| AmountTooLarge::MathOverflow("Fail to get destination amount"))?,
             out_vault_lp_mint.supply,
         )
-        .context("Fail to get out_vault_lp")?;
+        .context(AmountTooLarge::MathOverflow("Fail to get out_vault_lp"))?;
 
     let out_amount = out_vault
         .get_amount_by_share(current_time, out_vault_lp, out_vault_lp_mint.supply)
-        .context("Fail to get out_amount")?;
+        .context(AmountTooLarge::MathOverflow("Fail to get out_amount"))?;
+
+    let out_vault_liquidity =
+        out_vault.get_liquidity_by_token_vault(out_vault_token_account.amount);
 
     ensure!(
-        out_amount < out_vault_token_account.amount,
-        "Out amount > vault reserve"
+        out_vault_liquidity.is_served_by_idle(out_amount),
+        AmountTooLarge::ExceedReserve
     );
 
+    // Partner fee is a cut from protocol fee
+    let partner_fee = protocol_fee
+        .checked_mul(pool.partner_info.fee_numerator.into())
+        .and_then(|fee| fee.checked_div(FEE_DENOMINATOR.into()))
+        .context("Fail to calculate partner fee")?;
+
     Ok(QuoteResult {
         fee: trade_fee.try_into()?,
+        protocol_fee: protocol_fee.try_into()?,
+        partner_fee: partner_fee.try_into()?,
         out_amount,
+        out_vault_idle_amount: out_vault_liquidity.idle_amount,
+        out_vault_deployed_amount: out_vault_liquidity.deployed_amount,
+    })
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExactOutQuoteResult {
+    /// Minimum swap in amount giving at least the requested out amount
+    pub in_amount: u64,
+    /// Quote of swapping `in_amount`
+    pub quote: QuoteResult,
+}
+
+/// Compute the minimum in amount of `in_token_mint` to receive at least `out_amount`. The program only swaps exact in amounts,
+/// the in amount is found by a binary search over [compute_quote]. An in amount failing with [AmountTooLarge] bounds the
+/// search, any other failure is returned.
+pub fn compute_quote_exact_out(
+    in_token_mint: Pubkey,
+    out_amount: u64,
+    quote_data: QuoteData,
+) -> anyhow::Result<ExactOutQuoteResult> {
+    ensure!(out_amount > 0, "Out amount is zero");
+    ensure_swappable(&quote_data.pool, &quote_data.clock, in_token_mint)?;
+
+    let is_enough =
+        |in_amount: u64| match compute_quote(in_token_mint, in_amount, quote_data.clone()) {
+            Ok(quote) => Ok(quote.out_amount >= out_amount),
+            Err(err) if err.downcast_ref::<AmountTooLarge>().is_some() => Ok(true),
+            Err(err) => Err(err),
+        };
+
+    let (mut low, mut high) = (1u64, u64::MAX);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if is_enough(mid)? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    let quote = compute_quote(in_token_mint, low, quote_data)
+        .with_context(|| format!("Out amount {} is not reachable", out_amount))?;
+    ensure!(
+        quote.out_amount >= out_amount,
+        "Out amount {} is not reachable",
+        out_amount
+    );
+
+    Ok(ExactOutQuoteResult {
+        in_amount: low,
+        quote,
     })
 }
 
@@ -0,0 +1,226 @@
+//! Balanced deposit and withdraw math of the pool
+use crate::QuoteData;
+use anchor_lang::prelude::Pubkey;
+use anyhow::{bail, ensure, Context};
+use prog_dynamic_vault::state::Vault;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DepositQuote {
+    /// Pool LP amount minted to the user
+    pub pool_token_amount: u64,
+    /// Token A amount deposited to the pool
+    pub token_a_amount: u64,
+    /// Token B amount deposited to the pool
+    pub token_b_amount: u64,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WithdrawQuote {
+    /// Pool LP amount burned from the user
+    pub pool_token_amount: u64,
+    /// Token A amount received by the user
+    pub token_a_out_amount: u64,
+    /// Token B amount received by the user
+    pub token_b_out_amount: u64,
+    /// Liquidity of vault A remaining in token_vault. Token A out amount is only served from it.
+    pub vault_a_idle_amount: u64,
+    /// Liquidity of vault A deployed to strategies
+    pub vault_a_deployed_amount: u64,
+    /// Liquidity of vault B remaining in token_vault. Token B out amount is only served from it.
+    pub vault_b_idle_amount: u64,
+    /// Liquidity of vault B deployed to strategies
+    pub vault_b_deployed_amount: u64,
+}
+
+fn mul_div(x: u64, y: u64, denominator: u64, round_up: bool) -> Option<u64> {
+    let numerator = u128::from(x).checked_mul(y.into())?;
+    let denominator = u128::from(denominator);
+    let result = if round_up {
+        numerator
+            .checked_add(denominator.checked_sub(1)?)?
+            .checked_div(denominator)?
+    } else {
+        numerator.checked_div(denominator)?
+    };
+    result.try_into().ok()
+}
+
+/// Token amount required to mint the vault LP backing `pool_token_amount` of pool LP
+fn get_vault_deposit_amount(
+    vault: &Vault,
+    current_time: u64,
+    pool_token_amount: u64,
+    pool_lp_supply: u64,
+    pool_vault_lp_amount: u64,
+    vault_lp_supply: u64,
+) -> Option<u64> {
+    let vault_lp_amount = mul_div(
+        pool_token_amount,
+        pool_vault_lp_amount,
+        pool_lp_supply,
+        true,
+    )?;
+    let total_amount = vault.get_unlocked_amount(current_time)?;
+    mul_div(vault_lp_amount, total_amount, vault_lp_supply, true)
+}
+
+/// Token amount received by burning the vault LP backing `pool_token_amount` of pool LP
+fn get_vault_withdraw_amount(
+    vault: &Vault,
+    current_time: u64,
+    pool_token_amount: u64,
+    pool_lp_supply: u64,
+    pool_vault_lp_amount: u64,
+    vault_lp_supply: u64,
+) -> Option<u64> {
+    let vault_lp_amount = mul_div(
+        pool_token_amount,
+        pool_vault_lp_amount,
+        pool_lp_supply,
+        false,
+    )?;
+    vault.get_amount_by_share(current_time, vault_lp_amount, vault_lp_supply)
+}
+
+/// Compute the pool LP minted, and the token A, B required for a balanced deposit of `in_amount` of `in_token_mint`
+pub fn compute_deposit_quote(
+    in_token_mint: Pubkey,
+    in_amount: u64,
+    pool_lp_supply: u64,
+    quote_data: QuoteData,
+) -> anyhow::Result<DepositQuote> {
+    let QuoteData {
+        pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        clock,
+        ..
+    } = quote_data;
+
+    ensure!(pool.enabled, "Pool disabled");
+    ensure!(
+        pool_lp_supply > 0,
+        "Pool is depleted, bootstrap liquidity instead"
+    );
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let token_a_amount = vault_a
+        .get_amount_by_share(
+            current_time,
+            pool_vault_a_lp_token.amount,
+            vault_a_lp_mint.supply,
+        )
+        .context("Fail to get token a amount")?;
+
+    let token_b_amount = vault_b
+        .get_amount_by_share(
+            current_time,
+            pool_vault_b_lp_token.amount,
+            vault_b_lp_mint.supply,
+        )
+        .context("Fail to get token b amount")?;
+
+    let in_token_total_amount = if in_token_mint == pool.token_a_mint {
+        token_a_amount
+    } else if in_token_mint == pool.token_b_mint {
+        token_b_amount
+    } else {
+        bail!("In token mint not matches with pool token mints");
+    };
+
+    let pool_token_amount = mul_div(in_amount, pool_lp_supply, in_token_total_amount, false)
+        .context("Fail to get pool_token_amount")?;
+
+    let token_a_amount = get_vault_deposit_amount(
+        &vault_a,
+        current_time,
+        pool_token_amount,
+        pool_lp_supply,
+        pool_vault_a_lp_token.amount,
+        vault_a_lp_mint.supply,
+    )
+    .context("Fail to get token a deposit amount")?;
+
+    let token_b_amount = get_vault_deposit_amount(
+        &vault_b,
+        current_time,
+        pool_token_amount,
+        pool_lp_supply,
+        pool_vault_b_lp_token.amount,
+        vault_b_lp_mint.supply,
+    )
+    .context("Fail to get token b deposit amount")?;
+
+    Ok(DepositQuote {
+        pool_token_amount,
+        token_a_amount,
+        token_b_amount,
+    })
+}
+
+/// Compute the token A, B received for a balanced withdraw of `pool_token_amount` pool LP
+pub fn compute_withdraw_quote(
+    pool_token_amount: u64,
+    pool_lp_supply: u64,
+    quote_data: QuoteData,
+) -> anyhow::Result<WithdrawQuote> {
+    let QuoteData {
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        vault_a_token,
+        vault_b_token,
+        clock,
+        ..
+    } = quote_data;
+
+    ensure!(
+        pool_token_amount <= pool_lp_supply,
+        "Pool token amount > pool lp supply"
+    );
+
+    let current_time: u64 = clock.unix_timestamp.try_into()?;
+
+    let token_a_out_amount = get_vault_withdraw_amount(
+        &vault_a,
+        current_time,
+        pool_token_amount,
+        pool_lp_supply,
+        pool_vault_a_lp_token.amount,
+        vault_a_lp_mint.supply,
+    )
+    .context("Fail to get token a out amount")?;
+
+    let token_b_out_amount = get_vault_withdraw_amount(
+        &vault_b,
+        current_time,
+        pool_token_amount,
+        pool_lp_supply,
+        pool_vault_b_lp_token.amount,
+        vault_b_lp_mint.supply,
+    )
+    .context("Fail to get token b out amount")?;
+
+    let vault_a_liquidity = vault_a.get_liquidity_by_token_vault(vault_a_token.amount);
+    let vault_b_liquidity = vault_b.get_liquidity_by_token_vault(vault_b_token.amount);
+
+    Ok(WithdrawQuote {
+        pool_token_amount,
+        token_a_out_amount,
+        token_b_out_amount,
+        vault_a_idle_amount: vault_a_liquidity.idle_amount,
+        vault_a_deployed_amount: vault_a_liquidity.deployed_amount,
+        vault_b_idle_amount: vault_b_liquidity.idle_amount,
+        vault_b_deployed_amount: vault_b_liquidity.deployed_amount,
+    })
+}
@@ -0,0 +1,270 @@
+//! Program derived addresses of the dynamic amm and dynamic vault accounts
+use crate::discovery::find_pool_config;
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::pubkey;
+use prog_dynamic_amm::constants::seeds::CONFIG_PREFIX;
+use prog_dynamic_amm::{
+    instructions::*,
+    state::{CurveType, Pool, PoolType},
+};
+
+/// Metaplex token metadata program
+pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+pub fn derive_vault_address(token_mint: &Pubkey) -> Pubkey {
+    let (vault, _bump) = Pubkey::find_program_address(
+        &[
+            prog_dynamic_vault::seed::VAULT_PREFIX.as_ref(),
+            token_mint.as_ref(),
+            prog_dynamic_vault::get_base_address().as_ref(),
+        ],
+        &prog_dynamic_vault::id(),
+    );
+    vault
+}
+
+pub fn derive_token_vault_address(vault: &Pubkey) -> Pubkey {
+    let (token_vault, _bump) = Pubkey::find_program_address(
+        &[
+            prog_dynamic_vault::seed::TOKEN_VAULT_PREFIX.as_ref(),
+            vault.as_ref(),
+        ],
+        &prog_dynamic_vault::id(),
+    );
+    token_vault
+}
+
+pub fn derive_vault_lp_mint_address(vault: &Pubkey) -> Pubkey {
+    let (lp_mint, _bump) = Pubkey::find_program_address(
+        &[
+            prog_dynamic_vault::seed::LP_MINT_PREFIX.as_ref(),
+            vault.as_ref(),
+        ],
+        &prog_dynamic_vault::id(),
+    );
+    lp_mint
+}
+
+pub fn derive_pool_address(
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    trade_fee_bps: u64,
+) -> Pubkey {
+    derive_permissionless_pool_address(
+        CurveType::ConstantProduct,
+        token_a_mint,
+        token_b_mint,
+        trade_fee_bps,
+    )
+}
+
+/// Pool created by `initialize_permissionless_pool` or `initialize_permissionless_pool_with_fee_tier`. The trade fee is not part of the seeds when it is the default 0.25%.
+pub fn derive_permissionless_pool_address(
+    curve_type: CurveType,
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    trade_fee_bps: u64,
+) -> Pubkey {
+    let (pool, _bump) = Pubkey::find_program_address(
+        &[
+            &get_curve_type(curve_type).to_le_bytes(),
+            get_first_key(token_a_mint, token_b_mint).as_ref(),
+            get_second_key(token_a_mint, token_b_mint).as_ref(),
+            get_trade_fee_bps_bytes(trade_fee_bps).as_ref(), // Do not include owner trade fee
+        ],
+        &prog_dynamic_amm::id(),
+    );
+    pool
+}
+
+/// Pool created by `initialize_customizable_permissionless_constant_product_pool`
+pub fn derive_customizable_permissionless_constant_product_pool_address(
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+) -> Pubkey {
+    let (pool, _bump) = Pubkey::find_program_address(
+        &[
+            b"pool".as_ref(),
+            get_first_key(token_a_mint, token_b_mint).as_ref(),
+            get_second_key(token_a_mint, token_b_mint).as_ref(),
+        ],
+        &prog_dynamic_amm::id(),
+    );
+    pool
+}
+
+pub fn derive_constant_product_pool_with_config(
+    token_a_mint: Pubkey,
+    token_b_mint: Pubkey,
+    config: Pubkey,
+) -> Pubkey {
+    let (pool, _bump) = Pubkey::find_program_address(
+        &[
+            get_first_key(token_a_mint, token_b_mint).as_ref(),
+            get_second_key(token_a_mint, token_b_mint).as_ref(),
+            config.as_ref(),
+        ],
+        &prog_dynamic_amm::id(),
+    );
+
+    pool
+}
+
+pub fn derive_pool_lp_mint_address(pool: Pubkey) -> Pubkey {
+    let (lp_mint, _bump) = Pubkey::find_program_address(
+        &[b"lp_mint".as_ref(), pool.as_ref()],
+        &prog_dynamic_amm::id(),
+    );
+    lp_mint
+}
+
+pub fn derive_vault_lp_token_address(vault: Pubkey, pool: Pubkey) -> Pubkey {
+    let (vault_lp, _bump) =
+        Pubkey::find_program_address(&[vault.as_ref(), pool.as_ref()], &prog_dynamic_amm::id());
+    vault_lp
+}
+
+pub fn derive_pool_fee_token_address(token_mint: Pubkey, pool: Pubkey) -> Pubkey {
+    let (token_fee, _bump) = Pubkey::find_program_address(
+        &[b"fee".as_ref(), token_mint.as_ref(), pool.as_ref()],
+        &prog_dynamic_amm::id(),
+    );
+    token_fee
+}
+
+pub fn derive_lock_escrow_address(pool: Pubkey, owner: Pubkey) -> Pubkey {
+    let (lock_escrow, _bump) = Pubkey::find_program_address(
+        &[b"lock_escrow".as_ref(), pool.as_ref(), owner.as_ref()],
+        &prog_dynamic_amm::id(),
+    );
+    lock_escrow
+}
+
+pub fn derive_config_address(index: u64) -> Pubkey {
+    let (config, _bump) = Pubkey::find_program_address(
+        &[CONFIG_PREFIX, index.to_le_bytes().as_ref()],
+        &prog_dynamic_amm::id(),
+    );
+    config
+}
+
+/// Metaplex metadata account of the mint, e.g. the pool lp mint
+pub fn derive_mint_metadata_address(mint: Pubkey) -> Pubkey {
+    let (mint_metadata, _bump) = Pubkey::find_program_address(
+        &[
+            b"metadata".as_ref(),
+            METADATA_PROGRAM_ID.as_ref(),
+            mint.as_ref(),
+        ],
+        &METADATA_PROGRAM_ID,
+    );
+    mint_metadata
+}
+
+/// Instruction which created the pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PoolOrigin {
+    /// `initialize_permissionless_pool` or `initialize_permissionless_pool_with_fee_tier`
+    FeeTier { trade_fee_bps: u64 },
+    /// `initialize_permissionless_constant_product_pool_with_config`, or its v2
+    Config { config: Pubkey },
+    /// `initialize_customizable_permissionless_constant_product_pool`
+    Customizable,
+    /// `initialize_permissioned_pool`, the pool address is arbitrary
+    Permissioned,
+    /// Permissionless pool not matching any of the seeds. Either created from a config missing in the candidates, or the fee was changed after creation.
+    Unknown,
+}
+
+/// Find which creation path produced the pool by matching its address against the seeds of each path. Config pools are only recognized when their config is part of `configs`.
+pub fn get_pool_origin(pool_key: Pubkey, pool: &Pool, configs: &[Pubkey]) -> PoolOrigin {
+    if pool.pool_type == PoolType::Permissioned {
+        return PoolOrigin::Permissioned;
+    }
+
+    let token_a_mint = pool.token_a_mint;
+    let token_b_mint = pool.token_b_mint;
+
+    if derive_customizable_permissionless_constant_product_pool_address(token_a_mint, token_b_mint)
+        == pool_key
+    {
+        return PoolOrigin::Customizable;
+    }
+
+    if let Some(trade_fee_bps) = to_bps(
+        pool.fees.trade_fee_numerator.into(),
+        pool.fees.trade_fee_denominator.into(),
+    ) {
+        if derive_permissionless_pool_address(
+            pool.curve_type,
+            token_a_mint,
+            token_b_mint,
+            trade_fee_bps,
+        ) == pool_key
+        {
+            return PoolOrigin::FeeTier { trade_fee_bps };
+        }
+    }
+
+    match find_pool_config(pool_key, token_a_mint, token_b_mint, configs) {
+        Some(config) => PoolOrigin::Config { config },
+        None => PoolOrigin::Unknown,
+    }
+}
+
+/// Stored pool address not matching its seeds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressMismatch {
+    /// Pool field name
+    pub field: &'static str,
+    /// Address derived from the seeds
+    pub expected: Pubkey,
+    /// Address stored in the pool
+    pub actual: Pubkey,
+}
+
+/// Check the addresses stored in the pool against their seeds. Returns the mismatched addresses, empty when the pool is consistent.
+pub fn verify_pool_addresses(pool_key: Pubkey, pool: &Pool) -> Vec<AddressMismatch> {
+    let a_vault = derive_vault_address(&pool.token_a_mint);
+    let b_vault = derive_vault_address(&pool.token_b_mint);
+
+    let derived = [
+        (
+            "lp_mint",
+            derive_pool_lp_mint_address(pool_key),
+            pool.lp_mint,
+        ),
+        ("a_vault", a_vault, pool.a_vault),
+        ("b_vault", b_vault, pool.b_vault),
+        (
+            "a_vault_lp",
+            derive_vault_lp_token_address(pool.a_vault, pool_key),
+            pool.a_vault_lp,
+        ),
+        (
+            "b_vault_lp",
+            derive_vault_lp_token_address(pool.b_vault, pool_key),
+            pool.b_vault_lp,
+        ),
+        (
+            "protocol_token_a_fee",
+            derive_pool_fee_token_address(pool.token_a_mint, pool_key),
+            pool.protocol_token_a_fee,
+        ),
+        (
+            "protocol_token_b_fee",
+            derive_pool_fee_token_address(pool.token_b_mint, pool_key),
+            pool.protocol_token_b_fee,
+        ),
+    ];
+
+    derived
+        .into_iter()
+        .filter(|(_, expected, actual)| expected != actual)
+        .map(|(field, expected, actual)| AddressMismatch {
+            field,
+            expected,
+            actual,
+        })
+        .collect()
+}
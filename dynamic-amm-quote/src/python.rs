@@ -0,0 +1,206 @@
+//! Python bindings of the quote engine. All accounts are passed in as raw account data.
+use crate::account::{
+    deserialize_config, deserialize_lock_escrow, deserialize_mint, deserialize_pool,
+    deserialize_vault, QuoteAccounts,
+};
+use crate::liquidity;
+use crate::view::{ConfigView, LockEscrowView, PoolView, VaultView};
+use crate::QuoteData;
+use anchor_lang::prelude::Pubkey;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pythonize::pythonize;
+use serde::Serialize;
+use std::fmt::Display;
+use std::str::FromStr;
+
+fn to_py_error(e: impl Display) -> PyErr {
+    PyValueError::new_err(e.to_string())
+}
+
+fn parse_pubkey(key: &str) -> PyResult<Pubkey> {
+    Pubkey::from_str(key).map_err(to_py_error)
+}
+
+fn to_py_object<T: Serialize>(py: Python, value: &T) -> PyResult<PyObject> {
+    Ok(pythonize(py, value)?)
+}
+
+/// Raw account data of the accounts required by the quote. `stake` is the account data of the stake account returned by
+/// `depeg::get_stake_account`, only required by depeg pools
+#[pyclass(name = "QuoteAccounts")]
+#[derive(Clone)]
+pub struct PyQuoteAccounts {
+    pool: Vec<u8>,
+    vault_a: Vec<u8>,
+    vault_b: Vec<u8>,
+    pool_vault_a_lp_token: Vec<u8>,
+    pool_vault_b_lp_token: Vec<u8>,
+    vault_a_lp_mint: Vec<u8>,
+    vault_b_lp_mint: Vec<u8>,
+    vault_a_token: Vec<u8>,
+    vault_b_token: Vec<u8>,
+    clock: Vec<u8>,
+    stake: Option<Vec<u8>>,
+}
+
+#[pymethods]
+impl PyQuoteAccounts {
+    #[new]
+    #[pyo3(signature = (
+        pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        vault_a_token,
+        vault_b_token,
+        clock,
+        stake = None
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        pool: Vec<u8>,
+        vault_a: Vec<u8>,
+        vault_b: Vec<u8>,
+        pool_vault_a_lp_token: Vec<u8>,
+        pool_vault_b_lp_token: Vec<u8>,
+        vault_a_lp_mint: Vec<u8>,
+        vault_b_lp_mint: Vec<u8>,
+        vault_a_token: Vec<u8>,
+        vault_b_token: Vec<u8>,
+        clock: Vec<u8>,
+        stake: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            pool,
+            vault_a,
+            vault_b,
+            pool_vault_a_lp_token,
+            pool_vault_b_lp_token,
+            vault_a_lp_mint,
+            vault_b_lp_mint,
+            vault_a_token,
+            vault_b_token,
+            clock,
+            stake,
+        }
+    }
+}
+
+impl PyQuoteAccounts {
+    fn to_quote_data(&self) -> PyResult<QuoteData> {
+        QuoteData::try_from(QuoteAccounts {
+            pool: &self.pool,
+            vault_a: &self.vault_a,
+            vault_b: &self.vault_b,
+            pool_vault_a_lp_token: &self.pool_vault_a_lp_token,
+            pool_vault_b_lp_token: &self.pool_vault_b_lp_token,
+            vault_a_lp_mint: &self.vault_a_lp_mint,
+            vault_b_lp_mint: &self.vault_b_lp_mint,
+            vault_a_token: &self.vault_a_token,
+            vault_b_token: &self.vault_b_token,
+            clock: &self.clock,
+            stake: self.stake.as_deref(),
+        })
+        .map_err(to_py_error)
+    }
+}
+
+/// Compute the swap out amount and fee of `in_amount` of `in_token_mint`
+#[pyfunction]
+fn compute_quote(
+    py: Python,
+    in_token_mint: &str,
+    in_amount: u64,
+    accounts: &PyQuoteAccounts,
+) -> PyResult<PyObject> {
+    let quote = crate::compute_quote(
+        parse_pubkey(in_token_mint)?,
+        in_amount,
+        accounts.to_quote_data()?,
+    )
+    .map_err(to_py_error)?;
+    to_py_object(py, &quote)
+}
+
+/// Compute the pool LP minted, and the token A, B required for a balanced deposit of `in_amount` of `in_token_mint`
+#[pyfunction]
+fn compute_deposit_quote(
+    py: Python,
+    in_token_mint: &str,
+    in_amount: u64,
+    pool_lp_mint: &[u8],
+    accounts: &PyQuoteAccounts,
+) -> PyResult<PyObject> {
+    let pool_lp_mint = deserialize_mint(pool_lp_mint).map_err(to_py_error)?;
+    let quote = liquidity::compute_deposit_quote(
+        parse_pubkey(in_token_mint)?,
+        in_amount,
+        pool_lp_mint.supply,
+        accounts.to_quote_data()?,
+    )
+    .map_err(to_py_error)?;
+    to_py_object(py, &quote)
+}
+
+/// Compute the token A, B received for a balanced withdraw of `pool_token_amount` pool LP
+#[pyfunction]
+fn compute_withdraw_quote(
+    py: Python,
+    pool_token_amount: u64,
+    pool_lp_mint: &[u8],
+    accounts: &PyQuoteAccounts,
+) -> PyResult<PyObject> {
+    let pool_lp_mint = deserialize_mint(pool_lp_mint).map_err(to_py_error)?;
+    let quote = liquidity::compute_withdraw_quote(
+        pool_token_amount,
+        pool_lp_mint.supply,
+        accounts.to_quote_data()?,
+    )
+    .map_err(to_py_error)?;
+    to_py_object(py, &quote)
+}
+
+/// Deserialize pool account data into a dict
+#[pyfunction(name = "deserialize_pool")]
+fn deserialize_pool_account(py: Python, data: &[u8]) -> PyResult<PyObject> {
+    let pool = deserialize_pool(data).map_err(to_py_error)?;
+    to_py_object(py, &PoolView::from(&pool))
+}
+
+/// Deserialize vault account data into a dict
+#[pyfunction(name = "deserialize_vault")]
+fn deserialize_vault_account(py: Python, data: &[u8]) -> PyResult<PyObject> {
+    let vault = deserialize_vault(data).map_err(to_py_error)?;
+    to_py_object(py, &VaultView::from(&vault))
+}
+
+/// Deserialize lock escrow account data into a dict
+#[pyfunction(name = "deserialize_lock_escrow")]
+fn deserialize_lock_escrow_account(py: Python, data: &[u8]) -> PyResult<PyObject> {
+    let lock_escrow = deserialize_lock_escrow(data).map_err(to_py_error)?;
+    to_py_object(py, &LockEscrowView::from(&lock_escrow))
+}
+
+/// Deserialize config account data into a dict
+#[pyfunction(name = "deserialize_config")]
+fn deserialize_config_account(py: Python, data: &[u8]) -> PyResult<PyObject> {
+    let config = deserialize_config(data).map_err(to_py_error)?;
+    to_py_object(py, &ConfigView::from(&config))
+}
+
+#[pymodule]
+fn dynamic_amm_quote(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyQuoteAccounts>()?;
+    m.add_function(wrap_pyfunction!(compute_quote, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_deposit_quote, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_withdraw_quote, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_pool_account, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_vault_account, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_lock_escrow_account, m)?)?;
+    m.add_function(wrap_pyfunction!(deserialize_config_account, m)?)?;
+    Ok(())
+}
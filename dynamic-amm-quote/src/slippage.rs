@@ -0,0 +1,54 @@
+//! Slippage tolerance, in bps, applied to quoted amounts
+use crate::{compute_quote, QuoteData};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context};
+
+pub const MAX_SLIPPAGE_BPS: u64 = 10_000;
+
+/// Minimum amount accepted for the quoted `amount`, rounded down
+pub fn get_minimum_amount(amount: u64, slippage_bps: u64) -> anyhow::Result<u64> {
+    ensure!(
+        slippage_bps <= MAX_SLIPPAGE_BPS,
+        "Slippage {} bps above {} bps",
+        slippage_bps,
+        MAX_SLIPPAGE_BPS
+    );
+    let minimum_amount = u128::from(amount)
+        .checked_mul((MAX_SLIPPAGE_BPS - slippage_bps).into())
+        .and_then(|amount| amount.checked_div(MAX_SLIPPAGE_BPS.into()))
+        .context("Math overflow")?;
+    Ok(minimum_amount.try_into()?)
+}
+
+/// Maximum amount accepted for the quoted `amount`, rounded up
+pub fn get_maximum_amount(amount: u64, slippage_bps: u64) -> anyhow::Result<u64> {
+    ensure!(
+        slippage_bps <= MAX_SLIPPAGE_BPS,
+        "Slippage {} bps above {} bps",
+        slippage_bps,
+        MAX_SLIPPAGE_BPS
+    );
+    let maximum_amount = u128::from(amount)
+        .checked_mul((MAX_SLIPPAGE_BPS + slippage_bps).into())
+        .and_then(|amount| amount.checked_add((MAX_SLIPPAGE_BPS - 1).into()))
+        .and_then(|amount| amount.checked_div(MAX_SLIPPAGE_BPS.into()))
+        .context("Math overflow")?;
+    Ok(maximum_amount.try_into()?)
+}
+
+/// Price impact, in bps, of swapping `in_amount` of `in_token_mint` for the quoted `out_amount`.
+/// The marginal price is approximated by a swap of a thousandth of `in_amount`.
+pub fn compute_price_impact_bps(
+    quote_data: &QuoteData,
+    in_token_mint: Pubkey,
+    in_amount: u64,
+    out_amount: u64,
+) -> anyhow::Result<f64> {
+    let marginal_in_amount = (in_amount / 1000).max(1);
+    let marginal_quote = compute_quote(in_token_mint, marginal_in_amount, quote_data.clone())?;
+    ensure!(marginal_quote.out_amount > 0, "Reference swap too small");
+
+    let marginal_price = marginal_quote.out_amount as f64 / marginal_in_amount as f64;
+    let price = out_amount as f64 / in_amount as f64;
+    Ok((1.0 - price / marginal_price) * 10_000.0)
+}
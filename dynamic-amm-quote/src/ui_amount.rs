@@ -0,0 +1,110 @@
+//! Conversion between raw token amounts and decimal adjusted UI amounts
+use crate::{compute_quote, QuoteData, QuoteResult};
+use anchor_lang::prelude::Pubkey;
+use anyhow::{ensure, Context};
+
+/// Parse UI amount, eg: "1.5", into raw token amount. Fail when the amount has more fractional digits than `decimals`.
+pub fn parse_ui_amount(ui_amount: &str, decimals: u8) -> anyhow::Result<u64> {
+    let ui_amount = ui_amount.trim();
+    let (integer, fraction) = ui_amount.split_once('.').unwrap_or((ui_amount, ""));
+
+    ensure!(
+        !(integer.is_empty() && fraction.is_empty())
+            && integer.chars().all(|c| c.is_ascii_digit())
+            && fraction.chars().all(|c| c.is_ascii_digit()),
+        "Invalid amount {}",
+        ui_amount
+    );
+    ensure!(
+        fraction.len() <= decimals.into(),
+        "Amount {} has more than {} decimals",
+        ui_amount,
+        decimals
+    );
+
+    let integer: u64 = if integer.is_empty() {
+        0
+    } else {
+        integer.parse().context("Amount overflow")?
+    };
+    let fraction = format!("{:0<width$}", fraction, width = decimals.into());
+    let fraction: u64 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().context("Amount overflow")?
+    };
+
+    10u64
+        .checked_pow(decimals.into())
+        .and_then(|multiplier| integer.checked_mul(multiplier))
+        .and_then(|amount| amount.checked_add(fraction))
+        .context("Amount overflow")
+}
+
+/// Format raw token amount into UI amount. Trailing zeros of the fractional part are trimmed.
+pub fn format_ui_amount(amount: u64, decimals: u8) -> String {
+    let decimals: usize = decimals.into();
+    let digits = format!("{:0>width$}", amount, width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{}.{}", integer, fraction)
+    }
+}
+
+/// Price of one whole base token, in whole quote token
+pub fn get_ui_price(
+    base_amount: u64,
+    base_decimals: u8,
+    quote_amount: u64,
+    quote_decimals: u8,
+) -> Option<f64> {
+    if base_amount == 0 {
+        return None;
+    }
+    let base_amount = base_amount as f64 / 10f64.powi(base_decimals.into());
+    let quote_amount = quote_amount as f64 / 10f64.powi(quote_decimals.into());
+    Some(quote_amount / base_amount)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UiQuoteResult {
+    /// Swap in amount in UI unit
+    pub in_amount: String,
+    /// Swap out amount in UI unit
+    pub out_amount: String,
+    /// Total fee amount in UI unit of the in token
+    pub fee: String,
+    /// Execution price of one whole in token, in whole out token
+    pub price: Option<f64>,
+    /// Quote in raw token amount
+    pub quote: QuoteResult,
+}
+
+/// Compute quote of UI amount `ui_in_amount` of `in_token_mint`
+pub fn compute_ui_quote(
+    in_token_mint: Pubkey,
+    ui_in_amount: &str,
+    in_token_decimals: u8,
+    out_token_decimals: u8,
+    quote_data: QuoteData,
+) -> anyhow::Result<UiQuoteResult> {
+    let in_amount = parse_ui_amount(ui_in_amount, in_token_decimals)?;
+    let quote = compute_quote(in_token_mint, in_amount, quote_data)?;
+
+    Ok(UiQuoteResult {
+        in_amount: format_ui_amount(in_amount, in_token_decimals),
+        out_amount: format_ui_amount(quote.out_amount, out_token_decimals),
+        fee: format_ui_amount(quote.fee, in_token_decimals),
+        price: get_ui_price(
+            in_amount,
+            in_token_decimals,
+            quote.out_amount,
+            out_token_decimals,
+        ),
+        quote,
+    })
+}
@@ -0,0 +1,276 @@
+//! Serializable views of the program states. Public keys are represented in base58.
+use crate::pda::{AddressMismatch, PoolOrigin};
+use prog_dynamic_amm::state::{Config, CurveType, DepegType, LockEscrow, Pool, PoolFees, PoolType};
+use prog_dynamic_vault::state::Vault;
+use serde::Serialize;
+
+/// Serializable view of [PoolFees]
+#[derive(Serialize, Debug, Clone)]
+pub struct PoolFeesView {
+    pub trade_fee_numerator: u64,
+    pub trade_fee_denominator: u64,
+    pub protocol_trade_fee_numerator: u64,
+    pub protocol_trade_fee_denominator: u64,
+}
+
+impl From<&PoolFees> for PoolFeesView {
+    fn from(fees: &PoolFees) -> Self {
+        Self {
+            trade_fee_numerator: fees.trade_fee_numerator,
+            trade_fee_denominator: fees.trade_fee_denominator,
+            protocol_trade_fee_numerator: fees.protocol_trade_fee_numerator,
+            protocol_trade_fee_denominator: fees.protocol_trade_fee_denominator,
+        }
+    }
+}
+
+/// Serializable view of [CurveType]
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum CurveTypeView {
+    ConstantProduct,
+    Stable {
+        amp: u64,
+        token_a_multiplier: u64,
+        token_b_multiplier: u64,
+        precision_factor: u8,
+        base_virtual_price: u64,
+        base_cache_updated: u64,
+        depeg_type: String,
+        last_amp_updated_timestamp: u64,
+    },
+}
+
+impl From<&CurveType> for CurveTypeView {
+    fn from(curve_type: &CurveType) -> Self {
+        match curve_type {
+            CurveType::ConstantProduct => CurveTypeView::ConstantProduct,
+            CurveType::Stable {
+                amp,
+                token_multiplier,
+                depeg,
+                last_amp_updated_timestamp,
+            } => CurveTypeView::Stable {
+                amp: *amp,
+                token_a_multiplier: token_multiplier.token_a_multiplier,
+                token_b_multiplier: token_multiplier.token_b_multiplier,
+                precision_factor: token_multiplier.precision_factor,
+                base_virtual_price: depeg.base_virtual_price,
+                base_cache_updated: depeg.base_cache_updated,
+                depeg_type: match depeg.depeg_type {
+                    DepegType::None => "None",
+                    DepegType::Marinade => "Marinade",
+                    DepegType::Lido => "Lido",
+                    DepegType::SplStake => "SplStake",
+                }
+                .to_string(),
+                last_amp_updated_timestamp: *last_amp_updated_timestamp,
+            },
+        }
+    }
+}
+
+/// Serializable view of [Pool]
+#[derive(Serialize, Debug, Clone)]
+pub struct PoolView {
+    pub lp_mint: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub a_vault: String,
+    pub b_vault: String,
+    pub a_vault_lp: String,
+    pub b_vault_lp: String,
+    pub a_vault_lp_bump: u8,
+    pub enabled: bool,
+    pub protocol_token_a_fee: String,
+    pub protocol_token_b_fee: String,
+    pub fee_last_updated_at: u64,
+    pub fees: PoolFeesView,
+    pub pool_type: String,
+    pub stake: String,
+    pub total_locked_lp: u64,
+    pub activation_point: u64,
+    pub whitelisted_vault: String,
+    pub activation_type: u8,
+    pub partner_fee_numerator: u64,
+    pub partner_authority: String,
+    pub partner_pending_fee_a: u64,
+    pub partner_pending_fee_b: u64,
+    pub curve_type: CurveTypeView,
+}
+
+impl From<&Pool> for PoolView {
+    fn from(pool: &Pool) -> Self {
+        Self {
+            lp_mint: pool.lp_mint.to_string(),
+            token_a_mint: pool.token_a_mint.to_string(),
+            token_b_mint: pool.token_b_mint.to_string(),
+            a_vault: pool.a_vault.to_string(),
+            b_vault: pool.b_vault.to_string(),
+            a_vault_lp: pool.a_vault_lp.to_string(),
+            b_vault_lp: pool.b_vault_lp.to_string(),
+            a_vault_lp_bump: pool.a_vault_lp_bump,
+            enabled: pool.enabled,
+            protocol_token_a_fee: pool.protocol_token_a_fee.to_string(),
+            protocol_token_b_fee: pool.protocol_token_b_fee.to_string(),
+            fee_last_updated_at: pool.fee_last_updated_at,
+            fees: (&pool.fees).into(),
+            pool_type: match pool.pool_type {
+                PoolType::Permissioned => "Permissioned",
+                PoolType::Permissionless => "Permissionless",
+            }
+            .to_string(),
+            stake: pool.stake.to_string(),
+            total_locked_lp: pool.total_locked_lp,
+            activation_point: pool.bootstrapping.activation_point,
+            whitelisted_vault: pool.bootstrapping.whitelisted_vault.to_string(),
+            activation_type: pool.bootstrapping.activation_type,
+            partner_fee_numerator: pool.partner_info.fee_numerator,
+            partner_authority: pool.partner_info.partner_authority.to_string(),
+            partner_pending_fee_a: pool.partner_info.pending_fee_a,
+            partner_pending_fee_b: pool.partner_info.pending_fee_b,
+            curve_type: (&pool.curve_type).into(),
+        }
+    }
+}
+
+/// Serializable view of [Vault]
+#[derive(Serialize, Debug, Clone)]
+pub struct VaultView {
+    pub enabled: u8,
+    pub total_amount: u64,
+    pub token_vault: String,
+    pub fee_vault: String,
+    pub token_mint: String,
+    pub lp_mint: String,
+    /// Non default strategy addresses
+    pub strategies: Vec<String>,
+    pub base: String,
+    pub admin: String,
+    pub operator: String,
+    pub last_updated_locked_profit: u64,
+    pub last_report: u64,
+    pub locked_profit_degradation: u64,
+}
+
+impl From<&Vault> for VaultView {
+    fn from(vault: &Vault) -> Self {
+        Self {
+            enabled: vault.enabled,
+            total_amount: vault.total_amount,
+            token_vault: vault.token_vault.to_string(),
+            fee_vault: vault.fee_vault.to_string(),
+            token_mint: vault.token_mint.to_string(),
+            lp_mint: vault.lp_mint.to_string(),
+            strategies: vault
+                .get_strategies()
+                .map(|strategy| strategy.to_string())
+                .collect(),
+            base: vault.base.to_string(),
+            admin: vault.admin.to_string(),
+            operator: vault.operator.to_string(),
+            last_updated_locked_profit: vault.locked_profit_tracker.last_updated_locked_profit,
+            last_report: vault.locked_profit_tracker.last_report,
+            locked_profit_degradation: vault.locked_profit_tracker.locked_profit_degradation,
+        }
+    }
+}
+
+/// Serializable view of [LockEscrow]
+#[derive(Serialize, Debug, Clone)]
+pub struct LockEscrowView {
+    pub pool: String,
+    pub owner: String,
+    pub escrow_vault: String,
+    pub bump: u8,
+    pub total_locked_amount: u64,
+    pub lp_per_token: u128,
+    pub unclaimed_fee_pending: u64,
+    pub a_fee: u64,
+    pub b_fee: u64,
+}
+
+impl From<&LockEscrow> for LockEscrowView {
+    fn from(lock_escrow: &LockEscrow) -> Self {
+        Self {
+            pool: lock_escrow.pool.to_string(),
+            owner: lock_escrow.owner.to_string(),
+            escrow_vault: lock_escrow.escrow_vault.to_string(),
+            bump: lock_escrow.bump,
+            total_locked_amount: lock_escrow.total_locked_amount,
+            lp_per_token: lock_escrow.lp_per_token,
+            unclaimed_fee_pending: lock_escrow.unclaimed_fee_pending,
+            a_fee: lock_escrow.a_fee,
+            b_fee: lock_escrow.b_fee,
+        }
+    }
+}
+
+/// Serializable view of [Config]
+#[derive(Serialize, Debug, Clone)]
+pub struct ConfigView {
+    pub pool_fees: PoolFeesView,
+    pub activation_duration: u64,
+    pub vault_config_key: String,
+    pub pool_creator_authority: String,
+    pub activation_type: u8,
+    pub partner_fee_numerator: u64,
+}
+
+impl From<&Config> for ConfigView {
+    fn from(config: &Config) -> Self {
+        Self {
+            pool_fees: (&config.pool_fees).into(),
+            activation_duration: config.activation_duration,
+            vault_config_key: config.vault_config_key.to_string(),
+            pool_creator_authority: config.pool_creator_authority.to_string(),
+            activation_type: config.activation_type,
+            partner_fee_numerator: config.partner_fee_numerator,
+        }
+    }
+}
+
+/// Serializable view of [PoolOrigin]
+#[derive(Serialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum PoolOriginView {
+    FeeTier { trade_fee_bps: u64 },
+    Config { config: String },
+    Customizable,
+    Permissioned,
+    Unknown,
+}
+
+impl From<&PoolOrigin> for PoolOriginView {
+    fn from(origin: &PoolOrigin) -> Self {
+        match origin {
+            PoolOrigin::FeeTier { trade_fee_bps } => PoolOriginView::FeeTier {
+                trade_fee_bps: *trade_fee_bps,
+            },
+            PoolOrigin::Config { config } => PoolOriginView::Config {
+                config: config.to_string(),
+            },
+            PoolOrigin::Customizable => PoolOriginView::Customizable,
+            PoolOrigin::Permissioned => PoolOriginView::Permissioned,
+            PoolOrigin::Unknown => PoolOriginView::Unknown,
+        }
+    }
+}
+
+/// Serializable view of [AddressMismatch]
+#[derive(Serialize, Debug, Clone)]
+pub struct AddressMismatchView {
+    pub field: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl From<&AddressMismatch> for AddressMismatchView {
+    fn from(mismatch: &AddressMismatch) -> Self {
+        Self {
+            field: mismatch.field.to_string(),
+            expected: mismatch.expected.to_string(),
+            actual: mismatch.actual.to_string(),
+        }
+    }
+}
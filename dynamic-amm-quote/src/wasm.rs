@@ -0,0 +1,147 @@
+//! WASM bindings of the quote engine. All accounts are passed in as raw account data.
+use crate::account::{deserialize_pool, deserialize_vault, QuoteAccounts};
+use crate::pda;
+use crate::view::{PoolView, VaultView};
+use crate::QuoteData;
+use anchor_lang::prelude::Pubkey;
+use serde::Serialize;
+use std::fmt::Display;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+fn to_js_error(e: impl Display) -> JsError {
+    JsError::new(&e.to_string())
+}
+
+fn parse_pubkey(key: &str) -> Result<Pubkey, JsError> {
+    Pubkey::from_str(key).map_err(to_js_error)
+}
+
+fn to_js_value<T: Serialize>(value: &T) -> Result<JsValue, JsError> {
+    // u64 and u128 values can exceed Number.MAX_SAFE_INTEGER, therefore they are always serialized as BigInt
+    let serializer =
+        serde_wasm_bindgen::Serializer::new().serialize_large_number_types_as_bigints(true);
+    value.serialize(&serializer).map_err(to_js_error)
+}
+
+/// Compute the swap out amount and fee of `in_amount` of `in_token_mint`. `stake` is the account data of the stake account returned by `depeg::get_stake_account`, only required by depeg pools.
+#[allow(clippy::too_many_arguments)]
+#[wasm_bindgen(js_name = computeQuote)]
+pub fn compute_quote(
+    in_token_mint: &str,
+    in_amount: u64,
+    pool: &[u8],
+    vault_a: &[u8],
+    vault_b: &[u8],
+    pool_vault_a_lp_token: &[u8],
+    pool_vault_b_lp_token: &[u8],
+    vault_a_lp_mint: &[u8],
+    vault_b_lp_mint: &[u8],
+    vault_a_token: &[u8],
+    vault_b_token: &[u8],
+    clock: &[u8],
+    stake: Option<Vec<u8>>,
+) -> Result<JsValue, JsError> {
+    let quote_data = QuoteData::try_from(QuoteAccounts {
+        pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token,
+        pool_vault_b_lp_token,
+        vault_a_lp_mint,
+        vault_b_lp_mint,
+        vault_a_token,
+        vault_b_token,
+        clock,
+        stake: stake.as_deref(),
+    })
+    .map_err(to_js_error)?;
+
+    let quote = crate::compute_quote(parse_pubkey(in_token_mint)?, in_amount, quote_data)
+        .map_err(to_js_error)?;
+
+    to_js_value(&quote)
+}
+
+/// Deserialize pool account data
+#[wasm_bindgen(js_name = deserializePool)]
+pub fn deserialize_pool_account(data: &[u8]) -> Result<JsValue, JsError> {
+    let pool = deserialize_pool(data).map_err(to_js_error)?;
+    to_js_value(&PoolView::from(&pool))
+}
+
+/// Deserialize vault account data
+#[wasm_bindgen(js_name = deserializeVault)]
+pub fn deserialize_vault_account(data: &[u8]) -> Result<JsValue, JsError> {
+    let vault = deserialize_vault(data).map_err(to_js_error)?;
+    to_js_value(&VaultView::from(&vault))
+}
+
+/// Derive the address of the constant product pool created with fee tier
+#[wasm_bindgen(js_name = derivePoolAddress)]
+pub fn derive_pool_address(
+    token_a_mint: &str,
+    token_b_mint: &str,
+    trade_fee_bps: u64,
+) -> Result<String, JsError> {
+    let pool = pda::derive_pool_address(
+        parse_pubkey(token_a_mint)?,
+        parse_pubkey(token_b_mint)?,
+        trade_fee_bps,
+    );
+    Ok(pool.to_string())
+}
+
+/// Derive the address of the constant product pool created with config
+#[wasm_bindgen(js_name = derivePoolAddressWithConfig)]
+pub fn derive_constant_product_pool_with_config(
+    token_a_mint: &str,
+    token_b_mint: &str,
+    config: &str,
+) -> Result<String, JsError> {
+    let pool = pda::derive_constant_product_pool_with_config(
+        parse_pubkey(token_a_mint)?,
+        parse_pubkey(token_b_mint)?,
+        parse_pubkey(config)?,
+    );
+    Ok(pool.to_string())
+}
+
+/// Derive the LP mint address of the pool
+#[wasm_bindgen(js_name = derivePoolLpMintAddress)]
+pub fn derive_pool_lp_mint_address(pool: &str) -> Result<String, JsError> {
+    Ok(pda::derive_pool_lp_mint_address(parse_pubkey(pool)?).to_string())
+}
+
+/// Derive the vault LP token account address of the pool
+#[wasm_bindgen(js_name = deriveVaultLpTokenAddress)]
+pub fn derive_vault_lp_token_address(vault: &str, pool: &str) -> Result<String, JsError> {
+    Ok(pda::derive_vault_lp_token_address(parse_pubkey(vault)?, parse_pubkey(pool)?).to_string())
+}
+
+/// Derive the protocol fee token account address of the pool
+#[wasm_bindgen(js_name = derivePoolFeeTokenAddress)]
+pub fn derive_pool_fee_token_address(token_mint: &str, pool: &str) -> Result<String, JsError> {
+    Ok(
+        pda::derive_pool_fee_token_address(parse_pubkey(token_mint)?, parse_pubkey(pool)?)
+            .to_string(),
+    )
+}
+
+/// Derive the vault address of the token mint
+#[wasm_bindgen(js_name = deriveVaultAddress)]
+pub fn derive_vault_address(token_mint: &str) -> Result<String, JsError> {
+    Ok(pda::derive_vault_address(&parse_pubkey(token_mint)?).to_string())
+}
+
+/// Derive the token vault address of the vault
+#[wasm_bindgen(js_name = deriveTokenVaultAddress)]
+pub fn derive_token_vault_address(vault: &str) -> Result<String, JsError> {
+    Ok(pda::derive_token_vault_address(&parse_pubkey(vault)?).to_string())
+}
+
+/// Derive the LP mint address of the vault
+#[wasm_bindgen(js_name = deriveVaultLpMintAddress)]
+pub fn derive_vault_lp_mint_address(vault: &str) -> Result<String, JsError> {
+    Ok(pda::derive_vault_lp_mint_address(&parse_pubkey(vault)?).to_string())
+}
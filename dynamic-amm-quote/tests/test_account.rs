@@ -0,0 +1,264 @@
+use anchor_lang::prelude::{AnchorDeserialize, Clock};
+use anchor_lang::AccountSerialize;
+use anchor_spl::token::spl_token::state::{Account, AccountState, Mint};
+use dynamic_amm_quote::account::{
+    deserialize_clock, deserialize_mint, deserialize_pool, deserialize_token_account,
+    deserialize_vault, QuoteAccounts,
+};
+use dynamic_amm_quote::depeg::{get_stake_account, marinade};
+use dynamic_amm_quote::QuoteData;
+use prog_dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, TokenMultiplier};
+use prog_dynamic_vault::state::Vault;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn get_pool() -> Pool {
+    let mut pool: Pool = zeroed();
+    pool.lp_mint = Pubkey::new_unique();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.a_vault = Pubkey::new_unique();
+    pool.b_vault = Pubkey::new_unique();
+    pool.stake = Pubkey::new_unique();
+    pool.enabled = true;
+    pool.fees.trade_fee_numerator = 250;
+    pool.fees.trade_fee_denominator = 100_000;
+    pool.total_locked_lp = 1_000;
+    pool.bootstrapping.activation_point = 42;
+    pool.partner_info.fee_numerator = 5_000;
+    pool.curve_type = CurveType::Stable {
+        amp: 100,
+        token_multiplier: TokenMultiplier {
+            token_a_multiplier: 1,
+            token_b_multiplier: 1_000,
+            precision_factor: 9,
+        },
+        depeg: Depeg {
+            base_virtual_price: 0,
+            base_cache_updated: 0,
+            depeg_type: DepegType::None,
+        },
+        last_amp_updated_timestamp: 7,
+    };
+    pool
+}
+
+fn set_depeg_type(pool: &mut Pool, depeg_type: DepegType) {
+    if let CurveType::Stable { depeg, .. } = &mut pool.curve_type {
+        depeg.depeg_type = depeg_type;
+    }
+}
+
+fn serialize<T: AccountSerialize>(account: &T) -> Vec<u8> {
+    let mut data = vec![];
+    account.try_serialize(&mut data).unwrap();
+    data
+}
+
+fn token_account_data(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+    let mut data = vec![0u8; Account::LEN];
+    Account {
+        mint,
+        owner,
+        amount,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+fn mint_data(supply: u64, decimals: u8) -> Vec<u8> {
+    let mut data = vec![0u8; Mint::LEN];
+    Mint {
+        supply,
+        decimals,
+        is_initialized: true,
+        ..Mint::default()
+    }
+    .pack_into_slice(&mut data);
+    data
+}
+
+#[test]
+fn test_deserialize_accounts() {
+    let pool = get_pool();
+    let pool_data = serialize(&pool);
+    let decoded = deserialize_pool(&pool_data).unwrap();
+    assert_eq!(decoded.lp_mint, pool.lp_mint);
+    assert_eq!(decoded.token_a_mint, pool.token_a_mint);
+    assert_eq!(decoded.fees.trade_fee_numerator, 250);
+    assert_eq!(decoded.total_locked_lp, 1_000);
+    assert_eq!(decoded.bootstrapping.activation_point, 42);
+    assert_eq!(decoded.partner_info.fee_numerator, 5_000);
+    assert!(matches!(
+        decoded.curve_type,
+        CurveType::Stable {
+            amp: 100,
+            last_amp_updated_timestamp: 7,
+            ..
+        }
+    ));
+
+    let mut vault: Vault = zeroed();
+    vault.total_amount = 123;
+    vault.token_mint = pool.token_a_mint;
+    let vault_data = serialize(&vault);
+    let decoded = deserialize_vault(&vault_data).unwrap();
+    assert_eq!(decoded.total_amount, 123);
+    assert_eq!(decoded.token_mint, pool.token_a_mint);
+
+    // Discriminator mismatch
+    assert!(deserialize_pool(&vault_data).is_err());
+    assert!(deserialize_vault(&pool_data).is_err());
+    assert!(deserialize_pool(&pool_data[..100]).is_err());
+
+    let owner = Pubkey::new_unique();
+    let token_account =
+        deserialize_token_account(&token_account_data(pool.token_a_mint, owner, 500)).unwrap();
+    assert_eq!(token_account.mint, pool.token_a_mint);
+    assert_eq!(token_account.owner, owner);
+    assert_eq!(token_account.amount, 500);
+
+    let mint = deserialize_mint(&mint_data(1_000_000, 6)).unwrap();
+    assert_eq!(mint.supply, 1_000_000);
+    assert_eq!(mint.decimals, 6);
+
+    let clock = Clock {
+        slot: 10,
+        unix_timestamp: 20,
+        ..Clock::default()
+    };
+    let decoded = deserialize_clock(&bincode::serialize(&clock).unwrap()).unwrap();
+    assert_eq!(decoded.slot, 10);
+    assert_eq!(decoded.unix_timestamp, 20);
+}
+
+#[test]
+fn test_quote_accounts_to_quote_data() {
+    let mut pool = get_pool();
+    set_depeg_type(&mut pool, DepegType::SplStake);
+    let mut vault_a: Vault = zeroed();
+    vault_a.total_amount = 1_000;
+    let mut vault_b: Vault = zeroed();
+    vault_b.total_amount = 2_000;
+
+    let pool_data = serialize(&pool);
+    let vault_a_data = serialize(&vault_a);
+    let vault_b_data = serialize(&vault_b);
+    let a_vault_lp = Pubkey::new_unique();
+    let b_vault_lp = Pubkey::new_unique();
+    let pool_vault_a_lp_token = token_account_data(a_vault_lp, pool.a_vault, 100);
+    let pool_vault_b_lp_token = token_account_data(b_vault_lp, pool.b_vault, 200);
+    let vault_a_lp_mint = mint_data(1_000, 6);
+    let vault_b_lp_mint = mint_data(2_000, 9);
+    let vault_a_token = token_account_data(pool.token_a_mint, pool.a_vault, 300);
+    let vault_b_token = token_account_data(pool.token_b_mint, pool.b_vault, 400);
+    let clock = bincode::serialize(&Clock::default()).unwrap();
+    let stake = vec![1u8, 2, 3];
+
+    macro_rules! accounts {
+        ($pool: expr, $vault_a: expr, $stake: expr) => {
+            QuoteAccounts {
+                pool: $pool,
+                vault_a: $vault_a,
+                vault_b: &vault_b_data,
+                pool_vault_a_lp_token: &pool_vault_a_lp_token,
+                pool_vault_b_lp_token: &pool_vault_b_lp_token,
+                vault_a_lp_mint: &vault_a_lp_mint,
+                vault_b_lp_mint: &vault_b_lp_mint,
+                vault_a_token: &vault_a_token,
+                vault_b_token: &vault_b_token,
+                clock: &clock,
+                stake: $stake,
+            }
+        };
+    }
+
+    let quote_data =
+        QuoteData::try_from(accounts!(&pool_data, &vault_a_data, Some(stake.as_slice()))).unwrap();
+    assert_eq!(quote_data.pool.lp_mint, pool.lp_mint);
+    assert_eq!(quote_data.vault_a.total_amount, 1_000);
+    assert_eq!(quote_data.vault_b.total_amount, 2_000);
+    assert_eq!(quote_data.pool_vault_a_lp_token.amount, 100);
+    assert_eq!(quote_data.pool_vault_b_lp_token.amount, 200);
+    assert_eq!(quote_data.vault_a_lp_mint.decimals, 6);
+    assert_eq!(quote_data.vault_b_lp_mint.supply, 2_000);
+    assert_eq!(quote_data.vault_a_token.amount, 300);
+    assert_eq!(quote_data.vault_b_token.amount, 400);
+    assert_eq!(get_stake_account(&pool), Some(pool.stake));
+    assert_eq!(quote_data.stake_data.get(&pool.stake), Some(&stake));
+
+    let quote_data = QuoteData::try_from(accounts!(&pool_data, &vault_a_data, None)).unwrap();
+    assert!(quote_data.stake_data.is_empty());
+
+    // Marinade pools read the virtual price from the Marinade state, not from `Pool::stake`
+    let mut marinade_pool = pool.clone();
+    set_depeg_type(&mut marinade_pool, DepegType::Marinade);
+    let marinade_pool_data = serialize(&marinade_pool);
+    let quote_data = QuoteData::try_from(accounts!(
+        &marinade_pool_data,
+        &vault_a_data,
+        Some(stake.as_slice())
+    ))
+    .unwrap();
+    assert_eq!(
+        quote_data.stake_data.get(&marinade::stake::ID),
+        Some(&stake)
+    );
+    assert!(quote_data.stake_data.get(&pool.stake).is_none());
+
+    // Not a depeg pool
+    let mut stable_pool = pool.clone();
+    set_depeg_type(&mut stable_pool, DepegType::None);
+    let stable_pool_data = serialize(&stable_pool);
+    let quote_data = QuoteData::try_from(accounts!(
+        &stable_pool_data,
+        &vault_a_data,
+        Some(stake.as_slice())
+    ))
+    .unwrap();
+    assert!(quote_data.stake_data.is_empty());
+
+    // Accounts swapped by mistake
+    assert!(QuoteData::try_from(accounts!(&pool_data, &pool_data, None)).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_pool_view() {
+    use dynamic_amm_quote::view::{CurveTypeView, PoolView};
+
+    let pool = get_pool();
+    let view = PoolView::from(&pool);
+    assert_eq!(view.lp_mint, pool.lp_mint.to_string());
+    assert_eq!(view.token_a_mint, pool.token_a_mint.to_string());
+    assert_eq!(view.stake, pool.stake.to_string());
+    assert!(view.enabled);
+    assert_eq!(view.fees.trade_fee_numerator, 250);
+    assert_eq!(view.pool_type, "Permissioned");
+    assert_eq!(view.activation_point, 42);
+    assert_eq!(view.partner_fee_numerator, 5_000);
+    match view.curve_type {
+        CurveTypeView::Stable {
+            amp,
+            token_b_multiplier,
+            precision_factor,
+            depeg_type,
+            last_amp_updated_timestamp,
+            ..
+        } => {
+            assert_eq!(amp, 100);
+            assert_eq!(token_b_multiplier, 1_000);
+            assert_eq!(precision_factor, 9);
+            assert_eq!(depeg_type, "None");
+            assert_eq!(last_amp_updated_timestamp, 7);
+        }
+        CurveTypeView::ConstantProduct => panic!("Expected a stable curve"),
+    }
+}
@@ -0,0 +1,110 @@
+use anchor_lang::prelude::{AnchorDeserialize, Clock};
+use dynamic_amm_quote::admin::{
+    diff_pool, validate_activation_point, validate_amp_change, validate_pool_fees,
+};
+use prog_dynamic_amm::constants::MIN_CHANGE_AMP_DURATION;
+use prog_dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, PoolFees, TokenMultiplier};
+
+fn get_pool() -> Pool {
+    let data = vec![0u8; std::mem::size_of::<Pool>()];
+    Pool::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn get_clock(slot: u64, unix_timestamp: i64) -> Clock {
+    Clock {
+        slot,
+        unix_timestamp,
+        ..Clock::default()
+    }
+}
+
+fn get_stable_curve(amp: u64, last_amp_updated_timestamp: u64) -> CurveType {
+    CurveType::Stable {
+        amp,
+        token_multiplier: TokenMultiplier::default(),
+        depeg: Depeg {
+            base_virtual_price: 0,
+            base_cache_updated: 0,
+            depeg_type: DepegType::None,
+        },
+        last_amp_updated_timestamp,
+    }
+}
+
+#[test]
+fn test_validate_pool_fees() {
+    let mut pool = get_pool();
+    let clock = get_clock(0, 10_000);
+    let fees = PoolFees {
+        trade_fee_numerator: 1000,
+        trade_fee_denominator: 100_000,
+        protocol_trade_fee_numerator: 20_000,
+        protocol_trade_fee_denominator: 100_000,
+    };
+    assert!(validate_pool_fees(&pool, &fees, 0, false, &clock).is_ok());
+    assert!(validate_pool_fees(&pool, &fees, 0, true, &clock).is_ok());
+
+    let high_fees = PoolFees {
+        trade_fee_numerator: 15_001,
+        ..fees
+    };
+    assert!(validate_pool_fees(&pool, &high_fees, 0, false, &clock).is_err());
+
+    let low_fees = PoolFees {
+        trade_fee_numerator: 100,
+        ..fees
+    };
+    assert!(validate_pool_fees(&pool, &low_fees, 0, false, &clock).is_ok());
+    assert!(validate_pool_fees(&pool, &low_fees, 0, true, &clock).is_err());
+
+    pool.fee_last_updated_at = 9_000;
+    assert!(validate_pool_fees(&pool, &fees, 0, true, &clock).is_err());
+    assert!(validate_pool_fees(&pool, &fees, 0, false, &clock).is_ok());
+}
+
+#[test]
+fn test_validate_amp_change() {
+    let mut pool = get_pool();
+    let clock = get_clock(0, 10_000);
+    assert!(validate_amp_change(&pool, 200, &clock).is_err());
+
+    pool.curve_type = get_stable_curve(100, 10_000 - MIN_CHANGE_AMP_DURATION);
+    match validate_amp_change(&pool, 200, &clock).unwrap() {
+        CurveType::Stable { amp, .. } => assert_eq!(amp, 200),
+        CurveType::ConstantProduct => panic!("Expected stable curve"),
+    }
+    assert!(validate_amp_change(&pool, 0, &clock).is_err());
+    assert!(validate_amp_change(&pool, 10_001, &clock).is_err());
+
+    pool.curve_type = get_stable_curve(100, 10_000 - MIN_CHANGE_AMP_DURATION + 1);
+    assert!(validate_amp_change(&pool, 200, &clock).is_err());
+}
+
+#[test]
+fn test_validate_activation_point() {
+    let mut pool = get_pool();
+    pool.bootstrapping.activation_type = 1;
+    pool.bootstrapping.activation_point = 20_000;
+    let clock = get_clock(0, 10_000);
+
+    assert!(validate_activation_point(&pool, 15_000, &clock).is_ok());
+    assert!(validate_activation_point(&pool, 10_001, &clock).is_err());
+
+    let clock = get_clock(0, 20_000);
+    assert!(validate_activation_point(&pool, 30_000, &clock).is_err());
+}
+
+#[test]
+fn test_diff_pool() {
+    let before = get_pool();
+    let mut after = before.clone();
+    after.enabled = true;
+    after.fees.trade_fee_numerator = 250;
+
+    let changes = diff_pool(&before, &after);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].field, "enabled");
+    assert_eq!(changes[0].before, "false");
+    assert_eq!(changes[0].after, "true");
+    assert_eq!(changes[1].field, "fees.trade_fee_numerator");
+}
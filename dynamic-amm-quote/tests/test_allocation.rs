@@ -0,0 +1,103 @@
+use anchor_lang::prelude::AnchorDeserialize;
+use dynamic_amm_quote::allocation::compute_vault_allocation;
+use prog_dynamic_vault::state::{Strategy, StrategyType, Vault};
+use solana_sdk::pubkey::Pubkey;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn get_strategy(vault: Pubkey, strategy_type: StrategyType, current_liquidity: u64) -> Strategy {
+    Strategy {
+        vault,
+        strategy_type,
+        current_liquidity,
+        ..Strategy::default()
+    }
+}
+
+#[test]
+fn test_compute_vault_allocation() {
+    let vault = Pubkey::new_unique();
+    let mut vault_state: Vault = zeroed();
+    vault_state.total_amount = 1_000;
+
+    let strategies = vec![
+        (
+            Pubkey::new_unique(),
+            get_strategy(vault, StrategyType::Kamino, 300),
+        ),
+        (
+            Pubkey::new_unique(),
+            get_strategy(vault, StrategyType::Marginfi, 200),
+        ),
+        (
+            Pubkey::new_unique(),
+            Strategy {
+                is_disable: 1,
+                ..get_strategy(vault, StrategyType::Kamino, 100)
+            },
+        ),
+    ];
+
+    let allocation = compute_vault_allocation(vault, &vault_state, &strategies).unwrap();
+    assert_eq!(allocation.vault, vault);
+    assert_eq!(allocation.total_amount, 1_000);
+    assert_eq!(allocation.idle_amount, 400);
+    assert_eq!(allocation.strategies.len(), 3);
+    assert_eq!(allocation.strategies[0].strategy, strategies[0].0);
+    assert!(!allocation.strategies[0].is_disabled);
+    assert!(allocation.strategies[2].is_disabled);
+
+    // Idle and strategy liquidity sum up to the vault total amount
+    let deployed_amount = allocation
+        .strategies
+        .iter()
+        .map(|strategy| strategy.current_liquidity)
+        .sum::<u64>();
+    assert_eq!(
+        allocation.idle_amount + deployed_amount,
+        allocation.total_amount
+    );
+
+    // Grouped in the order the strategy type first appears
+    assert_eq!(
+        allocation.get_amount_by_strategy_type(),
+        vec![(StrategyType::Kamino, 400), (StrategyType::Marginfi, 200)]
+    );
+    assert_eq!(allocation.get_share(400), 0.4);
+    assert_eq!(allocation.get_share(allocation.idle_amount), 0.4);
+
+    // Strategies deploying more than the vault total amount
+    let mut small_vault_state: Vault = zeroed();
+    small_vault_state.total_amount = 500;
+    assert!(compute_vault_allocation(vault, &small_vault_state, &strategies).is_err());
+
+    // Strategy of another vault
+    let other_strategies = vec![(
+        Pubkey::new_unique(),
+        get_strategy(Pubkey::new_unique(), StrategyType::Kamino, 100),
+    )];
+    assert!(compute_vault_allocation(vault, &vault_state, &other_strategies).is_err());
+}
+
+#[test]
+fn test_compute_vault_allocation_without_strategy() {
+    let vault = Pubkey::new_unique();
+    let mut vault_state: Vault = zeroed();
+    vault_state.total_amount = 1_000;
+
+    let allocation = compute_vault_allocation(vault, &vault_state, &[]).unwrap();
+    assert_eq!(allocation.total_amount, 1_000);
+    assert_eq!(allocation.idle_amount, 1_000);
+    assert!(allocation.strategies.is_empty());
+    assert!(allocation.get_amount_by_strategy_type().is_empty());
+    assert_eq!(allocation.get_share(allocation.idle_amount), 1.0);
+
+    // Empty vault
+    let empty_vault_state: Vault = zeroed();
+    let allocation = compute_vault_allocation(vault, &empty_vault_state, &[]).unwrap();
+    assert_eq!(allocation.idle_amount, 0);
+    assert_eq!(allocation.get_share(0), 0.0);
+}
@@ -0,0 +1,40 @@
+use dynamic_amm_quote::amp_ramp::{get_next_step_time, plan_amp_ramp, AmpRampStep};
+use prog_dynamic_amm::constants::MIN_CHANGE_AMP_DURATION;
+
+#[test]
+fn test_plan_amp_ramp() {
+    let steps = plan_amp_ramp(100, 2000, 500, 1000, 3600).unwrap();
+    let amps = steps.iter().map(|step| step.amp).collect::<Vec<u64>>();
+    assert_eq!(amps, vec![600, 1100, 1600, 2000]);
+    assert_eq!(
+        steps[0],
+        AmpRampStep {
+            amp: 600,
+            earliest_time: 1000 + 3600,
+        }
+    );
+    assert_eq!(steps[3].earliest_time, 1000 + 4 * 3600);
+    // the plan and the job share the schedule
+    for (prev, step) in steps.iter().zip(steps.iter().skip(1)) {
+        assert_eq!(
+            step.earliest_time,
+            get_next_step_time(prev.earliest_time, 3600)
+        );
+    }
+    assert_eq!(
+        get_next_step_time(1000, MIN_CHANGE_AMP_DURATION - 1),
+        1000 + MIN_CHANGE_AMP_DURATION
+    );
+
+    let amps = plan_amp_ramp(2000, 100, 1000, 0, 600)
+        .unwrap()
+        .iter()
+        .map(|step| step.amp)
+        .collect::<Vec<u64>>();
+    assert_eq!(amps, vec![1000, 100]);
+
+    assert!(plan_amp_ramp(100, 100, 500, 0, 600).unwrap().is_empty());
+    assert!(plan_amp_ramp(100, 2000, 500, 0, MIN_CHANGE_AMP_DURATION - 1).is_err());
+    assert!(plan_amp_ramp(100, 2000, 0, 0, 600).is_err());
+    assert!(plan_amp_ramp(100, 10_001, 500, 0, 600).is_err());
+}
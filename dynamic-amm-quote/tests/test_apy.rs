@@ -0,0 +1,145 @@
+use dynamic_amm_quote::apy::{estimate_pool_apy, PoolSnapshot};
+use prog_dynamic_vault::state::{estimate_apy, VaultSnapshot, SECONDS_PER_YEAR};
+
+fn assert_approx_eq(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-9,
+        "{} is not {}",
+        actual,
+        expected
+    );
+}
+
+fn vault_snapshot(timestamp: u64, unlocked_amount: u64) -> VaultSnapshot {
+    VaultSnapshot {
+        timestamp,
+        unlocked_amount,
+        lp_supply: 1_000_000,
+    }
+}
+
+#[test]
+fn test_estimate_apy() {
+    let half_year = SECONDS_PER_YEAR / 2;
+
+    // 5% in half a year, compounded
+    let apy = estimate_apy(&[
+        vault_snapshot(1_000, 1_000_000),
+        vault_snapshot(1_000 + half_year, 1_050_000),
+    ])
+    .unwrap();
+    assert_approx_eq(apy, 1.05f64.powi(2) - 1.0);
+
+    // Every sample on the same growth curve
+    let apy = estimate_apy(&[
+        vault_snapshot(1_000, 1_000_000),
+        vault_snapshot(1_000 + half_year, 1_100_000),
+        vault_snapshot(1_000 + half_year * 2, 1_210_000),
+    ])
+    .unwrap();
+    assert_approx_eq(apy, 0.21);
+
+    // An early sample above the curve pulls the fit, unlike the growth between the first and the last sample
+    let apy = estimate_apy(&[
+        vault_snapshot(1_000, 1_000_000),
+        vault_snapshot(1_000 + half_year / 2, 1_200_000),
+        vault_snapshot(1_000 + half_year * 2, 1_210_000),
+    ])
+    .unwrap();
+    assert!(apy > 0.0 && apy < 0.21);
+
+    // Snapshot without lp supply is skipped
+    let apy = estimate_apy(&[
+        vault_snapshot(1_000, 1_000_000),
+        VaultSnapshot {
+            timestamp: 2_000,
+            unlocked_amount: 0,
+            lp_supply: 0,
+        },
+        vault_snapshot(1_000 + half_year * 2, 1_210_000),
+    ])
+    .unwrap();
+    assert_approx_eq(apy, 0.21);
+
+    assert_eq!(estimate_apy(&[]), None);
+    assert_eq!(estimate_apy(&[vault_snapshot(1_000, 1_000_000)]), None);
+    assert_eq!(
+        estimate_apy(&[
+            vault_snapshot(1_000, 1_000_000),
+            vault_snapshot(1_000, 1_100_000)
+        ]),
+        None
+    );
+}
+
+#[test]
+fn test_estimate_pool_apy() {
+    let start = PoolSnapshot {
+        timestamp: 1_000,
+        pool_lp_supply: 1_000,
+        pool_vault_a_lp_amount: 1_000,
+        pool_vault_b_lp_amount: 1_000,
+        vault_a_unlocked_amount: 1_000_000,
+        vault_a_lp_supply: 1_000_000,
+        vault_b_unlocked_amount: 1_000_000,
+        vault_b_lp_supply: 1_000_000,
+    };
+    // A year later, vault A lp price grew 5%, vault B lp price 10%, and the pool holds 10% more vault lp per pool lp
+    // after swaps moved it toward token A: sqrt(2_420 * 500) = 1_100
+    let end = PoolSnapshot {
+        timestamp: 1_000 + SECONDS_PER_YEAR,
+        pool_vault_a_lp_amount: 2_420,
+        pool_vault_b_lp_amount: 500,
+        vault_a_unlocked_amount: 1_050_000,
+        vault_b_unlocked_amount: 1_100_000,
+        ..start
+    };
+
+    assert_approx_eq(start.get_vault_lp_per_pool_lp().unwrap(), 1.0);
+    assert_approx_eq(end.get_vault_lp_per_pool_lp().unwrap(), 1.1);
+    assert_approx_eq(end.get_vault_lp_price().unwrap(), (1.05f64 * 1.1).sqrt());
+
+    let apy = estimate_pool_apy(&[start, end]).unwrap();
+    assert_approx_eq(apy.vault_a_apy, 0.05);
+    assert_approx_eq(apy.vault_b_apy, 0.1);
+    assert_approx_eq(apy.lending_apy, (1.05f64 * 1.1).sqrt() - 1.0);
+    assert_approx_eq(apy.trading_fee_apy, 0.1);
+    assert_approx_eq(apy.total_apy, (1.05f64 * 1.1).sqrt() * 1.1 - 1.0);
+
+    // Deposits and withdraws don't change the yield
+    let deposited = PoolSnapshot {
+        pool_lp_supply: end.pool_lp_supply * 2,
+        pool_vault_a_lp_amount: end.pool_vault_a_lp_amount * 2,
+        pool_vault_b_lp_amount: end.pool_vault_b_lp_amount * 2,
+        ..end
+    };
+    let apy_deposited = estimate_pool_apy(&[start, deposited]).unwrap();
+    assert_approx_eq(apy_deposited.trading_fee_apy, apy.trading_fee_apy);
+
+    // Lending only
+    let apy = estimate_pool_apy(&[
+        start,
+        PoolSnapshot {
+            timestamp: 1_000 + SECONDS_PER_YEAR,
+            vault_a_unlocked_amount: 1_050_000,
+            vault_b_unlocked_amount: 1_050_000,
+            ..start
+        },
+    ])
+    .unwrap();
+    assert_approx_eq(apy.lending_apy, 0.05);
+    assert_approx_eq(apy.trading_fee_apy, 0.0);
+    assert_approx_eq(apy.total_apy, 0.05);
+
+    assert_eq!(estimate_pool_apy(&[start]), None);
+    assert_eq!(
+        estimate_pool_apy(&[
+            PoolSnapshot {
+                pool_lp_supply: 0,
+                ..start
+            },
+            end
+        ]),
+        None
+    );
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::Pubkey;
+use dynamic_amm_quote::config::{validate_config_parameters, validate_customizable_params};
+use prog_dynamic_amm::constants::activation::MAX_ACTIVATION_SLOT_DURATION;
+use prog_dynamic_amm::constants::fee::MAX_PARTNER_FEE_NUMERATOR;
+use prog_dynamic_amm::instructions::{ConfigParameters, CustomizableParams};
+
+fn get_config_parameters() -> ConfigParameters {
+    ConfigParameters {
+        trade_fee_numerator: 2500,
+        protocol_trade_fee_numerator: 20000,
+        activation_duration: 0,
+        pool_creator_authority: Pubkey::default(),
+        activation_type: 0,
+        index: 0,
+        partner_fee_numerator: 0,
+    }
+}
+
+#[test]
+fn test_validate_config_parameters() {
+    assert!(validate_config_parameters(&get_config_parameters()).is_ok());
+
+    let mut config_parameters = get_config_parameters();
+    config_parameters.trade_fee_numerator = 100;
+    assert!(validate_config_parameters(&config_parameters).is_err());
+    config_parameters.trade_fee_numerator = 15001;
+    assert!(validate_config_parameters(&config_parameters).is_err());
+
+    let mut config_parameters = get_config_parameters();
+    config_parameters.activation_duration = MAX_ACTIVATION_SLOT_DURATION;
+    assert!(validate_config_parameters(&config_parameters).is_ok());
+    config_parameters.activation_duration = MAX_ACTIVATION_SLOT_DURATION + 1;
+    assert!(validate_config_parameters(&config_parameters).is_err());
+    config_parameters.activation_type = 2;
+    config_parameters.activation_duration = 0;
+    assert!(validate_config_parameters(&config_parameters).is_err());
+
+    let mut config_parameters = get_config_parameters();
+    config_parameters.partner_fee_numerator = MAX_PARTNER_FEE_NUMERATOR + 1;
+    assert!(validate_config_parameters(&config_parameters).is_err());
+}
+
+#[test]
+fn test_validate_customizable_params() {
+    let params = CustomizableParams {
+        trade_fee_numerator: 2500,
+        activation_point: None,
+        has_alpha_vault: false,
+        activation_type: 1,
+        padding: [0u8; 90],
+    };
+    assert!(validate_customizable_params(&params).is_ok());
+
+    assert!(validate_customizable_params(&CustomizableParams {
+        trade_fee_numerator: 15001,
+        ..params
+    })
+    .is_err());
+    assert!(validate_customizable_params(&CustomizableParams {
+        activation_type: 2,
+        ..params
+    })
+    .is_err());
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AnchorSerialize;
+use dynamic_amm_quote::curve::stable::{
+    compute_token_multiplier, generate_stable_curve_type, LstType,
+};
+use dynamic_amm_quote::depeg::{marinade, solido};
+use prog_dynamic_amm::constants::stable_curve::MAX_AMP;
+use prog_dynamic_amm::state::{CurveType, DepegType, TokenMultiplier};
+use spl_stake_pool::state::StakePool;
+
+#[test]
+fn test_compute_token_multiplier() {
+    assert_eq!(
+        compute_token_multiplier(6, 9),
+        TokenMultiplier {
+            token_a_multiplier: 1000,
+            token_b_multiplier: 1,
+            precision_factor: 9,
+        }
+    );
+    assert_eq!(
+        compute_token_multiplier(9, 9),
+        TokenMultiplier {
+            token_a_multiplier: 1,
+            token_b_multiplier: 1,
+            precision_factor: 9,
+        }
+    );
+}
+
+#[test]
+fn test_generate_stable_curve_type() {
+    assert!(generate_stable_curve_type(0, 9, 9, None).is_err());
+    assert!(generate_stable_curve_type(MAX_AMP + 1, 9, 9, None).is_err());
+
+    let stake_pool = Pubkey::new_unique();
+    let lst_type = LstType::SplStake { stake_pool };
+    assert_eq!(lst_type.get_stake_account(), stake_pool);
+
+    match generate_stable_curve_type(100, 9, 9, Some(lst_type)).unwrap() {
+        CurveType::Stable { amp, depeg, .. } => {
+            assert_eq!(amp, 100);
+            assert_eq!(depeg.depeg_type, DepegType::SplStake);
+        }
+        CurveType::ConstantProduct => panic!("Expected stable curve"),
+    }
+}
+
+#[test]
+fn test_verify_lst_mint() {
+    assert!(LstType::Marinade
+        .verify_lst_mint(marinade::mint::ID, &[])
+        .is_ok());
+    assert!(LstType::Marinade
+        .verify_lst_mint(solido::mint::ID, &[])
+        .is_err());
+    assert!(LstType::Lido.verify_lst_mint(solido::mint::ID, &[]).is_ok());
+    assert!(LstType::Lido
+        .verify_lst_mint(Pubkey::new_unique(), &[])
+        .is_err());
+
+    let pool_mint = Pubkey::new_unique();
+    let stake_pool_data = StakePool {
+        pool_mint,
+        ..StakePool::default()
+    }
+    .try_to_vec()
+    .unwrap();
+    let lst_type = LstType::SplStake {
+        stake_pool: Pubkey::new_unique(),
+    };
+    assert!(lst_type
+        .verify_lst_mint(pool_mint, &stake_pool_data)
+        .is_ok());
+    assert!(lst_type
+        .verify_lst_mint(marinade::mint::ID, &stake_pool_data)
+        .is_err());
+    // Not a stake pool account
+    assert!(lst_type.verify_lst_mint(pool_mint, &[]).is_err());
+}
@@ -0,0 +1,208 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::CompiledInstruction;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use dynamic_amm_quote::decoder::{decode_transaction, DynamicAmmInstruction, InnerInstructions};
+
+fn compile(
+    account_keys: &mut Vec<Pubkey>,
+    program_id: Pubkey,
+    accounts: &[Pubkey],
+    data: Vec<u8>,
+) -> CompiledInstruction {
+    let mut index_of = |key: Pubkey| -> u8 {
+        match account_keys.iter().position(|k| *k == key) {
+            Some(index) => index as u8,
+            None => {
+                account_keys.push(key);
+                (account_keys.len() - 1) as u8
+            }
+        }
+    };
+    CompiledInstruction {
+        program_id_index: index_of(program_id),
+        accounts: accounts.iter().map(|key| index_of(*key)).collect(),
+        data,
+    }
+}
+
+#[test]
+fn test_decode_transaction() {
+    let aggregator = Pubkey::new_unique();
+    let stake = Pubkey::new_unique();
+    let swap_accounts = prog_dynamic_amm::accounts::Swap {
+        pool: Pubkey::new_unique(),
+        user_source_token: Pubkey::new_unique(),
+        user_destination_token: Pubkey::new_unique(),
+        a_vault: Pubkey::new_unique(),
+        b_vault: Pubkey::new_unique(),
+        a_token_vault: Pubkey::new_unique(),
+        b_token_vault: Pubkey::new_unique(),
+        a_vault_lp_mint: Pubkey::new_unique(),
+        b_vault_lp_mint: Pubkey::new_unique(),
+        a_vault_lp: Pubkey::new_unique(),
+        b_vault_lp: Pubkey::new_unique(),
+        protocol_token_fee: Pubkey::new_unique(),
+        user: Pubkey::new_unique(),
+        vault_program: prog_dynamic_vault::ID,
+        token_program: anchor_spl::token::ID,
+    };
+    let mut accounts = swap_accounts
+        .to_account_metas(None)
+        .into_iter()
+        .map(|meta| meta.pubkey)
+        .collect::<Vec<Pubkey>>();
+    accounts.push(stake);
+
+    let swap_data = prog_dynamic_amm::instruction::Swap {
+        in_amount: 100,
+        minimum_out_amount: 90,
+    }
+    .data();
+
+    let mut account_keys = vec![];
+    let instructions = vec![
+        compile(&mut account_keys, aggregator, &accounts, vec![1, 2, 3]),
+        compile(
+            &mut account_keys,
+            prog_dynamic_amm::ID,
+            &accounts[..15],
+            prog_dynamic_amm::instruction::GetPoolInfo {}.data(),
+        ),
+    ];
+    let inner_instructions = vec![InnerInstructions {
+        index: 0,
+        instructions: vec![
+            compile(
+                &mut account_keys,
+                anchor_spl::token::ID,
+                &accounts[..2],
+                vec![3],
+            ),
+            compile(
+                &mut account_keys,
+                prog_dynamic_amm::ID,
+                &accounts,
+                swap_data,
+            ),
+        ],
+    }];
+
+    let decoded = decode_transaction(
+        &prog_dynamic_amm::ID,
+        &account_keys,
+        &instructions,
+        &inner_instructions,
+    )
+    .into_iter()
+    .collect::<Result<Vec<_>, _>>()
+    .unwrap();
+    assert_eq!(decoded.len(), 2);
+
+    assert_eq!(decoded[0].instruction_index, 0);
+    assert_eq!(decoded[0].inner_instruction_index, Some(1));
+    assert_eq!(decoded[0].remaining_accounts, vec![stake]);
+    match &decoded[0].instruction {
+        DynamicAmmInstruction::Swap {
+            in_amount,
+            minimum_out_amount,
+            accounts,
+        } => {
+            assert_eq!(*in_amount, 100);
+            assert_eq!(*minimum_out_amount, 90);
+            assert_eq!(accounts.pool, swap_accounts.pool);
+            assert_eq!(accounts.user, swap_accounts.user);
+            assert_eq!(
+                accounts.protocol_token_fee,
+                swap_accounts.protocol_token_fee
+            );
+        }
+        instruction => panic!("Unexpected instruction {}", instruction.name()),
+    }
+
+    assert_eq!(decoded[1].instruction_index, 1);
+    assert_eq!(decoded[1].inner_instruction_index, None);
+    assert_eq!(decoded[1].instruction.name(), "get_pool_info");
+    assert_eq!(decoded[1].remaining_accounts.len(), 7);
+}
+
+#[test]
+fn test_decode_transaction_with_invalid_instruction() {
+    let lock_accounts = prog_dynamic_amm::accounts::Lock {
+        pool: Pubkey::new_unique(),
+        lp_mint: Pubkey::new_unique(),
+        lock_escrow: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        source_tokens: Pubkey::new_unique(),
+        escrow_vault: Pubkey::new_unique(),
+        token_program: anchor_spl::token::ID,
+        a_vault: Pubkey::new_unique(),
+        b_vault: Pubkey::new_unique(),
+        a_vault_lp: Pubkey::new_unique(),
+        b_vault_lp: Pubkey::new_unique(),
+        a_vault_lp_mint: Pubkey::new_unique(),
+        b_vault_lp_mint: Pubkey::new_unique(),
+    };
+    let accounts = lock_accounts
+        .to_account_metas(None)
+        .into_iter()
+        .map(|meta| meta.pubkey)
+        .collect::<Vec<Pubkey>>();
+    let lock_data = prog_dynamic_amm::instruction::Lock { amount: 10 }.data();
+
+    let mut account_keys = vec![];
+    let instructions = vec![
+        // Truncated args
+        compile(
+            &mut account_keys,
+            prog_dynamic_amm::ID,
+            &accounts,
+            lock_data[..12].to_vec(),
+        ),
+        compile(
+            &mut account_keys,
+            prog_dynamic_amm::ID,
+            &accounts,
+            lock_data.clone(),
+        ),
+    ];
+    let inner_instructions = vec![InnerInstructions {
+        index: 1,
+        instructions: vec![
+            // Missing accounts
+            compile(
+                &mut account_keys,
+                prog_dynamic_amm::ID,
+                &accounts[..5],
+                lock_data,
+            ),
+        ],
+    }];
+
+    let decoded = decode_transaction(
+        &prog_dynamic_amm::ID,
+        &account_keys,
+        &instructions,
+        &inner_instructions,
+    );
+    assert_eq!(decoded.len(), 3);
+
+    let error = decoded[0].as_ref().unwrap_err();
+    assert_eq!(error.instruction_index, 0);
+    assert_eq!(error.inner_instruction_index, None);
+
+    let lock = decoded[1].as_ref().unwrap();
+    assert_eq!(lock.instruction_index, 1);
+    match &lock.instruction {
+        DynamicAmmInstruction::Lock { amount, accounts } => {
+            assert_eq!(*amount, 10);
+            assert_eq!(accounts.lock_escrow, lock_accounts.lock_escrow);
+        }
+        instruction => panic!("Unexpected instruction {:?}", instruction),
+    }
+    assert!(format!("{:?}", lock.instruction).starts_with("Lock { amount: 10, accounts: ["));
+
+    let error = decoded[2].as_ref().unwrap_err();
+    assert_eq!(error.instruction_index, 1);
+    assert_eq!(error.inner_instruction_index, Some(0));
+    assert!(error.to_string().contains("Not enough accounts for lock"));
+}
@@ -0,0 +1,176 @@
+use anchor_lang::prelude::{AnchorDeserialize, Clock};
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token::state::{Account, AccountState, Mint as SplMint};
+use anchor_spl::token::{Mint, TokenAccount};
+use dynamic_amm_quote::diagnostic::{diagnose_pool_error, diagnose_swap_error, get_pool_error};
+use dynamic_amm_quote::{compute_quote, QuoteData};
+use prog_dynamic_amm::error::PoolError;
+use prog_dynamic_amm::state::Pool;
+use prog_dynamic_vault::state::Vault;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn token_account(amount: u64) -> TokenAccount {
+    let mut data = vec![0u8; Account::LEN];
+    Account {
+        amount,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut data);
+    TokenAccount::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn mint(supply: u64) -> Mint {
+    let mut data = vec![0u8; SplMint::LEN];
+    SplMint {
+        supply,
+        is_initialized: true,
+        ..SplMint::default()
+    }
+    .pack_into_slice(&mut data);
+    Mint::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+/// Constant product pool without fee, owning all the liquidity of both vaults
+fn get_quote_data() -> QuoteData {
+    let mut pool: Pool = zeroed();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.enabled = true;
+
+    let mut vault: Vault = zeroed();
+    vault.total_amount = 1_000_000;
+
+    QuoteData {
+        pool,
+        vault_a: vault.clone(),
+        vault_b: vault,
+        pool_vault_a_lp_token: token_account(1_000_000),
+        pool_vault_b_lp_token: token_account(1_000_000),
+        vault_a_lp_mint: mint(1_000_000),
+        vault_b_lp_mint: mint(1_000_000),
+        vault_a_token: token_account(1_000_000),
+        vault_b_token: token_account(1_000_000),
+        clock: Clock {
+            slot: 50,
+            unix_timestamp: 70,
+            ..Clock::default()
+        },
+        stake_data: HashMap::new(),
+    }
+}
+
+#[test]
+fn test_get_pool_error() {
+    assert_eq!(get_pool_error(6000), Some(PoolError::MathOverflow));
+    assert_eq!(get_pool_error(6004), Some(PoolError::ExceededSlippage));
+    assert_eq!(get_pool_error(6015), Some(PoolError::PoolDisabled));
+    assert_eq!(get_pool_error(5999), None);
+    assert_eq!(get_pool_error(7000), None);
+
+    let mut code = 6000;
+    while let Some(error) = get_pool_error(code) {
+        assert_eq!(u32::from(error), code);
+        code += 1;
+    }
+    assert_eq!(get_pool_error(code - 1), Some(PoolError::InvalidQuoteMint));
+}
+
+#[test]
+fn test_diagnose_pool_error() {
+    let QuoteData {
+        mut pool, clock, ..
+    } = get_quote_data();
+    pool.bootstrapping.activation_point = 100;
+
+    assert_eq!(
+        diagnose_pool_error(PoolError::PoolDisabled, &pool, &clock),
+        "Pool is disabled by the admin. Only balanced withdraw is allowed"
+    );
+
+    for error in [
+        PoolError::InvalidActivationPoint,
+        PoolError::UnableToModifyActivationPoint,
+        PoolError::PreActivationSwapStarted,
+        PoolError::InvalidActivationDuration,
+    ] {
+        // Slot activation type
+        pool.bootstrapping.activation_type = 0;
+        assert_eq!(
+            diagnose_pool_error(error, &pool, &clock),
+            format!("{}. Current Slot 50, activation point 100", error)
+        );
+
+        // Timestamp activation type
+        pool.bootstrapping.activation_type = 1;
+        assert_eq!(
+            diagnose_pool_error(error, &pool, &clock),
+            format!("{}. Current Timestamp 70, activation point 100", error)
+        );
+
+        // Unknown activation type
+        pool.bootstrapping.activation_type = 2;
+        assert_eq!(diagnose_pool_error(error, &pool, &clock), error.to_string());
+    }
+
+    assert_eq!(
+        diagnose_pool_error(PoolError::MathOverflow, &pool, &clock),
+        PoolError::MathOverflow.to_string()
+    );
+}
+
+#[test]
+fn test_diagnose_swap_error() {
+    let quote_data = get_quote_data();
+    let token_a_mint = quote_data.pool.token_a_mint;
+    let quote = compute_quote(token_a_mint, 1_000, quote_data.clone()).unwrap();
+
+    assert_eq!(
+        diagnose_swap_error(
+            PoolError::ExceededSlippage,
+            token_a_mint,
+            1_000,
+            1_000,
+            quote_data.clone()
+        ),
+        format!(
+            "{}. Quoted out amount {}, minimum out amount 1000",
+            PoolError::ExceededSlippage,
+            quote.out_amount
+        )
+    );
+
+    // Quote failure is reported instead of the quote
+    assert_eq!(
+        diagnose_swap_error(
+            PoolError::ExceededSlippage,
+            Pubkey::new_unique(),
+            1_000,
+            1_000,
+            quote_data.clone()
+        ),
+        format!(
+            "{}. Fail to quote: In token mint not matches with pool token mints",
+            PoolError::ExceededSlippage
+        )
+    );
+
+    // Other errors are explained with the pool state
+    assert_eq!(
+        diagnose_swap_error(
+            PoolError::PoolDisabled,
+            token_a_mint,
+            1_000,
+            1_000,
+            quote_data.clone()
+        ),
+        diagnose_pool_error(PoolError::PoolDisabled, &quote_data.pool, &quote_data.clock)
+    );
+}
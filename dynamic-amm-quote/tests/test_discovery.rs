@@ -0,0 +1,149 @@
+use anchor_lang::{AnchorDeserialize, AnchorSerialize, Discriminator};
+use dynamic_amm_quote::discovery::*;
+use dynamic_amm_quote::pda::derive_constant_product_pool_with_config;
+use prog_dynamic_amm::state::{Config, Pool, PoolType};
+use solana_sdk::pubkey::Pubkey;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+/// Account data as stored on chain, the discriminator followed by the serialized state
+fn account_data<T: Discriminator + AnchorSerialize>(state: &T) -> Vec<u8> {
+    let mut data = T::discriminator().to_vec();
+    data.extend(state.try_to_vec().unwrap());
+    data
+}
+
+#[test]
+fn test_pool_filters() {
+    let token_a_mint = Pubkey::new_unique();
+    let token_b_mint = Pubkey::new_unique();
+    let partner_authority = Pubkey::new_unique();
+
+    let mut pool: Pool = zeroed();
+    pool.token_a_mint = token_a_mint;
+    pool.token_b_mint = token_b_mint;
+    pool.pool_type = PoolType::Permissionless;
+    pool.partner_info.partner_authority = partner_authority;
+    let data = account_data(&pool);
+
+    assert!(MemcmpFilter::discriminator::<Pool>().matches(&data));
+    assert!(!MemcmpFilter::discriminator::<Config>().matches(&data));
+
+    let [token_a_filter, token_b_filter] = get_pool_token_mint_filters(&token_a_mint);
+    assert!(token_a_filter.matches(&data));
+    assert!(!token_b_filter.matches(&data));
+    let [token_a_filter, token_b_filter] = get_pool_token_mint_filters(&token_b_mint);
+    assert!(!token_a_filter.matches(&data));
+    assert!(token_b_filter.matches(&data));
+
+    assert!(get_pool_type_filter(PoolType::Permissionless)
+        .unwrap()
+        .matches(&data));
+    assert!(!get_pool_type_filter(PoolType::Permissioned)
+        .unwrap()
+        .matches(&data));
+
+    assert!(get_pool_partner_authority_filter(&partner_authority).matches(&data));
+    assert!(!get_pool_partner_authority_filter(&token_a_mint).matches(&data));
+}
+
+#[test]
+fn test_config_filters() {
+    let pool_creator_authority = Pubkey::new_unique();
+
+    let mut config: Config = zeroed();
+    config.pool_creator_authority = pool_creator_authority;
+    config.vault_config_key = Pubkey::new_unique();
+    let data = account_data(&config);
+
+    assert!(MemcmpFilter::discriminator::<Config>().matches(&data));
+    assert!(get_config_pool_creator_authority_filter(&pool_creator_authority).matches(&data));
+    assert!(!get_config_pool_creator_authority_filter(&config.vault_config_key).matches(&data));
+}
+
+#[test]
+fn test_memcmp_filter_matches() {
+    let filter = MemcmpFilter::new(2, vec![1, 2]);
+    assert!(filter.matches(&[0, 0, 1, 2]));
+    assert!(filter.matches(&[0, 0, 1, 2, 3]));
+    assert!(!filter.matches(&[0, 1, 2, 0]));
+    // Data too short
+    assert!(!filter.matches(&[0, 0, 1]));
+    assert!(!MemcmpFilter::new(usize::MAX, vec![1]).matches(&[1]));
+}
+
+#[test]
+fn test_find_pool_config() {
+    let config = Pubkey::new_unique();
+    let token_a_mint = Pubkey::new_unique();
+    let token_b_mint = Pubkey::new_unique();
+    let pool_key = derive_constant_product_pool_with_config(token_a_mint, token_b_mint, config);
+
+    let configs = [Pubkey::new_unique(), config];
+    assert_eq!(
+        find_pool_config(pool_key, token_a_mint, token_b_mint, &configs),
+        Some(config)
+    );
+    // Config missing in the candidates
+    assert_eq!(
+        find_pool_config(pool_key, token_a_mint, token_b_mint, &configs[..1]),
+        None
+    );
+    // The seeds sort the mints
+    assert_eq!(
+        find_pool_config(pool_key, token_b_mint, token_a_mint, &configs),
+        Some(config)
+    );
+    // Other mints
+    assert_eq!(
+        find_pool_config(pool_key, token_a_mint, Pubkey::new_unique(), &configs),
+        None
+    );
+}
+
+#[test]
+fn test_group_pools_by_config() {
+    let config = Pubkey::new_unique();
+    let other_config = Pubkey::new_unique();
+    let unused_config = Pubkey::new_unique();
+    let configs = [config, other_config, unused_config];
+
+    let get_pool = |config: Pubkey| {
+        let token_a_mint = Pubkey::new_unique();
+        let token_b_mint = Pubkey::new_unique();
+        (
+            derive_constant_product_pool_with_config(token_a_mint, token_b_mint, config),
+            token_a_mint,
+            token_b_mint,
+        )
+    };
+    let pool = get_pool(config);
+    let other_pool = get_pool(config);
+    let other_config_pool = get_pool(other_config);
+    // Created from a config not in the candidates, or without config
+    let unknown_pool = get_pool(Pubkey::new_unique());
+    let pool_without_config = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+
+    let pools_by_config = group_pools_by_config(
+        &[
+            pool,
+            other_config_pool,
+            unknown_pool,
+            other_pool,
+            pool_without_config,
+        ],
+        &configs,
+    );
+    assert_eq!(pools_by_config.len(), 3);
+    assert_eq!(pools_by_config[&config], vec![pool.0, other_pool.0]);
+    assert_eq!(pools_by_config[&other_config], vec![other_config_pool.0]);
+    // Every config is listed, even without pool
+    assert!(pools_by_config[&unused_config].is_empty());
+}
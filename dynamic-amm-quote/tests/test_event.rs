@@ -0,0 +1,96 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::Event;
+use dynamic_amm_quote::event::{parse_events, DynamicAmmEvent};
+use prog_dynamic_amm::event::{Lock, Swap};
+
+fn program_data_log(data: Vec<u8>) -> String {
+    format!(
+        "Program data: {}",
+        anchor_lang::__private::base64::encode(data)
+    )
+}
+
+#[test]
+fn test_parse_events() {
+    let aggregator = Pubkey::new_unique();
+    let amm = prog_dynamic_amm::ID;
+    let vault = prog_dynamic_vault::ID;
+
+    let swap = Swap {
+        in_amount: 100,
+        out_amount: 99,
+        trade_fee: 1,
+        protocol_fee: 0,
+        host_fee: 0,
+    };
+    let lock = Lock {
+        pool: Pubkey::new_unique(),
+        owner: Pubkey::new_unique(),
+        amount: 10,
+    };
+
+    let logs = vec![
+        format!("Program {} invoke [1]", amm),
+        program_data_log(lock.data()),
+        format!("Program {} success", amm),
+        format!("Program {} invoke [1]", aggregator),
+        format!("Program {} invoke [2]", amm),
+        format!("Program {} invoke [3]", vault),
+        // Emitted by the vault, must be ignored
+        program_data_log(swap.data()),
+        format!("Program {} success", vault),
+        format!("Program {} invoke [3]", vault),
+        format!("Program {} success", vault),
+        program_data_log(swap.data()),
+        format!("Program {} success", amm),
+        format!("Program {} success", aggregator),
+    ];
+
+    let events = parse_events(&logs, amm);
+    assert_eq!(events.len(), 2);
+
+    let swap_logs = vec![
+        format!("Program {} invoke [1]", amm),
+        "Program log: Instruction: Swap".to_string(),
+        // Not a program id, must not be pushed to the execution stack
+        "Program invalid invoke [2]".to_string(),
+        format!("Program {} invoke [2]", vault),
+        format!("Program {} consumed 1000 of 200000 compute units", vault),
+        format!("Program return: {} AQ==", vault),
+        format!("Program {} success", vault),
+        program_data_log(swap.data()),
+        format!("Program {} invoke [2]", vault),
+        format!("Program {} failed: custom program error: 0x1", vault),
+        program_data_log(swap.data()),
+        format!("Program {} consumed 5000 of 200000 compute units", amm),
+        format!("Program {} success", amm),
+        // Outside of any dynamic amm invocation
+        program_data_log(swap.data()),
+    ];
+    let swap_events = parse_events(&swap_logs, amm);
+    assert_eq!(swap_events.len(), 2);
+    assert_eq!(swap_events[0].log_index, 7);
+    assert_eq!(swap_events[0].cpi_depth, 1);
+    assert_eq!(swap_events[1].log_index, 10);
+    assert_eq!(swap_events[1].cpi_depth, 1);
+
+    assert_eq!(events[0].cpi_depth, 1);
+    assert_eq!(events[0].log_index, 1);
+    match &events[0].event {
+        DynamicAmmEvent::Lock(event) => {
+            assert_eq!(event.pool, lock.pool);
+            assert_eq!(event.amount, lock.amount);
+        }
+        event => panic!("Unexpected event {:?}", event),
+    }
+
+    assert_eq!(events[1].cpi_depth, 2);
+    assert_eq!(events[1].log_index, 10);
+    match &events[1].event {
+        DynamicAmmEvent::Swap(event) => {
+            assert_eq!(event.in_amount, swap.in_amount);
+            assert_eq!(event.out_amount, swap.out_amount);
+        }
+        event => panic!("Unexpected event {:?}", event),
+    }
+}
@@ -0,0 +1,709 @@
+use anchor_lang::{AnchorDeserialize, AnchorSerialize};
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::spl_token::native_mint;
+use dynamic_amm_quote::decoder::DynamicAmmInstruction;
+use dynamic_amm_quote::instruction::{
+    close_config, create_config, partner_claim_fee, unwrap_sol, wrap_sol, NewPool,
+    PoolInstructionBuilder,
+};
+use dynamic_amm_quote::pda::*;
+use prog_dynamic_amm::instructions::{ConfigParameters, CustomizableParams};
+use prog_dynamic_amm::state::{CurveType, Depeg, DepegType, Pool, PoolFees, TokenMultiplier};
+use prog_dynamic_vault::state::Vault;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::pubkey::Pubkey;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn get_pool() -> Pool {
+    let mut pool: Pool = zeroed();
+    pool.lp_mint = Pubkey::new_unique();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.a_vault = Pubkey::new_unique();
+    pool.b_vault = Pubkey::new_unique();
+    pool.a_vault_lp = Pubkey::new_unique();
+    pool.b_vault_lp = Pubkey::new_unique();
+    pool.protocol_token_a_fee = Pubkey::new_unique();
+    pool.protocol_token_b_fee = Pubkey::new_unique();
+    pool
+}
+
+fn get_vault() -> Vault {
+    let mut vault: Vault = zeroed();
+    vault.token_vault = Pubkey::new_unique();
+    vault.lp_mint = Pubkey::new_unique();
+    vault
+}
+
+/// Decode the instruction, and check the decoded accounts serialize back to the instruction accounts, in the same order and with the same flags
+fn decode(ix: &Instruction) -> (DynamicAmmInstruction, Vec<Pubkey>) {
+    assert_eq!(ix.program_id, prog_dynamic_amm::ID);
+    let keys = ix
+        .accounts
+        .iter()
+        .map(|account| account.pubkey)
+        .collect::<Vec<Pubkey>>();
+    let (instruction, remaining_accounts) = DynamicAmmInstruction::decode(&ix.data, &keys)
+        .unwrap()
+        .unwrap();
+    let account_metas = instruction.account_metas();
+    assert_eq!(account_metas, ix.accounts[..account_metas.len()]);
+    assert_eq!(remaining_accounts, keys[account_metas.len()..]);
+    (instruction, remaining_accounts)
+}
+
+/// The program args don't implement PartialEq, they are compared serialized
+fn assert_same_args<T: AnchorSerialize>(actual: &T, expected: &T) {
+    assert_eq!(actual.try_to_vec().unwrap(), expected.try_to_vec().unwrap());
+}
+
+fn unexpected(instruction: DynamicAmmInstruction) -> ! {
+    panic!("Unexpected instruction {}", instruction.name())
+}
+
+fn get_stable_curve() -> CurveType {
+    CurveType::Stable {
+        amp: 100,
+        token_multiplier: TokenMultiplier::default(),
+        depeg: Depeg {
+            base_virtual_price: 0,
+            base_cache_updated: 0,
+            depeg_type: DepegType::None,
+        },
+        last_amp_updated_timestamp: 0,
+    }
+}
+
+#[test]
+fn test_swap_instruction() {
+    let pool = get_pool();
+    let a_vault = get_vault();
+    let b_vault = get_vault();
+    let builder = PoolInstructionBuilder {
+        pool_key: Pubkey::new_unique(),
+        pool: &pool,
+        a_vault: &a_vault,
+        b_vault: &b_vault,
+    };
+    let user = Pubkey::new_unique();
+
+    let ix = builder.swap(user, pool.token_b_mint, 100, 90).unwrap();
+    assert_eq!(ix.program_id, prog_dynamic_amm::ID);
+
+    let keys = ix
+        .accounts
+        .iter()
+        .map(|account| account.pubkey)
+        .collect::<Vec<Pubkey>>();
+    let (instruction, remaining_accounts) = DynamicAmmInstruction::decode(&ix.data, &keys)
+        .unwrap()
+        .unwrap();
+    assert!(remaining_accounts.is_empty());
+    match instruction {
+        DynamicAmmInstruction::Swap {
+            in_amount,
+            minimum_out_amount,
+            accounts,
+        } => {
+            assert_eq!(in_amount, 100);
+            assert_eq!(minimum_out_amount, 90);
+            assert_eq!(accounts.pool, builder.pool_key);
+            assert_eq!(
+                accounts.user_source_token,
+                get_associated_token_address(&user, &pool.token_b_mint)
+            );
+            assert_eq!(
+                accounts.user_destination_token,
+                get_associated_token_address(&user, &pool.token_a_mint)
+            );
+            assert_eq!(accounts.protocol_token_fee, pool.protocol_token_b_fee);
+            assert_eq!(accounts.a_token_vault, a_vault.token_vault);
+            assert_eq!(accounts.b_vault_lp_mint, b_vault.lp_mint);
+        }
+        instruction => panic!("Unexpected instruction {}", instruction.name()),
+    }
+
+    assert!(builder.swap(user, Pubkey::new_unique(), 100, 90).is_err());
+}
+
+#[test]
+fn test_depeg_remaining_accounts() {
+    let mut pool = get_pool();
+    let a_vault = get_vault();
+    let b_vault = get_vault();
+    let user = Pubkey::new_unique();
+
+    let builder = PoolInstructionBuilder {
+        pool_key: Pubkey::new_unique(),
+        pool: &pool,
+        a_vault: &a_vault,
+        b_vault: &b_vault,
+    };
+    assert!(builder.get_remaining_accounts().is_empty());
+
+    pool.stake = Pubkey::new_unique();
+    for (depeg_type, stake) in [
+        (
+            DepegType::Marinade,
+            solana_sdk::pubkey!("8szGkuLTAux9XMgZ2vtY39jVSowEcpBfFfD8hXSEqdGC"),
+        ),
+        (
+            DepegType::Lido,
+            solana_sdk::pubkey!("49Yi1TKkNyYjPAFdR9LBvoHcUjuPX4Df5T5yv39w2XTn"),
+        ),
+        (DepegType::SplStake, pool.stake),
+    ] {
+        pool.curve_type = CurveType::Stable {
+            amp: 100,
+            token_multiplier: TokenMultiplier::default(),
+            depeg: Depeg {
+                base_virtual_price: 0,
+                base_cache_updated: 0,
+                depeg_type,
+            },
+            last_amp_updated_timestamp: 0,
+        };
+        let builder = PoolInstructionBuilder {
+            pool_key: Pubkey::new_unique(),
+            pool: &pool,
+            a_vault: &a_vault,
+            b_vault: &b_vault,
+        };
+
+        let swap = builder.swap(user, pool.token_a_mint, 100, 90).unwrap();
+        let deposit = builder.add_balance_liquidity(user, 100, 100, 100);
+        let withdraw = builder.remove_balance_liquidity(user, 100, 0, 0);
+        for ix in [swap, deposit, withdraw] {
+            let remaining_account = ix.accounts.last().unwrap();
+            assert_eq!(remaining_account.pubkey, stake);
+            assert!(!remaining_account.is_writable);
+            assert!(!remaining_account.is_signer);
+        }
+    }
+}
+
+#[test]
+fn test_wrap_sol_instructions() {
+    let owner = Pubkey::new_unique();
+    let user_wsol = get_associated_token_address(&owner, &native_mint::ID);
+
+    let ixs = wrap_sol(owner, 1_000_000);
+    assert_eq!(ixs.len(), 3);
+    assert_eq!(ixs[0].program_id, anchor_spl::associated_token::ID);
+    assert_eq!(ixs[0].accounts[1].pubkey, user_wsol);
+    assert_eq!(ixs[1].program_id, solana_sdk::system_program::ID);
+    assert_eq!(ixs[1].accounts[1].pubkey, user_wsol);
+    assert_eq!(ixs[2].program_id, anchor_spl::token::ID);
+    assert_eq!(ixs[2].accounts[0].pubkey, user_wsol);
+
+    let ix = unwrap_sol(owner);
+    assert_eq!(ix.program_id, anchor_spl::token::ID);
+    assert_eq!(ix.accounts[0].pubkey, user_wsol);
+    assert_eq!(ix.accounts[1].pubkey, owner);
+}
+
+#[test]
+fn test_config_instructions() {
+    let admin = Pubkey::new_unique();
+    let pool_creator_authority = Pubkey::new_unique();
+    // Not Clone, built once for the instruction and once for the comparison
+    let get_config_parameters = || ConfigParameters {
+        trade_fee_numerator: 250,
+        protocol_trade_fee_numerator: 20,
+        activation_duration: 100,
+        pool_creator_authority,
+        activation_type: 1,
+        index: 7,
+        partner_fee_numerator: 10,
+    };
+
+    let ix = create_config(admin, get_config_parameters());
+    match decode(&ix).0 {
+        DynamicAmmInstruction::CreateConfig {
+            config_parameters: decoded_config_parameters,
+            accounts,
+        } => {
+            assert_same_args(&decoded_config_parameters, &get_config_parameters());
+            assert_eq!(accounts.config, derive_config_address(7));
+            assert_eq!(accounts.admin, admin);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let config = Pubkey::new_unique();
+    let rent_receiver = Pubkey::new_unique();
+    match decode(&close_config(admin, config, rent_receiver)).0 {
+        DynamicAmmInstruction::CloseConfig { accounts } => {
+            assert_eq!(accounts.config, config);
+            assert_eq!(accounts.admin, admin);
+            assert_eq!(accounts.rent_receiver, rent_receiver);
+        }
+        instruction => unexpected(instruction),
+    }
+}
+
+#[test]
+fn test_pool_creation_instructions() {
+    let b_vault = get_vault();
+    let new_pool = NewPool {
+        token_a_mint: Pubkey::new_unique(),
+        token_b_mint: Pubkey::new_unique(),
+        a_vault: None,
+        b_vault: Some(&b_vault),
+    };
+    let payer = Pubkey::new_unique();
+    let a_vault_key = derive_vault_address(&new_pool.token_a_mint);
+
+    let curve_type = get_stable_curve();
+    match decode(&new_pool.initialize_permissionless_pool(payer, curve_type, 100, 200)).0 {
+        DynamicAmmInstruction::InitializePermissionlessPool {
+            curve_type: decoded_curve_type,
+            token_a_amount,
+            token_b_amount,
+            accounts,
+        } => {
+            assert_same_args(&decoded_curve_type, &curve_type);
+            assert_eq!((token_a_amount, token_b_amount), (100, 200));
+            assert_eq!(
+                accounts.pool,
+                derive_permissionless_pool_address(
+                    curve_type,
+                    new_pool.token_a_mint,
+                    new_pool.token_b_mint,
+                    25
+                )
+            );
+            // Vault A is not initialized, its accounts are derived
+            assert_eq!(accounts.a_vault, a_vault_key);
+            assert_eq!(
+                accounts.a_token_vault,
+                derive_token_vault_address(&a_vault_key)
+            );
+            assert_eq!(
+                accounts.a_vault_lp_mint,
+                derive_vault_lp_mint_address(&a_vault_key)
+            );
+            assert_eq!(accounts.b_token_vault, b_vault.token_vault);
+            assert_eq!(accounts.b_vault_lp_mint, b_vault.lp_mint);
+            assert_eq!(
+                accounts.payer_pool_lp,
+                get_associated_token_address(&payer, &accounts.lp_mint)
+            );
+            assert_eq!(accounts.payer, payer);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let ix = new_pool.initialize_permissionless_pool_with_fee_tier(
+        payer,
+        CurveType::ConstantProduct,
+        30,
+        100,
+        200,
+    );
+    match decode(&ix).0 {
+        DynamicAmmInstruction::InitializePermissionlessPoolWithFeeTier {
+            trade_fee_bps,
+            token_a_amount,
+            token_b_amount,
+            accounts,
+            ..
+        } => {
+            assert_eq!(trade_fee_bps, 30);
+            assert_eq!((token_a_amount, token_b_amount), (100, 200));
+            assert_eq!(
+                accounts.pool,
+                derive_permissionless_pool_address(
+                    CurveType::ConstantProduct,
+                    new_pool.token_a_mint,
+                    new_pool.token_b_mint,
+                    30
+                )
+            );
+            assert_eq!(
+                accounts.protocol_token_a_fee,
+                derive_pool_fee_token_address(new_pool.token_a_mint, accounts.pool)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let params = CustomizableParams {
+        trade_fee_numerator: 400,
+        activation_point: Some(1_000),
+        has_alpha_vault: true,
+        activation_type: 1,
+        padding: [0; 90],
+    };
+    let ix = new_pool
+        .initialize_customizable_permissionless_constant_product_pool(payer, 100, 200, params);
+    match decode(&ix).0 {
+        DynamicAmmInstruction::InitializeCustomizablePermissionlessConstantProductPool {
+            token_a_amount,
+            token_b_amount,
+            params: decoded_params,
+            accounts,
+        } => {
+            assert_eq!((token_a_amount, token_b_amount), (100, 200));
+            assert_same_args(&decoded_params, &params);
+            assert_eq!(
+                accounts.pool,
+                derive_customizable_permissionless_constant_product_pool_address(
+                    new_pool.token_a_mint,
+                    new_pool.token_b_mint
+                )
+            );
+            assert_eq!(
+                accounts.mint_metadata,
+                derive_mint_metadata_address(accounts.lp_mint)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let config = Pubkey::new_unique();
+    let pool_key = derive_constant_product_pool_with_config(
+        new_pool.token_a_mint,
+        new_pool.token_b_mint,
+        config,
+    );
+    let ix = new_pool
+        .initialize_permissionless_constant_product_pool_with_config(payer, config, 100, 200);
+    match decode(&ix).0 {
+        DynamicAmmInstruction::InitializePermissionlessConstantProductPoolWithConfig {
+            token_a_amount,
+            token_b_amount,
+            accounts,
+        } => {
+            assert_eq!((token_a_amount, token_b_amount), (100, 200));
+            assert_eq!(accounts.pool, pool_key);
+            assert_eq!(accounts.config, config);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let ix = new_pool.initialize_permissionless_constant_product_pool_with_config2(
+        payer,
+        config,
+        100,
+        200,
+        Some(1_000),
+    );
+    match decode(&ix).0 {
+        DynamicAmmInstruction::InitializePermissionlessConstantProductPoolWithConfig2 {
+            token_a_amount,
+            token_b_amount,
+            activation_point,
+            accounts,
+        } => {
+            assert_eq!((token_a_amount, token_b_amount), (100, 200));
+            assert_eq!(activation_point, Some(1_000));
+            assert_eq!(accounts.pool, pool_key);
+            assert_eq!(accounts.config, config);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let admin = Pubkey::new_unique();
+    let pool_key = Pubkey::new_unique();
+    let ix = new_pool.initialize_permissioned_pool(admin, pool_key, curve_type);
+    match decode(&ix).0 {
+        DynamicAmmInstruction::InitializePermissionedPool {
+            curve_type: decoded_curve_type,
+            accounts,
+        } => {
+            assert_same_args(&decoded_curve_type, &curve_type);
+            assert_eq!(accounts.pool, pool_key);
+            assert_eq!(accounts.admin, admin);
+            assert_eq!(
+                accounts.admin_token_a,
+                get_associated_token_address(&admin, &new_pool.token_a_mint)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+}
+
+#[test]
+fn test_liquidity_instructions() {
+    let pool = get_pool();
+    let a_vault = get_vault();
+    let b_vault = get_vault();
+    let builder = PoolInstructionBuilder {
+        pool_key: Pubkey::new_unique(),
+        pool: &pool,
+        a_vault: &a_vault,
+        b_vault: &b_vault,
+    };
+    let user = Pubkey::new_unique();
+    let user_pool_lp = get_associated_token_address(&user, &pool.lp_mint);
+
+    let (instruction, remaining_accounts) =
+        decode(&builder.add_balance_liquidity(user, 10, 20, 30));
+    assert!(remaining_accounts.is_empty());
+    match instruction {
+        DynamicAmmInstruction::AddBalanceLiquidity {
+            pool_token_amount,
+            maximum_token_a_amount,
+            maximum_token_b_amount,
+            accounts,
+        } => {
+            assert_eq!(
+                (
+                    pool_token_amount,
+                    maximum_token_a_amount,
+                    maximum_token_b_amount
+                ),
+                (10, 20, 30)
+            );
+            assert_eq!(accounts.pool, builder.pool_key);
+            assert_eq!(accounts.user_pool_lp, user_pool_lp);
+            assert_eq!(accounts.a_token_vault, a_vault.token_vault);
+            assert_eq!(accounts.b_vault_lp_mint, b_vault.lp_mint);
+            assert_eq!(
+                accounts.user_b_token,
+                get_associated_token_address(&user, &pool.token_b_mint)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.add_imbalance_liquidity(user, 10, 20, 30)).0 {
+        DynamicAmmInstruction::AddImbalanceLiquidity {
+            minimum_pool_token_amount,
+            token_a_amount,
+            token_b_amount,
+            accounts,
+        } => {
+            assert_eq!(
+                (minimum_pool_token_amount, token_a_amount, token_b_amount),
+                (10, 20, 30)
+            );
+            assert_eq!(accounts.user_pool_lp, user_pool_lp);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.remove_balance_liquidity(user, 10, 20, 30)).0 {
+        DynamicAmmInstruction::RemoveBalanceLiquidity {
+            pool_token_amount,
+            minimum_a_token_out,
+            minimum_b_token_out,
+            accounts,
+        } => {
+            assert_eq!(
+                (pool_token_amount, minimum_a_token_out, minimum_b_token_out),
+                (10, 20, 30)
+            );
+            assert_eq!(accounts.user_pool_lp, user_pool_lp);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let ix = builder
+        .remove_liquidity_single_side(user, pool.token_b_mint, 10, 20)
+        .unwrap();
+    match decode(&ix).0 {
+        DynamicAmmInstruction::RemoveLiquiditySingleSide {
+            pool_token_amount,
+            minimum_out_amount,
+            accounts,
+        } => {
+            assert_eq!((pool_token_amount, minimum_out_amount), (10, 20));
+            assert_eq!(accounts.user_pool_lp, user_pool_lp);
+            assert_eq!(
+                accounts.user_destination_token,
+                get_associated_token_address(&user, &pool.token_b_mint)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+    assert!(builder
+        .remove_liquidity_single_side(user, Pubkey::new_unique(), 10, 20)
+        .is_err());
+
+    match decode(&builder.bootstrap_liquidity(user, 20, 30)).0 {
+        DynamicAmmInstruction::BootstrapLiquidity {
+            token_a_amount,
+            token_b_amount,
+            accounts,
+        } => {
+            assert_eq!((token_a_amount, token_b_amount), (20, 30));
+            assert_eq!(accounts.user_pool_lp, user_pool_lp);
+            assert_eq!(
+                accounts.user_a_token,
+                get_associated_token_address(&user, &pool.token_a_mint)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.get_pool_info()).0 {
+        DynamicAmmInstruction::GetPoolInfo { accounts } => {
+            assert_eq!(accounts.pool, builder.pool_key);
+            assert_eq!(accounts.a_vault_lp_mint, a_vault.lp_mint);
+        }
+        instruction => unexpected(instruction),
+    }
+}
+
+#[test]
+fn test_lock_instructions() {
+    let pool = get_pool();
+    let a_vault = get_vault();
+    let b_vault = get_vault();
+    let builder = PoolInstructionBuilder {
+        pool_key: Pubkey::new_unique(),
+        pool: &pool,
+        a_vault: &a_vault,
+        b_vault: &b_vault,
+    };
+    let owner = Pubkey::new_unique();
+    let payer = Pubkey::new_unique();
+    let lock_escrow = derive_lock_escrow_address(builder.pool_key, owner);
+    let escrow_vault = get_associated_token_address(&lock_escrow, &pool.lp_mint);
+    assert_eq!(builder.get_escrow_vault(owner), escrow_vault);
+
+    match decode(&builder.create_lock_escrow(owner, payer)).0 {
+        DynamicAmmInstruction::CreateLockEscrow { accounts } => {
+            assert_eq!(accounts.lock_escrow, lock_escrow);
+            assert_eq!(accounts.owner, owner);
+            assert_eq!(accounts.payer, payer);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.lock(owner, 100)).0 {
+        DynamicAmmInstruction::Lock { amount, accounts } => {
+            assert_eq!(amount, 100);
+            assert_eq!(accounts.lock_escrow, lock_escrow);
+            assert_eq!(
+                accounts.source_tokens,
+                get_associated_token_address(&owner, &pool.lp_mint)
+            );
+            assert_eq!(accounts.escrow_vault, escrow_vault);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.claim_fee(owner, 100)).0 {
+        DynamicAmmInstruction::ClaimFee {
+            max_amount,
+            accounts,
+        } => {
+            assert_eq!(max_amount, 100);
+            assert_eq!(accounts.lock_escrow, lock_escrow);
+            assert_eq!(accounts.escrow_vault, escrow_vault);
+            assert_eq!(accounts.b_token_vault, b_vault.token_vault);
+            assert_eq!(
+                accounts.user_a_token,
+                get_associated_token_address(&owner, &pool.token_a_mint)
+            );
+        }
+        instruction => unexpected(instruction),
+    }
+}
+
+#[test]
+fn test_pool_admin_instructions() {
+    let mut pool = get_pool();
+    pool.partner_info.partner_authority = Pubkey::new_unique();
+    let a_vault = get_vault();
+    let b_vault = get_vault();
+    let builder = PoolInstructionBuilder {
+        pool_key: Pubkey::new_unique(),
+        pool: &pool,
+        a_vault: &a_vault,
+        b_vault: &b_vault,
+    };
+    let admin = Pubkey::new_unique();
+
+    match decode(&partner_claim_fee(builder.pool_key, &pool, 10, 20)).0 {
+        DynamicAmmInstruction::PartnerClaimFee {
+            max_amount_a,
+            max_amount_b,
+            accounts,
+        } => {
+            assert_eq!((max_amount_a, max_amount_b), (10, 20));
+            assert_eq!(
+                accounts.partner_authority,
+                pool.partner_info.partner_authority
+            );
+            assert_eq!(
+                accounts.partner_token_b,
+                get_associated_token_address(
+                    &pool.partner_info.partner_authority,
+                    &pool.token_b_mint
+                )
+            );
+            assert_eq!(accounts.protocol_token_a_fee, pool.protocol_token_a_fee);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.create_mint_metadata(admin)).0 {
+        DynamicAmmInstruction::CreateMintMetadata { accounts } => {
+            assert_eq!(
+                accounts.mint_metadata,
+                derive_mint_metadata_address(pool.lp_mint)
+            );
+            assert_eq!(accounts.payer, admin);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.enable_or_disable_pool(admin, false)).0 {
+        DynamicAmmInstruction::EnableOrDisablePool { enable, accounts } => {
+            assert!(!enable);
+            assert_eq!(accounts.pool, builder.pool_key);
+            assert_eq!(accounts.admin, admin);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let fees = PoolFees {
+        trade_fee_numerator: 25,
+        trade_fee_denominator: 10_000,
+        protocol_trade_fee_numerator: 5,
+        protocol_trade_fee_denominator: 10_000,
+    };
+    match decode(&builder.set_pool_fees(admin, fees, 30)).0 {
+        DynamicAmmInstruction::SetPoolFees {
+            fees: decoded_fees,
+            new_partner_fee_numerator,
+            accounts,
+        } => {
+            assert_same_args(&decoded_fees, &fees);
+            assert_eq!(new_partner_fee_numerator, 30);
+            assert_eq!(accounts.fee_operator, admin);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    let curve_type = get_stable_curve();
+    match decode(&builder.override_curve_param(admin, curve_type)).0 {
+        DynamicAmmInstruction::OverrideCurveParam {
+            curve_type: decoded_curve_type,
+            accounts,
+        } => {
+            assert_same_args(&decoded_curve_type, &curve_type);
+            assert_eq!(accounts.admin, admin);
+        }
+        instruction => unexpected(instruction),
+    }
+
+    match decode(&builder.update_activation_point(admin, 1_000)).0 {
+        DynamicAmmInstruction::UpdateActivationPoint {
+            new_activation_point,
+            accounts,
+        } => {
+            assert_eq!(new_activation_point, 1_000);
+            assert_eq!(accounts.pool, builder.pool_key);
+        }
+        instruction => unexpected(instruction),
+    }
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::{AnchorDeserialize, Clock};
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token::state::{Account, AccountState, Mint as SplMint};
+use anchor_spl::token::{Mint, TokenAccount};
+use dynamic_amm_quote::liquidity::{compute_deposit_quote, compute_withdraw_quote};
+use dynamic_amm_quote::QuoteData;
+use prog_dynamic_amm::state::Pool;
+use prog_dynamic_vault::state::Vault;
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn token_account(amount: u64) -> TokenAccount {
+    let mut data = vec![0u8; Account::LEN];
+    Account {
+        amount,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut data);
+    TokenAccount::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn mint(supply: u64) -> Mint {
+    let mut data = vec![0u8; SplMint::LEN];
+    SplMint {
+        supply,
+        is_initialized: true,
+        ..SplMint::default()
+    }
+    .pack_into_slice(&mut data);
+    Mint::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+/// Pool holding 333 / 1_000 LP of vault A (1_000 tokens), and 500 / 1_000 LP of vault B (3_001 tokens)
+fn get_quote_data() -> QuoteData {
+    let mut pool: Pool = zeroed();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.enabled = true;
+
+    let mut vault_a: Vault = zeroed();
+    vault_a.total_amount = 1_000;
+    let mut vault_b: Vault = zeroed();
+    vault_b.total_amount = 3_001;
+
+    QuoteData {
+        pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token: token_account(333),
+        pool_vault_b_lp_token: token_account(500),
+        vault_a_lp_mint: mint(1_000),
+        vault_b_lp_mint: mint(1_000),
+        vault_a_token: token_account(400),
+        vault_b_token: token_account(3_001),
+        clock: Clock::default(),
+        stake_data: HashMap::new(),
+    }
+}
+
+#[test]
+fn test_compute_deposit_quote() {
+    let quote_data = get_quote_data();
+    let token_a_mint = quote_data.pool.token_a_mint;
+    let token_b_mint = quote_data.pool.token_b_mint;
+
+    // 10 * 100 / 333 = 3.003 pool LP, rounded down
+    let quote = compute_deposit_quote(token_a_mint, 10, 100, quote_data.clone()).unwrap();
+    assert_eq!(quote.pool_token_amount, 3);
+    // Vault A LP: 3 * 333 / 100 = 9.99 -> 10, token A: 10 * 1_000 / 1_000 = 10
+    assert_eq!(quote.token_a_amount, 10);
+    // Vault B LP: 3 * 500 / 100 = 15, token B: 15 * 3_001 / 1_000 = 45.015 -> 46
+    assert_eq!(quote.token_b_amount, 46);
+
+    // Token B reserve is 500 * 3_001 / 1_000 = 1_500, 31 * 100 / 1_500 = 2.06 pool LP
+    let quote = compute_deposit_quote(token_b_mint, 31, 100, quote_data.clone()).unwrap();
+    assert_eq!(quote.pool_token_amount, 2);
+    // Vault A LP: 2 * 333 / 100 = 6.66 -> 7, Vault B LP: 2 * 500 / 100 = 10, 10 * 3_001 / 1_000 = 30.01 -> 31
+    assert_eq!(quote.token_a_amount, 7);
+    assert_eq!(quote.token_b_amount, 31);
+
+    // Depleted pool must be bootstrapped
+    assert!(compute_deposit_quote(token_a_mint, 10, 0, quote_data.clone()).is_err());
+    assert!(compute_deposit_quote(Pubkey::new_unique(), 10, 100, quote_data.clone()).is_err());
+
+    let mut disabled_quote_data = quote_data;
+    disabled_quote_data.pool.enabled = false;
+    assert!(compute_deposit_quote(token_a_mint, 10, 100, disabled_quote_data).is_err());
+}
+
+#[test]
+fn test_compute_withdraw_quote() {
+    let quote_data = get_quote_data();
+
+    let quote = compute_withdraw_quote(3, 100, quote_data.clone()).unwrap();
+    assert_eq!(quote.pool_token_amount, 3);
+    // Vault A LP: 3 * 333 / 100 = 9.99 -> 9, token A: 9 * 1_000 / 1_000 = 9
+    assert_eq!(quote.token_a_out_amount, 9);
+    // Vault B LP: 3 * 500 / 100 = 15, token B: 15 * 3_001 / 1_000 = 45.015 -> 45
+    assert_eq!(quote.token_b_out_amount, 45);
+    assert_eq!(quote.vault_a_idle_amount, 400);
+    assert_eq!(quote.vault_a_deployed_amount, 600);
+    assert_eq!(quote.vault_b_idle_amount, 3_001);
+    assert_eq!(quote.vault_b_deployed_amount, 0);
+
+    // Deposit and withdraw of the same pool LP rounds in favour of the pool
+    let deposit_quote =
+        compute_deposit_quote(quote_data.pool.token_a_mint, 10, 100, quote_data.clone()).unwrap();
+    assert!(deposit_quote.token_a_amount > quote.token_a_out_amount);
+    assert!(deposit_quote.token_b_amount > quote.token_b_out_amount);
+
+    // Withdraw all
+    let quote = compute_withdraw_quote(100, 100, quote_data.clone()).unwrap();
+    assert_eq!(quote.token_a_out_amount, 333);
+    assert_eq!(quote.token_b_out_amount, 1_500);
+
+    assert!(compute_withdraw_quote(101, 100, quote_data.clone()).is_err());
+    // Nothing to withdraw from a depleted pool
+    assert!(compute_withdraw_quote(0, 0, quote_data).is_err());
+}
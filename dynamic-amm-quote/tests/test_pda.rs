@@ -0,0 +1,77 @@
+use anchor_lang::AnchorDeserialize;
+use dynamic_amm_quote::pda::*;
+use prog_dynamic_amm::state::{Pool, PoolType};
+use solana_sdk::pubkey::Pubkey;
+
+fn get_customizable_pool() -> (Pubkey, Pool) {
+    let token_a_mint = Pubkey::new_unique();
+    let token_b_mint = Pubkey::new_unique();
+    let pool_key = derive_customizable_permissionless_constant_product_pool_address(
+        token_a_mint,
+        token_b_mint,
+    );
+
+    let data = vec![0u8; std::mem::size_of::<Pool>()];
+    let mut pool = Pool::deserialize(&mut data.as_ref()).unwrap();
+    pool.pool_type = PoolType::Permissionless;
+    pool.token_a_mint = token_a_mint;
+    pool.token_b_mint = token_b_mint;
+    pool.lp_mint = derive_pool_lp_mint_address(pool_key);
+    pool.a_vault = derive_vault_address(&token_a_mint);
+    pool.b_vault = derive_vault_address(&token_b_mint);
+    pool.a_vault_lp = derive_vault_lp_token_address(pool.a_vault, pool_key);
+    pool.b_vault_lp = derive_vault_lp_token_address(pool.b_vault, pool_key);
+    pool.protocol_token_a_fee = derive_pool_fee_token_address(token_a_mint, pool_key);
+    pool.protocol_token_b_fee = derive_pool_fee_token_address(token_b_mint, pool_key);
+
+    (pool_key, pool)
+}
+
+#[test]
+fn test_get_pool_origin() {
+    let (pool_key, mut pool) = get_customizable_pool();
+    assert_eq!(
+        get_pool_origin(pool_key, &pool, &[]),
+        PoolOrigin::Customizable
+    );
+
+    let config = derive_config_address(0);
+    let pool_key =
+        derive_constant_product_pool_with_config(pool.token_a_mint, pool.token_b_mint, config);
+    assert_eq!(get_pool_origin(pool_key, &pool, &[]), PoolOrigin::Unknown);
+    assert_eq!(
+        get_pool_origin(pool_key, &pool, &[derive_config_address(1), config]),
+        PoolOrigin::Config { config }
+    );
+
+    pool.fees.trade_fee_numerator = 1000;
+    pool.fees.trade_fee_denominator = 100000;
+    let pool_key = derive_pool_address(pool.token_a_mint, pool.token_b_mint, 100);
+    assert_eq!(
+        get_pool_origin(pool_key, &pool, &[]),
+        PoolOrigin::FeeTier { trade_fee_bps: 100 }
+    );
+
+    pool.pool_type = PoolType::Permissioned;
+    assert_eq!(
+        get_pool_origin(Pubkey::new_unique(), &pool, &[]),
+        PoolOrigin::Permissioned
+    );
+}
+
+#[test]
+fn test_verify_pool_addresses() {
+    let (pool_key, mut pool) = get_customizable_pool();
+    assert!(verify_pool_addresses(pool_key, &pool).is_empty());
+
+    let expected = pool.protocol_token_b_fee;
+    pool.protocol_token_b_fee = Pubkey::new_unique();
+    assert_eq!(
+        verify_pool_addresses(pool_key, &pool),
+        vec![AddressMismatch {
+            field: "protocol_token_b_fee",
+            expected,
+            actual: pool.protocol_token_b_fee,
+        }]
+    );
+}
@@ -1,10 +1,14 @@
 use anchor_client::{solana_client::nonblocking::rpc_client::RpcClient, Cluster};
+use anchor_lang::prelude::AnchorDeserialize;
 use anchor_lang::AccountDeserialize;
 use anchor_lang::InstructionData;
 use anchor_lang::ToAccountMetas;
 use anchor_spl::{
     associated_token::get_associated_token_address,
-    token::{spl_token::state::AccountState, Mint, TokenAccount},
+    token::{
+        spl_token::state::{Account as SplAccount, AccountState, Mint as SplMint},
+        Mint, TokenAccount,
+    },
 };
 use dynamic_amm_quote::QuoteData;
 use prog_dynamic_amm::state::Pool;
@@ -370,6 +374,14 @@ async fn test_quote() {
 
             println!("{:#?}", quote);
 
+            let protocol_token_fee = if in_token_mint == pool_state.token_a_mint {
+                pool_state.protocol_token_a_fee
+            } else {
+                pool_state.protocol_token_b_fee
+            };
+            let before_protocol_fee =
+                get_token_balance(&mut banks_client, protocol_token_fee).await;
+
             let token_received = swap(
                 &mut banks_client,
                 pool,
@@ -386,6 +398,346 @@ async fn test_quote() {
                 quote.out_amount, token_received,
                 "Swap quote amount doesn't matches actual swap out amount"
             );
+            // The partner share of the protocol fee stays in the pool until claimed
+            assert_eq!(
+                get_token_balance(&mut banks_client, protocol_token_fee).await
+                    - before_protocol_fee,
+                quote.protocol_fee - quote.partner_fee,
+                "Quote protocol fee doesn't match the protocol fee received"
+            );
+        }
+    }
+}
+
+async fn get_token_balance(banks_client: &mut BanksClient, token_account: Pubkey) -> u64 {
+    banks_client
+        .get_account(token_account)
+        .await
+        .unwrap()
+        .map(|account| TokenAccount::try_deserialize(&mut account.data.as_ref()).unwrap())
+        .unwrap()
+        .amount
+}
+
+/// Add or remove balanced liquidity, and return the token A, B balance changes of the user
+async fn balance_liquidity(
+    banks_client: &mut BanksClient,
+    pool: Pubkey,
+    ix_data: Vec<u8>,
+    quote_data: QuoteData,
+    mock_user_keypair: &Keypair,
+) -> (u64, u64) {
+    let QuoteData {
+        vault_a: vault_a_state,
+        pool: pool_state,
+        vault_b: vault_b_state,
+        ..
+    } = quote_data;
+
+    let user = mock_user_keypair.pubkey();
+    let user_a_token = get_associated_token_address(&user, &pool_state.token_a_mint);
+    let user_b_token = get_associated_token_address(&user, &pool_state.token_b_mint);
+
+    let before_a_balance = get_token_balance(banks_client, user_a_token).await;
+    let before_b_balance = get_token_balance(banks_client, user_b_token).await;
+
+    let ix_accounts = prog_dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
+        pool,
+        lp_mint: pool_state.lp_mint,
+        user_pool_lp: get_associated_token_address(&user, &pool_state.lp_mint),
+        a_vault_lp: pool_state.a_vault_lp,
+        b_vault_lp: pool_state.b_vault_lp,
+        a_vault: pool_state.a_vault,
+        b_vault: pool_state.b_vault,
+        a_vault_lp_mint: vault_a_state.lp_mint,
+        b_vault_lp_mint: vault_b_state.lp_mint,
+        a_token_vault: vault_a_state.token_vault,
+        b_token_vault: vault_b_state.token_vault,
+        user_a_token,
+        user_b_token,
+        user,
+        vault_program: prog_dynamic_vault::ID,
+        token_program: anchor_spl::token::ID,
+    }
+    .to_account_metas(None);
+
+    let ix = Instruction {
+        program_id: prog_dynamic_amm::ID,
+        accounts: ix_accounts,
+        data: ix_data,
+    };
+
+    process_and_assert_ok(&[ix], mock_user_keypair, &[mock_user_keypair], banks_client).await;
+
+    let after_a_balance = get_token_balance(banks_client, user_a_token).await;
+    let after_b_balance = get_token_balance(banks_client, user_b_token).await;
+
+    (
+        before_a_balance.abs_diff(after_a_balance),
+        before_b_balance.abs_diff(after_b_balance),
+    )
+}
+
+#[tokio::test]
+async fn test_balance_liquidity_quote() {
+    let pools: [Pubkey; 3] = [
+        solana_sdk::pubkey!("32D4zRxNc1EssbJieVHfPhZM3rH6CzfUPrWUuWxD9prG"),
+        solana_sdk::pubkey!("12axRhGcPfHdg345DSdtzrj51vkf6uk2jAxDF5E1cQY"),
+        solana_sdk::pubkey!("HKyrNi2yfBQyFY7jH3c2h9YqVrmuLqe3tUZXFQhNY6PW"),
+    ];
+
+    for pool in pools {
+        let mut program_test = ProgramTest::default();
+
+        program_test.prefer_bpf(true);
+        program_test.add_program("dynamic_amm", prog_dynamic_amm::ID, None);
+        program_test.add_program("dynamic_vault", prog_dynamic_vault::ID, None);
+
+        let rpc_client = RpcClient::new(Cluster::Mainnet.url().to_owned());
+
+        let SetupContextResult {
+            mock_user_keypair,
+            mut banks_client,
+        } = setup_accounts_and_start(&rpc_client, program_test, pool).await;
+
+        let quote_data = get_quote_data(&mut banks_client, pool).await;
+        let lp_mint = quote_data.pool.lp_mint;
+        let pool_lp_supply = banks_client
+            .get_account(lp_mint)
+            .await
+            .unwrap()
+            .map(|account| Mint::try_deserialize(&mut account.data.as_ref()).unwrap())
+            .unwrap()
+            .supply;
+
+        // Deposit rounds up, so the quoted amounts must be enough as maximum token amounts
+        let deposit_quote = dynamic_amm_quote::liquidity::compute_deposit_quote(
+            quote_data.pool.token_a_mint,
+            100_000_000,
+            pool_lp_supply,
+            quote_data.clone(),
+        )
+        .unwrap();
+
+        println!("{:#?}", deposit_quote);
+
+        let ix_data = prog_dynamic_amm::instruction::AddBalanceLiquidity {
+            pool_token_amount: deposit_quote.pool_token_amount,
+            maximum_token_a_amount: deposit_quote.token_a_amount,
+            maximum_token_b_amount: deposit_quote.token_b_amount,
+        }
+        .data();
+        let (token_a_deposited, token_b_deposited) = balance_liquidity(
+            &mut banks_client,
+            pool,
+            ix_data,
+            quote_data,
+            &mock_user_keypair,
+        )
+        .await;
+
+        assert_eq!(
+            (deposit_quote.token_a_amount, deposit_quote.token_b_amount),
+            (token_a_deposited, token_b_deposited),
+            "Deposit quote amounts doesn't matches actual deposited amounts"
+        );
+
+        // Withdraw rounds down, so the quoted amounts must be reachable as minimum token out amounts
+        let quote_data = get_quote_data(&mut banks_client, pool).await;
+        let pool_lp_supply = pool_lp_supply + deposit_quote.pool_token_amount;
+        let withdraw_quote = dynamic_amm_quote::liquidity::compute_withdraw_quote(
+            deposit_quote.pool_token_amount,
+            pool_lp_supply,
+            quote_data.clone(),
+        )
+        .unwrap();
+
+        println!("{:#?}", withdraw_quote);
+
+        let ix_data = prog_dynamic_amm::instruction::RemoveBalanceLiquidity {
+            pool_token_amount: withdraw_quote.pool_token_amount,
+            minimum_a_token_out: withdraw_quote.token_a_out_amount,
+            minimum_b_token_out: withdraw_quote.token_b_out_amount,
         }
+        .data();
+        let (token_a_received, token_b_received) = balance_liquidity(
+            &mut banks_client,
+            pool,
+            ix_data,
+            quote_data,
+            &mock_user_keypair,
+        )
+        .await;
+
+        assert_eq!(
+            (
+                withdraw_quote.token_a_out_amount,
+                withdraw_quote.token_b_out_amount
+            ),
+            (token_a_received, token_b_received),
+            "Withdraw quote amounts doesn't matches actual withdrawn amounts"
+        );
     }
 }
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn token_account(amount: u64) -> TokenAccount {
+    let mut data = vec![0u8; SplAccount::LEN];
+    SplAccount {
+        amount,
+        state: AccountState::Initialized,
+        ..SplAccount::default()
+    }
+    .pack_into_slice(&mut data);
+    TokenAccount::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn mint(supply: u64) -> Mint {
+    let mut data = vec![0u8; SplMint::LEN];
+    SplMint {
+        supply,
+        is_initialized: true,
+        ..SplMint::default()
+    }
+    .pack_into_slice(&mut data);
+    Mint::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+/// Constant product pool of 0.25% trade fee, 20% of it to the protocol, and half of the protocol fee to the partner
+fn get_fee_quote_data() -> QuoteData {
+    let mut pool: Pool = zeroed();
+    pool.token_a_mint = Pubkey::new_unique();
+    pool.token_b_mint = Pubkey::new_unique();
+    pool.enabled = true;
+    pool.fees.trade_fee_numerator = 250;
+    pool.fees.trade_fee_denominator = 100_000;
+    pool.fees.protocol_trade_fee_numerator = 20_000;
+    pool.fees.protocol_trade_fee_denominator = 100_000;
+    pool.partner_info.fee_numerator = 50_000;
+
+    let mut vault: Vault = zeroed();
+    vault.total_amount = 1_000_000_000;
+
+    QuoteData {
+        pool,
+        vault_a: vault.clone(),
+        vault_b: vault,
+        pool_vault_a_lp_token: token_account(1_000_000_000),
+        pool_vault_b_lp_token: token_account(1_000_000_000),
+        vault_a_lp_mint: mint(1_000_000_000),
+        vault_b_lp_mint: mint(1_000_000_000),
+        vault_a_token: token_account(1_000_000_000),
+        vault_b_token: token_account(1_000_000_000),
+        clock: Clock {
+            slot: 50,
+            unix_timestamp: 70,
+            ..Clock::default()
+        },
+        stake_data: HashMap::new(),
+    }
+}
+
+#[test]
+fn test_quote_fee_breakdown() {
+    let quote_data = get_fee_quote_data();
+    let token_a_mint = quote_data.pool.token_a_mint;
+
+    // trade fee 250, of which 50 to the protocol, of which 25 to the partner
+    let quote =
+        dynamic_amm_quote::compute_quote(token_a_mint, 100_000, quote_data.clone()).unwrap();
+    assert_eq!(quote.fee, 200);
+    assert_eq!(quote.protocol_fee, 50);
+    assert_eq!(quote.partner_fee, 25);
+
+    // The protocol fee is at least one token, the partner fee rounds down
+    let quote = dynamic_amm_quote::compute_quote(token_a_mint, 100, quote_data.clone()).unwrap();
+    assert_eq!(quote.fee, 0);
+    assert_eq!(quote.protocol_fee, 1);
+    assert_eq!(quote.partner_fee, 0);
+
+    // No partner
+    let mut no_partner_quote_data = quote_data.clone();
+    no_partner_quote_data.pool.partner_info.fee_numerator = 0;
+    let quote =
+        dynamic_amm_quote::compute_quote(token_a_mint, 100_000, no_partner_quote_data).unwrap();
+    assert_eq!(quote.protocol_fee, 50);
+    assert_eq!(quote.partner_fee, 0);
+
+    // No protocol fee
+    let mut no_protocol_quote_data = quote_data;
+    no_protocol_quote_data
+        .pool
+        .fees
+        .protocol_trade_fee_numerator = 0;
+    let quote =
+        dynamic_amm_quote::compute_quote(token_a_mint, 100_000, no_protocol_quote_data).unwrap();
+    assert_eq!(quote.fee, 250);
+    assert_eq!(quote.protocol_fee, 0);
+    assert_eq!(quote.partner_fee, 0);
+}
+
+#[test]
+fn test_quote_exact_out() {
+    let quote_data = get_fee_quote_data();
+    let token_a_mint = quote_data.pool.token_a_mint;
+
+    // Reachable: the minimal in amount, one less falls short
+    for out_amount in [1, 12_345, 1_000_000, 400_000_000] {
+        let result = dynamic_amm_quote::compute_quote_exact_out(
+            token_a_mint,
+            out_amount,
+            quote_data.clone(),
+        )
+        .unwrap();
+        assert!(result.quote.out_amount >= out_amount);
+        let quote =
+            dynamic_amm_quote::compute_quote(token_a_mint, result.in_amount, quote_data.clone())
+                .unwrap();
+        assert_eq!(quote.out_amount, result.quote.out_amount);
+        if result.in_amount > 1 {
+            let quote = dynamic_amm_quote::compute_quote(
+                token_a_mint,
+                result.in_amount - 1,
+                quote_data.clone(),
+            )
+            .unwrap();
+            assert!(quote.out_amount < out_amount);
+        }
+    }
+
+    // Unreachable: the pool can't give all its reserve, nor more
+    for out_amount in [1_000_000_000, u64::MAX] {
+        let err = dynamic_amm_quote::compute_quote_exact_out(
+            token_a_mint,
+            out_amount,
+            quote_data.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Out amount {} is not reachable", out_amount)
+        );
+    }
+
+    assert!(
+        dynamic_amm_quote::compute_quote_exact_out(token_a_mint, 0, quote_data.clone()).is_err()
+    );
+
+    // Pool preconditions are returned as is, not as an unreachable out amount
+    let mut disabled_quote_data = quote_data.clone();
+    disabled_quote_data.pool.enabled = false;
+    let err = dynamic_amm_quote::compute_quote_exact_out(token_a_mint, 1_000, disabled_quote_data)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Pool disabled");
+
+    let err = dynamic_amm_quote::compute_quote_exact_out(Pubkey::new_unique(), 1_000, quote_data)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "In token mint not matches with pool token mints"
+    );
+}
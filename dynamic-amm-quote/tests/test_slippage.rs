@@ -0,0 +1,19 @@
+use dynamic_amm_quote::slippage::{get_maximum_amount, get_minimum_amount};
+
+#[test]
+fn test_get_minimum_amount() {
+    assert_eq!(get_minimum_amount(1_000_000, 0).unwrap(), 1_000_000);
+    assert_eq!(get_minimum_amount(1_000_000, 50).unwrap(), 995_000);
+    assert_eq!(get_minimum_amount(999, 100).unwrap(), 989);
+    assert_eq!(get_minimum_amount(u64::MAX, 10_000).unwrap(), 0);
+    assert!(get_minimum_amount(1_000_000, 10_001).is_err());
+}
+
+#[test]
+fn test_get_maximum_amount() {
+    assert_eq!(get_maximum_amount(1_000_000, 0).unwrap(), 1_000_000);
+    assert_eq!(get_maximum_amount(1_000_000, 50).unwrap(), 1_005_000);
+    assert_eq!(get_maximum_amount(999, 100).unwrap(), 1009);
+    assert!(get_maximum_amount(u64::MAX, 1).is_err());
+    assert!(get_maximum_amount(1_000_000, 10_001).is_err());
+}
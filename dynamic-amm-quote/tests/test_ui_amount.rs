@@ -0,0 +1,39 @@
+use dynamic_amm_quote::ui_amount::{format_ui_amount, get_ui_price, parse_ui_amount};
+
+#[test]
+fn test_parse_ui_amount() {
+    assert_eq!(parse_ui_amount("1.5", 9).unwrap(), 1_500_000_000);
+    assert_eq!(parse_ui_amount("1", 6).unwrap(), 1_000_000);
+    assert_eq!(parse_ui_amount(".25", 2).unwrap(), 25);
+    assert_eq!(parse_ui_amount("42", 0).unwrap(), 42);
+    assert_eq!(parse_ui_amount("0.000001", 6).unwrap(), 1);
+
+    assert!(parse_ui_amount("0.0000001", 6).is_err());
+    assert!(parse_ui_amount("1.5.0", 6).is_err());
+    assert!(parse_ui_amount("-1", 6).is_err());
+    assert!(parse_ui_amount("", 6).is_err());
+    assert!(parse_ui_amount(".", 6).is_err());
+    assert!(parse_ui_amount("18446744073709.551616", 6).is_err());
+}
+
+#[test]
+fn test_format_ui_amount() {
+    assert_eq!(format_ui_amount(1_500_000_000, 9), "1.5");
+    assert_eq!(format_ui_amount(1, 6), "0.000001");
+    assert_eq!(format_ui_amount(1_000_000, 6), "1");
+    assert_eq!(format_ui_amount(42, 0), "42");
+    assert_eq!(format_ui_amount(0, 9), "0");
+
+    for (amount, decimals) in [(123_456_789u64, 6u8), (u64::MAX, 9), (7, 3)] {
+        let ui_amount = format_ui_amount(amount, decimals);
+        assert_eq!(parse_ui_amount(&ui_amount, decimals).unwrap(), amount);
+    }
+}
+
+#[test]
+fn test_get_ui_price() {
+    // 1 SOL (9 decimals) for 150 USDC (6 decimals)
+    let price = get_ui_price(1_000_000_000, 9, 150_000_000, 6).unwrap();
+    assert!((price - 150.0).abs() < f64::EPSILON);
+    assert!(get_ui_price(0, 9, 150_000_000, 6).is_none());
+}
@@ -0,0 +1,133 @@
+use anchor_lang::prelude::{AnchorDeserialize, Clock};
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::spl_token::state::{Account, AccountState, Mint as SplMint};
+use anchor_spl::token::{Mint, TokenAccount};
+use dynamic_amm_quote::{compute_quote, AmountTooLarge, QuoteData};
+use prog_dynamic_amm::state::Pool;
+use prog_dynamic_vault::state::{Vault, VaultLiquidity};
+use solana_sdk::program_pack::Pack;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::HashMap;
+
+fn zeroed<T: AnchorDeserialize>() -> T {
+    let data = vec![0u8; std::mem::size_of::<T>()];
+    T::deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn token_account(amount: u64) -> TokenAccount {
+    let mut data = vec![0u8; Account::LEN];
+    Account {
+        amount,
+        state: AccountState::Initialized,
+        ..Account::default()
+    }
+    .pack_into_slice(&mut data);
+    TokenAccount::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+fn mint(supply: u64) -> Mint {
+    let mut data = vec![0u8; SplMint::LEN];
+    SplMint {
+        supply,
+        is_initialized: true,
+        ..SplMint::default()
+    }
+    .pack_into_slice(&mut data);
+    Mint::try_deserialize(&mut data.as_ref()).unwrap()
+}
+
+const TOKEN_A_MINT: Pubkey = Pubkey::new_from_array([1u8; 32]);
+const TOKEN_B_MINT: Pubkey = Pubkey::new_from_array([2u8; 32]);
+
+/// Constant product pool without fee, owning all the liquidity of both vaults
+fn get_quote_data(vault_b_idle_amount: u64) -> QuoteData {
+    let mut pool: Pool = zeroed();
+    pool.token_a_mint = TOKEN_A_MINT;
+    pool.token_b_mint = TOKEN_B_MINT;
+    pool.enabled = true;
+
+    let mut vault_a: Vault = zeroed();
+    vault_a.total_amount = 1_000_000;
+    let mut vault_b: Vault = zeroed();
+    vault_b.total_amount = 1_000_000;
+
+    QuoteData {
+        pool,
+        vault_a,
+        vault_b,
+        pool_vault_a_lp_token: token_account(1_000_000),
+        pool_vault_b_lp_token: token_account(1_000_000),
+        vault_a_lp_mint: mint(1_000_000),
+        vault_b_lp_mint: mint(1_000_000),
+        vault_a_token: token_account(1_000_000),
+        vault_b_token: token_account(vault_b_idle_amount),
+        clock: Clock::default(),
+        stake_data: HashMap::new(),
+    }
+}
+
+#[test]
+fn test_get_liquidity_by_token_vault() {
+    let mut vault: Vault = zeroed();
+    vault.total_amount = 1_000;
+
+    let liquidity = vault.get_liquidity_by_token_vault(400);
+    assert_eq!(
+        liquidity,
+        VaultLiquidity {
+            idle_amount: 400,
+            deployed_amount: 600,
+        }
+    );
+    assert_eq!(liquidity.total_amount(), Some(1_000));
+
+    // Nothing deployed
+    let liquidity = vault.get_liquidity_by_token_vault(1_000);
+    assert_eq!(liquidity.deployed_amount, 0);
+
+    // Token vault holding more than the vault accounted for, eg: direct transfer
+    let liquidity = vault.get_liquidity_by_token_vault(1_200);
+    assert_eq!(liquidity.idle_amount, 1_200);
+    assert_eq!(liquidity.deployed_amount, 0);
+}
+
+#[test]
+fn test_is_served_by_idle() {
+    let liquidity = VaultLiquidity {
+        idle_amount: 400,
+        deployed_amount: 600,
+    };
+    assert!(liquidity.is_served_by_idle(0));
+    assert!(liquidity.is_served_by_idle(399));
+    // Same as the program, the token vault can't be fully drained
+    assert!(!liquidity.is_served_by_idle(400));
+    assert!(!liquidity.is_served_by_idle(1_000));
+
+    assert!(!VaultLiquidity::default().is_served_by_idle(0));
+}
+
+#[test]
+fn test_quote_out_vault_liquidity() {
+    let quote = compute_quote(TOKEN_A_MINT, 1_000, get_quote_data(500_000)).unwrap();
+    assert!(quote.out_amount > 0);
+    assert_eq!(quote.out_vault_idle_amount, 500_000);
+    assert_eq!(quote.out_vault_deployed_amount, 500_000);
+    let out_amount = quote.out_amount;
+
+    // Out vault is A when swapping B to A
+    let quote = compute_quote(TOKEN_B_MINT, 1_000, get_quote_data(500_000)).unwrap();
+    assert_eq!(quote.out_vault_idle_amount, 1_000_000);
+    assert_eq!(quote.out_vault_deployed_amount, 0);
+
+    let err = compute_quote(TOKEN_A_MINT, 1_000, get_quote_data(out_amount)).unwrap_err();
+    assert_eq!(err.to_string(), "Out amount > vault reserve");
+    assert_eq!(
+        err.downcast_ref::<AmountTooLarge>(),
+        Some(&AmountTooLarge::ExceedReserve)
+    );
+
+    let quote = compute_quote(TOKEN_A_MINT, 1_000, get_quote_data(out_amount + 1)).unwrap();
+    assert_eq!(quote.out_amount, out_amount);
+    assert_eq!(quote.out_vault_idle_amount, out_amount + 1);
+    assert_eq!(quote.out_vault_deployed_amount, 1_000_000 - out_amount - 1);
+}
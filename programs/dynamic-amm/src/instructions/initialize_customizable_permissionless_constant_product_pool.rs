@@ -166,7 +166,7 @@ pub struct InitializeCustomizablePermissionlessConstantProductPool<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
 pub struct CustomizableParams {
     /// Trading fee.
     pub trade_fee_numerator: u32,
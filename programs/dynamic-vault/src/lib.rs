@@ -41,6 +41,24 @@ pub mod dynamic_vault {
     pub fn initialize(ctx: Context<Initialize>) -> Result<()> {
         Ok(())
     }
+
+    /// user deposit liquidity to vault
+    pub fn deposit(
+        ctx: Context<DepositWithdrawLiquidity>,
+        token_amount: u64,
+        minimum_lp_token_amount: u64,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// user withdraw liquidity from vault
+    pub fn withdraw(
+        ctx: Context<DepositWithdrawLiquidity>,
+        unmint_amount: u64,
+        min_out_amount: u64,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Accounts for [vault::initialize]
@@ -95,3 +113,27 @@ pub struct Initialize<'info> {
     /// system_program
     pub system_program: Program<'info, System>,
 }
+
+/// Accounts for [vault::deposit] and [vault::withdraw]
+#[derive(Accounts)]
+pub struct DepositWithdrawLiquidity<'info> {
+    /// Vault account
+    #[account(mut, has_one = token_vault, has_one = lp_mint)]
+    pub vault: Box<Account<'info, Vault>>,
+    /// Token vault account
+    #[account(mut)]
+    pub token_vault: Box<Account<'info, TokenAccount>>,
+    /// LP mint account
+    #[account(mut)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    /// User token account. Token is transferred from it on deposit, and into it on withdraw
+    #[account(mut, token::mint = vault.token_mint)]
+    pub user_token: Box<Account<'info, TokenAccount>>,
+    /// User LP token account. LP is minted into it on deposit, and burned from it on withdraw
+    #[account(mut, token::mint = lp_mint)]
+    pub user_lp: Box<Account<'info, TokenAccount>>,
+    /// User account. Must be owner of user_token and user_lp
+    pub user: Signer<'info>,
+    /// token_program
+    pub token_program: Program<'info, Token>,
+}
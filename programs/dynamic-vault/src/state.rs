@@ -9,6 +9,8 @@ pub const MAX_STRATEGY: usize = 30;
 pub const MAX_BUMPS: usize = 10;
 /// DENOMINATOR of degradation
 pub const LOCKED_PROFIT_DEGRADATION_DENOMINATOR: u128 = 1_000_000_000_000;
+/// Number of seconds in a year, used to annualize the vault yield
+pub const SECONDS_PER_YEAR: u64 = 365 * 24 * 60 * 60;
 
 /// Vault struct
 #[account]
@@ -79,6 +81,142 @@ impl Vault {
         )
         .ok()
     }
+
+    /// Get snapshot of the vault at `current_time`. A future `current_time` projects the snapshot with the locked profit drip.
+    pub fn get_snapshot(&self, current_time: u64, lp_supply: u64) -> Option<VaultSnapshot> {
+        Some(VaultSnapshot {
+            timestamp: current_time,
+            unlocked_amount: self.get_unlocked_amount(current_time)?,
+            lp_supply,
+        })
+    }
+
+    /// Get the strategy addresses that vault currently supports
+    pub fn get_strategies(&self) -> impl Iterator<Item = &Pubkey> {
+        self.strategies
+            .iter()
+            .filter(|strategy| **strategy != Pubkey::default())
+    }
+
+    /// Get the liquidity deployed to strategies. `strategies` are the states of [Vault::strategies]
+    pub fn get_deployed_amount<'a>(
+        &self,
+        strategies: impl IntoIterator<Item = &'a Strategy>,
+    ) -> Option<u64> {
+        strategies.into_iter().try_fold(0u64, |total, strategy| {
+            total.checked_add(strategy.current_liquidity)
+        })
+    }
+
+    /// Get the liquidity split between token_vault and strategies. `strategies` are the states of [Vault::strategies]
+    pub fn get_liquidity<'a>(
+        &self,
+        strategies: impl IntoIterator<Item = &'a Strategy>,
+    ) -> Option<VaultLiquidity> {
+        let deployed_amount = self.get_deployed_amount(strategies)?;
+        Some(VaultLiquidity {
+            idle_amount: self.total_amount.checked_sub(deployed_amount)?,
+            deployed_amount,
+        })
+    }
+
+    /// Get the liquidity split between token_vault and strategies from the token_vault balance
+    pub fn get_liquidity_by_token_vault(&self, token_vault_amount: u64) -> VaultLiquidity {
+        VaultLiquidity {
+            idle_amount: token_vault_amount,
+            deployed_amount: self.total_amount.saturating_sub(token_vault_amount),
+        }
+    }
+}
+
+/// Liquidity of the vault, split between token_vault and strategies
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VaultLiquidity {
+    /// Liquidity remaining in token_vault
+    pub idle_amount: u64,
+    /// Liquidity deployed to strategies
+    pub deployed_amount: u64,
+}
+
+impl VaultLiquidity {
+    /// Total liquidity of the vault
+    pub fn total_amount(&self) -> Option<u64> {
+        self.idle_amount.checked_add(self.deployed_amount)
+    }
+
+    /// Whether `out_amount` can be withdrawn from token_vault, without pulling liquidity from strategies
+    pub fn is_served_by_idle(&self, out_amount: u64) -> bool {
+        out_amount < self.idle_amount
+    }
+}
+
+/// Snapshot of the vault, used to estimate the vault yield
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct VaultSnapshot {
+    /// Timestamp (in seconds) of the snapshot
+    pub timestamp: u64,
+    /// Unlocked amount of the vault
+    pub unlocked_amount: u64,
+    /// Lp mint supply of the vault
+    pub lp_supply: u64,
+}
+
+impl VaultSnapshot {
+    /// Get token amount per lp token
+    pub fn get_lp_price(&self) -> Option<f64> {
+        if self.lp_supply == 0 {
+            return None;
+        }
+        Some(self.unlocked_amount as f64 / self.lp_supply as f64)
+    }
+}
+
+/// Estimate the compounded APY of the vault from the lp price growth over the snapshots. Snapshots with no lp supply are skipped.
+pub fn estimate_apy(snapshots: &[VaultSnapshot]) -> Option<f64> {
+    let prices = snapshots
+        .iter()
+        .filter_map(|snapshot| Some((snapshot.timestamp, snapshot.get_lp_price()?)))
+        .collect::<Vec<_>>();
+    estimate_apy_by_prices(&prices)
+}
+
+/// Estimate the compounded APY from (timestamp, price) samples. The growth rate is the least squares fit of the log price over time,
+/// so every sample counts and a single noisy sample doesn't skew the estimation. Same as the growth between the first and the last sample when there are two.
+pub fn estimate_apy_by_prices(prices: &[(u64, f64)]) -> Option<f64> {
+    let first_timestamp = prices.first()?.0;
+    let samples = prices
+        .iter()
+        .filter(|(_, price)| *price > 0.0)
+        .map(|(timestamp, price)| {
+            // Relative to the first sample to keep the precision of f64
+            let elapsed = *timestamp as f64 - first_timestamp as f64;
+            (elapsed, price.ln())
+        })
+        .collect::<Vec<_>>();
+    if samples.len() < 2 {
+        return None;
+    }
+
+    let count = samples.len() as f64;
+    let mean_elapsed = samples.iter().map(|(elapsed, _)| elapsed).sum::<f64>() / count;
+    let mean_log_price = samples.iter().map(|(_, log_price)| log_price).sum::<f64>() / count;
+
+    let (covariance, variance) = samples.iter().fold(
+        (0.0, 0.0),
+        |(covariance, variance), (elapsed, log_price)| {
+            let elapsed = elapsed - mean_elapsed;
+            (
+                covariance + elapsed * (log_price - mean_log_price),
+                variance + elapsed * elapsed,
+            )
+        },
+    );
+    if variance == 0.0 {
+        return None;
+    }
+    // Log growth per second
+    let rate = covariance / variance;
+    Some((rate * SECONDS_PER_YEAR as f64).exp() - 1.0)
 }
 
 /// LockedProfitTracker struct
@@ -110,6 +248,17 @@ impl LockedProfitTracker {
         let locked_profit = u64::try_from(locked_profit).ok()?;
         Some(locked_profit)
     }
+
+    /// Get the timestamp (in seconds) when all the locked profit is released
+    pub fn get_unlock_time(&self) -> Option<u64> {
+        if self.locked_profit_degradation == 0 {
+            return None;
+        }
+        let duration = LOCKED_PROFIT_DEGRADATION_DENOMINATOR
+            .checked_add(u128::from(self.locked_profit_degradation) - 1)?
+            .checked_div(u128::from(self.locked_profit_degradation))?;
+        self.last_report.checked_add(u64::try_from(duration).ok()?)
+    }
 }
 
 impl Default for StrategyType {
@@ -0,0 +1,147 @@
+use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use anchor_client::solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use anchor_lang::{AccountDeserialize, Discriminator};
+use dynamic_amm_quote::discovery::*;
+use prog_dynamic_amm::state::{Config, Pool, PoolType};
+use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
+use std::collections::BTreeMap;
+
+/// Max number of accounts fetched by a single getMultipleAccounts
+pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+fn to_rpc_filter(filter: MemcmpFilter) -> RpcFilterType {
+    RpcFilterType::Memcmp(Memcmp::new_raw_bytes(filter.offset, filter.bytes))
+}
+
+/// Get the addresses of the `T` accounts of the program matching `filters`. Only the addresses are fetched, the account data are fetched in chunks afterward.
+pub fn get_program_account_keys<T: Discriminator>(
+    client: &RpcClient,
+    filters: Vec<MemcmpFilter>,
+) -> anyhow::Result<Vec<Pubkey>> {
+    let mut filters = filters;
+    filters.push(MemcmpFilter::discriminator::<T>());
+    let filters = filters.into_iter().map(to_rpc_filter).collect();
+
+    let accounts = client.get_program_accounts_with_config(
+        &prog_dynamic_amm::ID,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: 0,
+                    length: 0,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    Ok(accounts.into_iter().map(|(key, _)| key).collect())
+}
+
+/// Fetch and deserialize the accounts in chunks of [MAX_MULTIPLE_ACCOUNTS]. Closed accounts are skipped.
+pub fn get_multiple_accounts_chunked<T: AccountDeserialize>(
+    client: &RpcClient,
+    keys: &[Pubkey],
+) -> anyhow::Result<Vec<(Pubkey, T)>> {
+    let mut result = vec![];
+    for chunk in keys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = client.get_multiple_accounts(chunk)?;
+        for (key, account) in chunk.iter().zip(accounts) {
+            if let Some(account) = account {
+                result.push((*key, T::try_deserialize(&mut account.data.as_ref())?));
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Get all the pools having `token_mint` as token A or token B
+pub fn get_pools_by_token(
+    client: &RpcClient,
+    token_mint: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, Pool)>> {
+    let mut keys = vec![];
+    for filter in get_pool_token_mint_filters(token_mint) {
+        keys.extend(get_program_account_keys::<Pool>(client, vec![filter])?);
+    }
+    keys.sort();
+    keys.dedup();
+    get_multiple_accounts_chunked(client, &keys)
+}
+
+/// Get all the pools where `partner_authority` is the partner
+pub fn get_pools_by_partner_authority(
+    client: &RpcClient,
+    partner_authority: &Pubkey,
+) -> anyhow::Result<Vec<(Pubkey, Pool)>> {
+    let keys = get_program_account_keys::<Pool>(
+        client,
+        vec![get_pool_partner_authority_filter(partner_authority)],
+    )?;
+    get_multiple_accounts_chunked(client, &keys)
+}
+
+/// Get the pools created from each of the configs, with a single getProgramAccounts. Only the mints of the permissionless pools are fetched,
+/// each pool is matched to a config by deriving its address from the config, the pool fees can be changed after creation.
+pub fn get_pools_by_configs(
+    client: &RpcClient,
+    configs: &[Pubkey],
+) -> anyhow::Result<BTreeMap<Pubkey, Vec<Pubkey>>> {
+    let accounts = client.get_program_accounts_with_config(
+        &prog_dynamic_amm::ID,
+        RpcProgramAccountsConfig {
+            filters: Some(vec![
+                to_rpc_filter(MemcmpFilter::discriminator::<Pool>()),
+                to_rpc_filter(get_pool_type_filter(PoolType::Permissionless)?),
+            ]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: Some(UiDataSliceConfig {
+                    offset: POOL_TOKEN_A_MINT_OFFSET,
+                    length: 64,
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    let mut pools = vec![];
+    for (pool_key, account) in accounts {
+        if account.data.len() < 64 {
+            continue;
+        }
+        let token_a_mint = Pubkey::try_from(&account.data[..32])?;
+        let token_b_mint = Pubkey::try_from(&account.data[32..64])?;
+        pools.push((pool_key, token_a_mint, token_b_mint));
+    }
+    Ok(group_pools_by_config(&pools, configs))
+}
+
+/// Get the pools created from the config
+pub fn get_pools_by_config(client: &RpcClient, config: &Pubkey) -> anyhow::Result<Vec<Pubkey>> {
+    let mut pools_by_config = get_pools_by_configs(client, &[*config])?;
+    Ok(pools_by_config.remove(config).unwrap_or_default())
+}
+
+/// Get all the configs. When `pool_creator_authority` is set, only the configs restricted to it are returned.
+pub fn get_configs(
+    client: &RpcClient,
+    pool_creator_authority: Option<&Pubkey>,
+) -> anyhow::Result<Vec<(Pubkey, Config)>> {
+    let filters = match pool_creator_authority {
+        Some(pool_creator_authority) => {
+            vec![get_config_pool_creator_authority_filter(
+                pool_creator_authority,
+            )]
+        }
+        None => vec![],
+    };
+    let keys = get_program_account_keys::<Config>(client, filters)?;
+    get_multiple_accounts_chunked(client, &keys)
+}
@@ -4,6 +4,9 @@ pub const RENT_EXEMPTION_SYSTEM_ACCOUNT: u64 = 890880;
 pub const TRANSFER_TOKEN_COMPUTE_UNIT: u32 = 50000;
 pub const DEFAULT_COMPUTE_UNIT: u32 = 200000;
 pub const CREATE_POOL_COMPUTE_UNIT: u32 = 400000;
+pub const CREATE_TOKEN_ACCOUNT_COMPUTE_UNIT: u32 = 30000;
+pub const PARTNER_CLAIM_FEE_COMPUTE_UNIT: u32 = 50000;
+pub const MAX_COMPUTE_UNIT: u32 = 1400000;
 
 pub fn estimate_sol_transfer_fee(priority_fee: u64) -> u64 {
     let compute_unit: u64 = TRANSFER_SOL_COMPUTE_UNIT.into();
@@ -0,0 +1,250 @@
+use crate::*;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::admin::validate_amp_change;
+use dynamic_amm_quote::amp_ramp::{
+    compute_amp_price_impact, get_next_step_time, plan_amp_ramp, AmpPriceImpact, AmpRampStep,
+};
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use prog_dynamic_amm::state::CurveType;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const AMP_UPDATE_POLL_COUNT: usize = 30;
+
+#[derive(Parser, Debug, Clone)]
+pub struct AmpRampDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    #[clap(long, env)]
+    pub target_amp: u64,
+    /// Maximum amp change of a step
+    #[clap(long, env)]
+    pub max_amp_change: u64,
+    /// Seconds between two steps, at least the minimum amp change duration of 600 seconds
+    #[clap(long, env, default_value = "600")]
+    pub interval: u64,
+    /// In token of the reference swap the price impact is projected for. Token A when not set.
+    #[clap(long, env)]
+    pub source_token: Option<Pubkey>,
+    /// In amount of the reference swap. Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub in_amount: String,
+    /// Execute the plan, saving the progress to this file. The job resumes from it after a restart. Only the plan is printed when not set.
+    #[clap(long, env)]
+    pub job_path: Option<PathBuf>,
+}
+
+/// JSON output of `dynamic-amm amp-ramp`
+#[derive(Serialize, Debug)]
+pub struct AmpRampOutput {
+    /// Price impact of the reference swap at the current amp
+    pub current_price_impact: AmpPriceImpact,
+    /// Planned steps
+    pub steps: Vec<AmpRampStepOutput>,
+    /// Number of steps done by the job, null when only the plan is printed
+    pub completed: Option<usize>,
+}
+
+/// Planned step, with the price impact of the reference swap at the amp of the step
+#[derive(Serialize, Debug)]
+pub struct AmpRampStepOutput {
+    pub step: AmpRampStep,
+    pub price_impact: AmpPriceImpact,
+}
+
+/// Progress of an amp ramp job
+#[derive(serde::Serialize, serde::Deserialize, Debug)]
+struct AmpRampJob {
+    pool: String,
+    target_amp: u64,
+    interval: u64,
+    /// Amp after each step
+    amps: Vec<u64>,
+    /// Number of steps done
+    completed: usize,
+}
+
+impl AmpRampJob {
+    fn load(path: &Path) -> anyhow::Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_reader(File::open(path)?)?))
+    }
+
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        serde_json::to_writer_pretty(File::create(path)?, self)?;
+        Ok(())
+    }
+}
+
+fn get_amp(curve_type: CurveType) -> (u64, u64) {
+    match curve_type {
+        CurveType::Stable {
+            amp,
+            last_amp_updated_timestamp,
+            ..
+        } => (amp, last_amp_updated_timestamp),
+        CurveType::ConstantProduct => output::exit_with_error("Only stable pools have an amp"),
+    }
+}
+
+pub fn process_amp_ramp(args: &Args, sub_args: &AmpRampDynamicAmmArgs) {
+    let AmpRampDynamicAmmArgs {
+        pool,
+        target_amp,
+        max_amp_change,
+        interval,
+        source_token,
+        in_amount,
+        job_path,
+    } = sub_args;
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let quote_data = get_quote_data(args, pool).unwrap();
+    let (current_amp, last_amp_updated_timestamp) = get_amp(quote_data.pool.curve_type);
+
+    let source_token = source_token.unwrap_or(quote_data.pool.token_a_mint);
+    let in_mint: Mint = program_amm_client.account(source_token).unwrap();
+    let in_amount = args.parse_amount(in_amount, in_mint.decimals).unwrap();
+
+    let steps = plan_amp_ramp(
+        current_amp,
+        *target_amp,
+        *max_amp_change,
+        last_amp_updated_timestamp,
+        *interval,
+    )
+    .unwrap();
+
+    let current_impact =
+        compute_amp_price_impact(&quote_data, current_amp, source_token, in_amount).unwrap();
+    info!(
+        "current amp {} out_amount {} price_impact {:.2} bps",
+        current_amp, current_impact.out_amount, current_impact.price_impact_bps
+    );
+    let mut step_outputs = vec![];
+    for (i, step) in steps.iter().enumerate() {
+        let impact =
+            compute_amp_price_impact(&quote_data, step.amp, source_token, in_amount).unwrap();
+        info!(
+            "step {} amp {} earliest_time {} out_amount {} price_impact {:.2} bps",
+            i, step.amp, step.earliest_time, impact.out_amount, impact.price_impact_bps
+        );
+        step_outputs.push(AmpRampStepOutput {
+            step: *step,
+            price_impact: impact,
+        });
+    }
+    let mut ramp_output = AmpRampOutput {
+        current_price_impact: current_impact,
+        steps: step_outputs,
+        completed: None,
+    };
+    output::emit(&ramp_output);
+
+    let Some(job_path) = job_path else {
+        return;
+    };
+    if args.tx_action != TX_ACTION_SENT_TX {
+        output::exit_with_error(&format!(
+            "Executing the amp ramp requires --tx-action {}",
+            TX_ACTION_SENT_TX
+        ));
+    }
+
+    let mut job = match AmpRampJob::load(job_path).unwrap() {
+        Some(job) => {
+            if job.pool != pool.to_string() || job.target_amp != *target_amp {
+                output::exit_with_error(&format!(
+                    "Job {} ramps pool {} to amp {}",
+                    job_path.display(),
+                    job.pool,
+                    job.target_amp
+                ));
+            }
+            info!(
+                "resume job, {} of {} steps done",
+                job.completed,
+                job.amps.len()
+            );
+            job
+        }
+        None => {
+            let job = AmpRampJob {
+                pool: pool.to_string(),
+                target_amp: *target_amp,
+                interval: *interval,
+                amps: steps.iter().map(|step| step.amp).collect(),
+                completed: 0,
+            };
+            job.save(job_path).unwrap();
+            job
+        }
+    };
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+    while job.completed < job.amps.len() {
+        let step_amp = job.amps[job.completed];
+        let quote_data = get_quote_data(args, pool).unwrap();
+        let (amp, last_amp_updated_timestamp) = get_amp(quote_data.pool.curve_type);
+
+        // the step may have landed before the job was saved
+        if amp == step_amp {
+            job.completed += 1;
+            job.save(job_path).unwrap();
+            continue;
+        }
+
+        let next_time = get_next_step_time(last_amp_updated_timestamp, job.interval);
+        let chain_time = quote_data.clock.unix_timestamp as u64;
+        if chain_time < next_time {
+            let wait = next_time - chain_time;
+            info!(
+                "step {} amp {} in {} seconds",
+                job.completed, step_amp, wait
+            );
+            std::thread::sleep(Duration::from_secs(wait.min(60)));
+            continue;
+        }
+        info!("step {} amp {} at {}", job.completed, step_amp, chain_time);
+
+        let curve_type =
+            validate_amp_change(&quote_data.pool, step_amp, &quote_data.clock).unwrap();
+        let mut expected = quote_data.pool.clone();
+        expected.curve_type = curve_type;
+        let builder = PoolInstructionBuilder {
+            pool_key: *pool,
+            pool: &quote_data.pool,
+            a_vault: &quote_data.vault_a,
+            b_vault: &quote_data.vault_b,
+        };
+        let ix = builder.override_curve_param(keypair.pubkey(), curve_type);
+        send_pool_admin_instruction(args, pool, &quote_data.pool, &expected, ix, &keypair);
+
+        // wait for the new amp to be finalized, so the step is not sent twice
+        let landed = (0..AMP_UPDATE_POLL_COUNT).any(|_| {
+            let pool_state: prog_dynamic_amm::state::Pool =
+                program_amm_client.account(*pool).unwrap();
+            let landed = get_amp(pool_state.curve_type).0 == step_amp;
+            if !landed {
+                std::thread::sleep(Duration::from_secs(2));
+            }
+            landed
+        });
+        if !landed {
+            output::exit_with_error(&format!(
+                "Pool amp is not {} after step {}",
+                step_amp, job.completed
+            ));
+        }
+        job.completed += 1;
+        job.save(job_path).unwrap();
+    }
+    info!("done amp ramp to {}", target_amp);
+    ramp_output.completed = Some(job.completed);
+    output::emit(&ramp_output);
+}
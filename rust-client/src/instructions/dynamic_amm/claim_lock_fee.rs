@@ -0,0 +1,164 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::instruction::{
+    create_associated_token_account_idempotent, PoolInstructionBuilder,
+};
+use prog_dynamic_amm::state::Pool;
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ClaimLockFeeDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Max pool lp amount of fee to claim, all the claimable fee when not set. Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub max_amount: Option<String>,
+}
+
+/// Fee claimable from a lock escrow. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct ClaimableLockFeeOutput {
+    /// Pool lp amount of the fee
+    pub lp: u64,
+    pub token_a: u64,
+    pub token_b: u64,
+}
+
+impl From<&prog_dynamic_amm::event::ClaimFee> for ClaimableLockFeeOutput {
+    fn from(claimable: &prog_dynamic_amm::event::ClaimFee) -> Self {
+        ClaimableLockFeeOutput {
+            lp: claimable.amount,
+            token_a: claimable.a_fee,
+            token_b: claimable.b_fee,
+        }
+    }
+}
+
+/// JSON output of `dynamic-amm claim-lock-fee`
+#[derive(Serialize, Debug)]
+pub struct ClaimLockFeeOutput {
+    pub claimable: ClaimableLockFeeOutput,
+}
+
+/// Create the owner token accounts used by claim_fee when missing: the token A and B accounts receiving the claimed fee,
+/// and the lp account the program uses as `source_tokens`
+pub fn get_create_fee_token_accounts_ixs(
+    pool_state: &Pool,
+    owner: Pubkey,
+    payer: Pubkey,
+) -> Vec<Instruction> {
+    [
+        pool_state.token_a_mint,
+        pool_state.token_b_mint,
+        pool_state.lp_mint,
+    ]
+    .into_iter()
+    .map(|mint| create_associated_token_account_idempotent(payer, owner, mint))
+    .collect()
+}
+
+/// Claimable fee of the lock escrow, by simulating a claim of all the fee. `ixs` are the instructions required before the claim.
+pub fn get_claimable_lock_fee(
+    args: &Args,
+    builder: &PoolInstructionBuilder,
+    owner: Pubkey,
+    ixs: &[Instruction],
+) -> anyhow::Result<prog_dynamic_amm::event::ClaimFee> {
+    let mut ixs = ixs.to_vec();
+    ixs.push(builder.claim_fee(owner, u64::MAX));
+    transaction_utils::simulate_pool_event(args, owner, &ixs)
+}
+
+pub fn process_claim_lock_fee_dynamic_pool(args: &Args, sub_args: &ClaimLockFeeDynamicAmmArgs) {
+    let ClaimLockFeeDynamicAmmArgs { pool, max_amount } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let pool_state: Pool = program_amm_client.account(*pool).unwrap();
+
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+    let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
+    let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
+
+    let pool_lp_mint: Mint = program_amm_client.account(pool_state.lp_mint).unwrap();
+    let token_a_mint: Mint = program_amm_client.account(pool_state.token_a_mint).unwrap();
+    let token_b_mint: Mint = program_amm_client.account(pool_state.token_b_mint).unwrap();
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &pool_state,
+        a_vault: &a_vault_state,
+        b_vault: &b_vault_state,
+    };
+
+    ixs.extend(get_create_fee_token_accounts_ixs(
+        &pool_state,
+        keypair.pubkey(),
+        keypair.pubkey(),
+    ));
+
+    let claimable = get_claimable_lock_fee(args, &builder, keypair.pubkey(), &ixs).unwrap();
+    info!(
+        "claimable lp {} token_a {} token_b {}",
+        args.format_amount(claimable.amount, pool_lp_mint.decimals),
+        args.format_amount(claimable.a_fee, token_a_mint.decimals),
+        args.format_amount(claimable.b_fee, token_b_mint.decimals)
+    );
+    output::emit(&ClaimLockFeeOutput {
+        claimable: ClaimableLockFeeOutput::from(&claimable),
+    });
+
+    let max_amount = match max_amount {
+        Some(max_amount) => args
+            .parse_amount(max_amount, pool_lp_mint.decimals)
+            .unwrap(),
+        None => claimable.amount,
+    };
+    if claimable.amount == 0 || max_amount == 0 {
+        info!("nothing to claim");
+        return;
+    }
+    ixs.push(builder.claim_fee(keypair.pubkey(), max_amount));
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let payload = args
+        .to_rpc_args()
+        .send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            "".to_string(),
+            sucess_cb,
+            failed_cb,
+        )
+        .unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done claim lock fee {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot claim lock fee");
+}
@@ -0,0 +1,282 @@
+use self::fee_estimation::{
+    CREATE_TOKEN_ACCOUNT_COMPUTE_UNIT, MAX_COMPUTE_UNIT, PARTNER_CLAIM_FEE_COMPUTE_UNIT,
+};
+use crate::discovery::get_pools_by_partner_authority;
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use dynamic_amm_quote::instruction::{
+    create_associated_token_account_idempotent, partner_claim_fee,
+};
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::packet::PACKET_DATA_SIZE;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Pool to claim, with the max token A and B amounts claimed from it. Parsed from `<pool>` or `<pool>:<max_amount_a>:<max_amount_b>`,
+/// an empty amount claims all the pending fee of the token. Amounts are parsed with the decimals of the pool tokens.
+#[derive(Debug, Clone)]
+pub struct PoolClaimArg {
+    pub pool: Pubkey,
+    pub max_amount_a: Option<String>,
+    pub max_amount_b: Option<String>,
+}
+
+impl FromStr for PoolClaimArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_amount = |amount: &str| (!amount.is_empty()).then(|| amount.to_string());
+        match s.split(':').collect::<Vec<&str>>().as_slice() {
+            [pool] => Ok(PoolClaimArg {
+                pool: Pubkey::from_str(pool)?,
+                max_amount_a: None,
+                max_amount_b: None,
+            }),
+            [pool, max_amount_a, max_amount_b] => Ok(PoolClaimArg {
+                pool: Pubkey::from_str(pool)?,
+                max_amount_a: parse_amount(max_amount_a),
+                max_amount_b: parse_amount(max_amount_b),
+            }),
+            _ => Err(anyhow::anyhow!(
+                "Expected <pool> or <pool>:<max_amount_a>:<max_amount_b>, got {}",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ClaimPartnerFeesDynamicAmmArgs {
+    /// Pools to claim, as `<pool>` or `<pool>:<max_amount_a>:<max_amount_b>`. All the pending fee is claimed when the amounts are not set.
+    /// All the pools of the partner authority with pending fee when not set. Raw amounts, or UI amounts with --ui-amount
+    #[clap(long, env)]
+    pub pool: Vec<PoolClaimArg>,
+}
+
+/// Partner fee claimed from a pool, in the `claims` list of the JSON output. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct PartnerFeeClaimOutput {
+    pub pool: String,
+    pub token_a: u64,
+    pub token_b: u64,
+}
+
+/// Instruction claiming the partner fee of a pool, with the mints of the token accounts it lacks
+struct PoolClaim {
+    pool_key: Pubkey,
+    ix: Instruction,
+    missing_token_mints: Vec<Pubkey>,
+}
+
+/// Mints of the token accounts lacked by the claims, created once in the transaction of the claims
+fn get_missing_token_mints(claims: &[PoolClaim]) -> Vec<Pubkey> {
+    let mut mints = vec![];
+    for mint in claims
+        .iter()
+        .flat_map(|claim| claim.missing_token_mints.iter())
+    {
+        if !mints.contains(mint) {
+            mints.push(*mint);
+        }
+    }
+    mints
+}
+
+fn get_compute_unit(claims: &[PoolClaim]) -> u64 {
+    let create_count = get_missing_token_mints(claims).len() as u64;
+    claims.len() as u64 * u64::from(PARTNER_CLAIM_FEE_COMPUTE_UNIT)
+        + create_count * u64::from(CREATE_TOKEN_ACCOUNT_COMPUTE_UNIT)
+}
+
+fn get_transaction_ixs(
+    payer: &Pubkey,
+    priority_fee: u64,
+    claims: &[PoolClaim],
+) -> Vec<Instruction> {
+    let compute_unit = u32::try_from(get_compute_unit(claims)).unwrap_or(u32::MAX);
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(compute_unit),
+    ];
+    // Every transaction creates the token accounts its claims lack, it may land without the others
+    ixs.extend(
+        get_missing_token_mints(claims)
+            .into_iter()
+            .map(|mint| create_associated_token_account_idempotent(*payer, *payer, mint)),
+    );
+    ixs.extend(claims.iter().map(|claim| claim.ix.clone()));
+    ixs
+}
+
+/// Whether the claims fit in a single transaction, under the packet size and the compute unit limit
+fn fit_in_transaction(payer: &Pubkey, priority_fee: u64, claims: &[PoolClaim]) -> bool {
+    if get_compute_unit(claims) > u64::from(MAX_COMPUTE_UNIT) {
+        return false;
+    }
+    // Unsigned transaction holds placeholder signatures of the same size
+    let tx = Transaction::new_with_payer(
+        &get_transaction_ixs(payer, priority_fee, claims),
+        Some(payer),
+    );
+    bincode::serialized_size(&tx).map_or(false, |size| size <= PACKET_DATA_SIZE as u64)
+}
+
+/// Split the claims into transactions, in order. A claim not fitting alone is still sent alone.
+fn split_claims(payer: &Pubkey, priority_fee: u64, claims: Vec<PoolClaim>) -> Vec<Vec<PoolClaim>> {
+    let mut batches: Vec<Vec<PoolClaim>> = vec![];
+    let mut batch = vec![];
+    for claim in claims {
+        batch.push(claim);
+        if batch.len() > 1 && !fit_in_transaction(payer, priority_fee, &batch) {
+            let claim = batch.pop().unwrap();
+            batches.push(std::mem::replace(&mut batch, vec![claim]));
+        }
+    }
+    if !batch.is_empty() {
+        batches.push(batch);
+    }
+    batches
+}
+
+pub fn process_claim_partner_fees(args: &Args, sub_args: &ClaimPartnerFeesDynamicAmmArgs) {
+    let ClaimPartnerFeesDynamicAmmArgs { pool } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let partner_pools = get_pools_by_partner_authority(&client, &keypair.pubkey()).unwrap();
+    let not_partner_pools = pool
+        .iter()
+        .filter(|pool_arg| {
+            !partner_pools
+                .iter()
+                .any(|(pool_key, _)| *pool_key == pool_arg.pool)
+        })
+        .map(|pool_arg| pool_arg.pool.to_string())
+        .collect::<Vec<String>>();
+    if !not_partner_pools.is_empty() {
+        output::exit_with_error(&format!(
+            "Pools {} are not partner pools of {}",
+            not_partner_pools.join(","),
+            keypair.pubkey()
+        ));
+    }
+
+    let mut pools = vec![];
+    for (pool_key, state) in partner_pools {
+        if !pool.is_empty() && !pool.iter().any(|pool_arg| pool_arg.pool == pool_key) {
+            continue;
+        }
+        if state.partner_info.pending_fee_a == 0 && state.partner_info.pending_fee_b == 0 {
+            if !pool.is_empty() {
+                info!("pool {} has no pending fee", pool_key);
+            }
+            continue;
+        }
+        pools.push((pool_key, state));
+    }
+    if pools.is_empty() {
+        info!("nothing to claim");
+        return;
+    }
+    let decimals = get_pool_token_decimals(&client, &pools).unwrap();
+
+    let mut missing_token_accounts = HashMap::new();
+    let mut claims = vec![];
+    for (pool_key, pool_state) in pools.iter() {
+        let pool_arg = pool.iter().find(|pool_arg| pool_arg.pool == *pool_key);
+        let token_a_decimals = decimals[&pool_state.token_a_mint];
+        let token_b_decimals = decimals[&pool_state.token_b_mint];
+        let parse_max_amount = |max_amount: Option<&String>, decimals: u8| match max_amount {
+            Some(max_amount) => args.parse_amount(max_amount, decimals).unwrap(),
+            None => u64::MAX,
+        };
+        let max_amount_a = parse_max_amount(
+            pool_arg.and_then(|pool_arg| pool_arg.max_amount_a.as_ref()),
+            token_a_decimals,
+        );
+        let max_amount_b = parse_max_amount(
+            pool_arg.and_then(|pool_arg| pool_arg.max_amount_b.as_ref()),
+            token_b_decimals,
+        );
+
+        let missing_token_mints = [pool_state.token_a_mint, pool_state.token_b_mint]
+            .into_iter()
+            .filter(|token_mint| {
+                let token_account = get_associated_token_address(&keypair.pubkey(), token_mint);
+                *missing_token_accounts
+                    .entry(token_account)
+                    .or_insert_with(|| client.get_account(&token_account).is_err())
+            })
+            .collect();
+
+        let partner_info = pool_state.partner_info;
+        info!(
+            "pool {} claim token_a {} token_b {}",
+            pool_key,
+            args.format_amount(
+                partner_info.pending_fee_a.min(max_amount_a),
+                token_a_decimals
+            ),
+            args.format_amount(
+                partner_info.pending_fee_b.min(max_amount_b),
+                token_b_decimals
+            )
+        );
+        output::push(
+            "claims",
+            &PartnerFeeClaimOutput {
+                pool: pool_key.to_string(),
+                token_a: partner_info.pending_fee_a.min(max_amount_a),
+                token_b: partner_info.pending_fee_b.min(max_amount_b),
+            },
+        );
+
+        claims.push(PoolClaim {
+            pool_key: *pool_key,
+            ix: partner_claim_fee(*pool_key, pool_state, max_amount_a, max_amount_b),
+            missing_token_mints,
+        });
+    }
+
+    let mut result = BTreeMap::new();
+    let batches = split_claims(&keypair.pubkey(), args.priority_fee, claims);
+    for (index, batch) in batches.iter().enumerate() {
+        let ixs = get_transaction_ixs(&keypair.pubkey(), args.priority_fee, batch);
+        let blockhash = client.get_latest_blockhash().unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &ixs,
+            Some(&keypair.pubkey()),
+            &[&keypair],
+            blockhash,
+        );
+        let wallet_memo = batch
+            .iter()
+            .map(|claim| claim.pool_key.to_string())
+            .collect::<Vec<String>>()
+            .join(",");
+        let payload = args.to_rpc_args().send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            wallet_memo,
+            sucess_cb,
+            failed_cb,
+        );
+        result.insert(index as u64, payload.ok());
+    }
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(wallet_memo: String, sig: Signature) {
+    info!("done claim partner fees {} {:?}", wallet_memo, sig);
+}
+fn failed_cb(wallet_memo: String) {
+    info!("cannot claim partner fees {}", wallet_memo);
+}
@@ -0,0 +1,101 @@
+use crate::discovery::get_pools_by_config;
+use crate::*;
+use anchor_client::ClientError;
+use dynamic_amm_quote::instruction::close_config;
+use prog_dynamic_amm::state::Config;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CloseConfigDynamicAmmArgs {
+    #[clap(long, env)]
+    pub config: Pubkey,
+    /// Number of pools created from the config, confirmed by the admin. The config is not closed when it doesn't match.
+    #[clap(long, env)]
+    pub expected_pool_count: usize,
+    /// Receiver of the config rent, the keypair when not set
+    #[clap(long, env)]
+    pub rent_receiver: Option<Pubkey>,
+}
+
+/// JSON output of `dynamic-amm close-config`
+#[derive(Serialize, Debug)]
+pub struct CloseConfigOutput {
+    /// Pools created from the config
+    pub pools: Vec<String>,
+}
+
+pub fn process_close_config(args: &Args, sub_args: &CloseConfigDynamicAmmArgs) {
+    let CloseConfigDynamicAmmArgs {
+        config,
+        expected_pool_count,
+        rent_receiver,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let config_state: Config = match program_amm_client.account(*config) {
+        Err(ClientError::AccountNotFound) => {
+            output::exit_with_error(&format!("Config {} not found", config))
+        }
+        result => result.unwrap(),
+    };
+    print_config(config, &config_state);
+
+    let pools = get_pools_by_config(&client, config).unwrap();
+    info!("  pools {}", pools.len());
+    for pool in pools.iter() {
+        info!("    {}", pool);
+    }
+    output::emit(&CloseConfigOutput {
+        pools: pools.iter().map(|pool| pool.to_string()).collect(),
+    });
+    if pools.len() != *expected_pool_count {
+        output::exit_with_error(&format!(
+            "Config is used by {} pools, expected {}. Pass --expected-pool-count {} to close it",
+            pools.len(),
+            expected_pool_count,
+            pools.len()
+        ));
+    }
+
+    let ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        close_config(
+            keypair.pubkey(),
+            *config,
+            rent_receiver.unwrap_or(keypair.pubkey()),
+        ),
+    ];
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let payload = args
+        .to_rpc_args()
+        .send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            "".to_string(),
+            sucess_cb,
+            failed_cb,
+        )
+        .unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done close config {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot close config");
+}
@@ -0,0 +1,110 @@
+use crate::*;
+use dynamic_amm_quote::config::validate_config_parameters;
+use dynamic_amm_quote::instruction::create_config;
+use prog_dynamic_amm::instructions::ConfigParameters;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateConfigDynamicAmmArgs {
+    /// Config index, part of the config address
+    #[clap(long, env)]
+    pub index: u64,
+    /// Trade fee numerator, the fee denominator is 100_000
+    #[clap(long, env)]
+    pub trade_fee_numerator: u64,
+    /// Protocol trade fee numerator, share of the trade fee, the fee denominator is 100_000
+    #[clap(long, env)]
+    pub protocol_trade_fee_numerator: u64,
+    /// Activation duration, in slot or second depending on activation_type
+    #[clap(long, env, default_value = "0")]
+    pub activation_duration: u64,
+    /// 0 slot, 1 timestamp
+    #[clap(long, env, default_value = "0")]
+    pub activation_type: u8,
+    /// Only pool_creator_authority can create pools with the config. Anyone when not set.
+    #[clap(long, env)]
+    pub pool_creator_authority: Option<Pubkey>,
+    /// Partner fee numerator, share of the protocol trade fee, the fee denominator is 100_000
+    #[clap(long, env, default_value = "0")]
+    pub partner_fee_numerator: u64,
+}
+
+/// JSON output of `dynamic-amm create-config`
+#[derive(Serialize, Debug)]
+pub struct CreateConfigOutput {
+    /// Address of the config created
+    pub config: String,
+}
+
+pub fn process_create_config(args: &Args, sub_args: &CreateConfigDynamicAmmArgs) {
+    let CreateConfigDynamicAmmArgs {
+        index,
+        trade_fee_numerator,
+        protocol_trade_fee_numerator,
+        activation_duration,
+        activation_type,
+        pool_creator_authority,
+        partner_fee_numerator,
+    } = sub_args;
+
+    let config_parameters = ConfigParameters {
+        trade_fee_numerator: *trade_fee_numerator,
+        protocol_trade_fee_numerator: *protocol_trade_fee_numerator,
+        activation_duration: *activation_duration,
+        pool_creator_authority: pool_creator_authority.unwrap_or_default(),
+        activation_type: *activation_type,
+        index: *index,
+        partner_fee_numerator: *partner_fee_numerator,
+    };
+    validate_config_parameters(&config_parameters).unwrap();
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let config = derive_config_address(*index);
+    if client.get_account(&config).is_ok() {
+        output::exit_with_error(&format!(
+            "Config {} of index {} already exists",
+            config, index
+        ));
+    }
+    info!("config {}", config);
+    output::emit(&CreateConfigOutput {
+        config: config.to_string(),
+    });
+
+    let ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        create_config(keypair.pubkey(), config_parameters),
+    ];
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let payload = args
+        .to_rpc_args()
+        .send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            "".to_string(),
+            sucess_cb,
+            failed_cb,
+        )
+        .unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done create config {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot create config");
+}
@@ -0,0 +1,85 @@
+use crate::*;
+use dynamic_amm_quote::config::validate_customizable_params;
+use prog_dynamic_amm::instructions::CustomizableParams;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreateCustomizablePoolDynamicAmmArgs {
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_a_amount: String,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_b_amount: String,
+    /// Trade fee numerator, the fee denominator is 100_000
+    #[clap(long, env)]
+    pub trade_fee_numerator: u32,
+    /// Slot, or timestamp, the pool starts trading. Immediately when not set.
+    #[clap(long, env)]
+    pub activation_point: Option<u64>,
+    /// 0 slot, 1 timestamp
+    #[clap(long, env, default_value = "0")]
+    pub activation_type: u8,
+    /// The pool supports an alpha vault
+    #[clap(long)]
+    pub has_alpha_vault: bool,
+}
+
+pub fn process_create_customizable_pool(
+    args: &Args,
+    sub_args: &CreateCustomizablePoolDynamicAmmArgs,
+) {
+    let CreateCustomizablePoolDynamicAmmArgs {
+        token_a_mint,
+        token_b_mint,
+        token_a_amount,
+        token_b_amount,
+        trade_fee_numerator,
+        activation_point,
+        activation_type,
+        has_alpha_vault,
+    } = sub_args;
+
+    let params = CustomizableParams {
+        trade_fee_numerator: *trade_fee_numerator,
+        activation_point: *activation_point,
+        has_alpha_vault: *has_alpha_vault,
+        activation_type: *activation_type,
+        padding: [0u8; 90],
+    };
+    validate_customizable_params(&params).unwrap();
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let states = NewPoolStates::new(&program_amm_client, *token_a_mint, *token_b_mint).unwrap();
+    let (token_a_amount, token_b_amount) =
+        states.parse_amounts(args, token_a_amount, token_b_amount);
+    let new_pool = states.to_new_pool(*token_a_mint, *token_b_mint);
+
+    let pool = derive_customizable_permissionless_constant_product_pool_address(
+        *token_a_mint,
+        *token_b_mint,
+    );
+    info!("pool {}", pool);
+    output::emit(&CreatePoolOutput {
+        pool: pool.to_string(),
+    });
+
+    let mut ixs = get_create_pool_pre_instructions(args, &new_pool, keypair.pubkey());
+    ixs.push(
+        new_pool.initialize_customizable_permissionless_constant_product_pool(
+            keypair.pubkey(),
+            token_a_amount,
+            token_b_amount,
+            params,
+        ),
+    );
+
+    send_create_pool_transaction(args, &ixs, &keypair, &[]);
+}
@@ -0,0 +1,54 @@
+use crate::*;
+use solana_sdk::signer::keypair::{read_keypair_file, Keypair};
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreatePermissionedPoolDynamicAmmArgs {
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    #[clap(flatten)]
+    pub curve: StableCurveArgs,
+    /// Keypair of the pool address. A new keypair is generated when not set.
+    #[clap(long, env)]
+    pub pool_keypair_path: Option<String>,
+}
+
+pub fn process_create_permissioned_pool(
+    args: &Args,
+    sub_args: &CreatePermissionedPoolDynamicAmmArgs,
+) {
+    let CreatePermissionedPoolDynamicAmmArgs {
+        token_a_mint,
+        token_b_mint,
+        curve,
+        pool_keypair_path,
+    } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+    let pool_keypair = match pool_keypair_path {
+        Some(path) => read_keypair_file(path).unwrap(),
+        None => Keypair::new(),
+    };
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let states = NewPoolStates::new(&program_amm_client, *token_a_mint, *token_b_mint).unwrap();
+    let new_pool = states.to_new_pool(*token_a_mint, *token_b_mint);
+    let curve_type = curve
+        .get_curve_type(&program_amm_client, &states, *token_b_mint)
+        .unwrap_or_else(|err| output::exit_with_error(&err.to_string()));
+
+    info!("pool {}", pool_keypair.pubkey());
+    output::emit(&CreatePoolOutput {
+        pool: pool_keypair.pubkey().to_string(),
+    });
+
+    let mut ixs = get_create_pool_pre_instructions(args, &new_pool, keypair.pubkey());
+    let mut ix =
+        new_pool.initialize_permissioned_pool(keypair.pubkey(), pool_keypair.pubkey(), curve_type);
+    curve.append_remaining_accounts(&mut ix);
+    ixs.push(ix);
+
+    send_create_pool_transaction(args, &ixs, &keypair, &[&pool_keypair]);
+}
@@ -0,0 +1,62 @@
+use crate::*;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreatePermissionlessPoolDynamicAmmArgs {
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_a_amount: String,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_b_amount: String,
+    #[clap(flatten)]
+    pub curve: StableCurveArgs,
+}
+
+pub fn process_create_permissionless_pool(
+    args: &Args,
+    sub_args: &CreatePermissionlessPoolDynamicAmmArgs,
+) {
+    let CreatePermissionlessPoolDynamicAmmArgs {
+        token_a_mint,
+        token_b_mint,
+        token_a_amount,
+        token_b_amount,
+        curve,
+    } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let states = NewPoolStates::new(&program_amm_client, *token_a_mint, *token_b_mint).unwrap();
+    let (token_a_amount, token_b_amount) =
+        states.parse_amounts(args, token_a_amount, token_b_amount);
+    let new_pool = states.to_new_pool(*token_a_mint, *token_b_mint);
+    let curve_type = curve
+        .get_curve_type(&program_amm_client, &states, *token_b_mint)
+        .unwrap_or_else(|err| output::exit_with_error(&err.to_string()));
+
+    // initialize_permissionless_pool creates the pool of the default 0.25% trade fee
+    let pool = derive_permissionless_pool_address(curve_type, *token_a_mint, *token_b_mint, 25);
+    info!("pool {}", pool);
+    output::emit(&CreatePoolOutput {
+        pool: pool.to_string(),
+    });
+
+    let mut ixs = get_create_pool_pre_instructions(args, &new_pool, keypair.pubkey());
+    let mut ix = new_pool.initialize_permissionless_pool(
+        keypair.pubkey(),
+        curve_type,
+        token_a_amount,
+        token_b_amount,
+    );
+    curve.append_remaining_accounts(&mut ix);
+    ixs.push(ix);
+
+    send_create_pool_transaction(args, &ixs, &keypair, &[]);
+}
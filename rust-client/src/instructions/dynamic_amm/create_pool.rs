@@ -1,18 +1,21 @@
 use self::fee_estimation::CREATE_POOL_COMPUTE_UNIT;
 use crate::*;
-use anchor_lang::AccountDeserialize;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
-use anchor_spl::associated_token::get_associated_token_address;
+use anchor_client::{ClientError, Program};
+use anchor_spl::token::Mint;
+use clap::ValueEnum;
+use dynamic_amm_quote::curve::stable::{generate_stable_curve_type, LstType};
+use dynamic_amm_quote::instruction::NewPool;
 use prog_dynamic_amm::state::CurveType;
 use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
 use solana_sdk::instruction::Instruction;
-use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::keypair::{read_keypair_file, Keypair};
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
+use std::rc::Rc;
+
 #[derive(Parser, Debug, Clone)]
 pub struct CreateDynamicAmmPoolArgs {
     #[clap(long, env)]
@@ -21,130 +24,189 @@ pub struct CreateDynamicAmmPoolArgs {
     pub token_b_mint: Pubkey,
     #[clap(long, env)]
     pub trade_fee_bps: u64,
+    /// Raw amount, or UI amount with --ui-amount
     #[clap(long, env)]
-    pub token_a_amount: u64,
+    pub token_a_amount: String,
+    /// Raw amount, or UI amount with --ui-amount
     #[clap(long, env)]
-    pub token_b_amount: u64,
+    pub token_b_amount: String,
+    #[clap(flatten)]
+    pub curve: StableCurveArgs,
 }
 
-pub fn process_new_dynamic_pool(args: &Args, sub_args: &CreateDynamicAmmPoolArgs) {
-    let CreateDynamicAmmPoolArgs {
-        token_a_mint,
-        token_b_mint,
-        trade_fee_bps,
-        token_a_amount,
-        token_b_amount,
-    } = sub_args;
+/// Staking program of the liquid staking token of a depeg pool
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DepegTypeArg {
+    None,
+    Marinade,
+    Lido,
+    SplStake,
+}
 
-    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
-    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+#[derive(Parser, Debug, Clone)]
+pub struct StableCurveArgs {
+    /// Create a stable pool. Constant product pool when not set.
+    #[clap(long)]
+    pub stable: bool,
+    /// Amplification coefficient of the stable pool
+    #[clap(long, env, default_value = "100")]
+    pub amp: u64,
+    /// Depeg type of the stable pool, token B being the liquid staking token of the staking program
+    #[clap(long, env, value_enum, default_value = "none")]
+    pub depeg_type: DepegTypeArg,
+    /// SPL stake pool of token B, with --depeg-type spl-stake
+    #[clap(long, env, required_if_eq("depeg-type", "spl-stake"))]
+    pub stake_pool: Option<Pubkey>,
+}
 
-    let mut ixs = vec![
-        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
-        ComputeBudgetInstruction::set_compute_unit_limit(CREATE_POOL_COMPUTE_UNIT),
-    ];
-    // check vault
-    let a_vault = derive_vault_address(token_a_mint);
-    let a_token_vault = derive_token_vault_address(&a_vault);
-    let a_vault_lp_mint = derive_vault_lp_mint_address(&a_vault);
-    if client.get_account(&a_vault).is_err() {
-        ixs.push(Instruction {
-            program_id: prog_dynamic_vault::ID,
-            accounts: prog_dynamic_vault::accounts::Initialize {
-                vault: a_vault,
-                token_vault: a_token_vault,
-                token_mint: *token_a_mint,
-                token_program: spl_token::ID,
-                lp_mint: a_vault_lp_mint,
-                rent: anchor_client::solana_sdk::sysvar::rent::ID,
-                system_program: solana_program::system_program::ID,
-                payer: keypair.pubkey(),
-            }
-            .to_account_metas(None),
-            data: prog_dynamic_vault::instruction::Initialize {}.data(),
-        });
+impl StableCurveArgs {
+    pub fn get_lst_type(&self) -> Option<LstType> {
+        match self.depeg_type {
+            DepegTypeArg::None => None,
+            DepegTypeArg::Marinade => Some(LstType::Marinade),
+            DepegTypeArg::Lido => Some(LstType::Lido),
+            DepegTypeArg::SplStake => self
+                .stake_pool
+                .map(|stake_pool| LstType::SplStake { stake_pool }),
+        }
     }
 
-    let b_vault = derive_vault_address(token_b_mint);
-    let b_token_vault = derive_token_vault_address(&b_vault);
-    let mut b_vault_lp_mint = derive_vault_lp_mint_address(&b_vault);
-    match client.get_account(&b_vault) {
-        Ok(account) => {
-            let mut buff = account.data.as_slice();
-            let vault_state = Vault::try_deserialize_unchecked(&mut buff).unwrap();
-            b_vault_lp_mint = vault_state.lp_mint;
+    /// Curve type of the new pool. For a depeg pool, token B must be the liquid staking token of the depeg type.
+    pub fn get_curve_type(
+        &self,
+        program_client: &Program<Rc<Keypair>>,
+        states: &NewPoolStates,
+        token_b_mint: Pubkey,
+    ) -> anyhow::Result<CurveType> {
+        if !self.stable {
+            return Ok(CurveType::ConstantProduct);
         }
-        Err(_) => {
-            ixs.push(Instruction {
-                program_id: prog_dynamic_vault::ID,
-                accounts: prog_dynamic_vault::accounts::Initialize {
-                    vault: b_vault,
-                    token_vault: b_token_vault,
-                    token_mint: *token_b_mint,
-                    token_program: spl_token::ID,
-                    lp_mint: b_vault_lp_mint,
-                    rent: anchor_client::solana_sdk::sysvar::rent::ID,
-                    system_program: solana_program::system_program::ID,
-                    payer: keypair.pubkey(),
+        let lst_type = self.get_lst_type();
+        if let Some(lst_type) = lst_type {
+            let stake_account_data = match lst_type {
+                LstType::SplStake { stake_pool } => {
+                    program_client.rpc().get_account_data(&stake_pool)?
                 }
-                .to_account_metas(None),
-                data: prog_dynamic_vault::instruction::Initialize {}.data(),
-            });
+                _ => vec![],
+            };
+            lst_type.verify_lst_mint(token_b_mint, &stake_account_data)?;
         }
+        generate_stable_curve_type(
+            self.amp,
+            states.token_a_mint.decimals,
+            states.token_b_mint.decimals,
+            lst_type,
+        )
     }
 
-    let pool = derive_pool_address(*token_a_mint, *token_b_mint, *trade_fee_bps);
-    let pool_lp_mint = derive_pool_lp_mint_address(pool);
-    let (mint_metadata, _bump) = mpl_token_metadata::accounts::Metadata::find_pda(&pool_lp_mint);
-    ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::InitializePermissionlessPoolWithFeeTier {
-            pool,
-            rent: anchor_client::solana_sdk::sysvar::rent::ID,
-            system_program: solana_program::system_program::ID,
-            payer: keypair.pubkey(),
-            a_vault,
-            b_vault,
-            a_token_vault,
-            b_token_vault,
-            a_vault_lp_mint,
-            b_vault_lp_mint,
-            lp_mint: pool_lp_mint,
-            token_a_mint: *token_a_mint,
-            token_b_mint: *token_b_mint,
-            token_program: spl_token::ID,
-            associated_token_program: spl_associated_token_account::ID,
-            a_vault_lp: derive_vault_lp_token_address(a_vault, pool),
-            b_vault_lp: derive_vault_lp_token_address(b_vault, pool),
-            payer_token_a: get_associated_token_address(&keypair.pubkey(), token_a_mint),
-            payer_token_b: get_associated_token_address(&keypair.pubkey(), token_b_mint),
-            payer_pool_lp: get_associated_token_address(&keypair.pubkey(), &pool_lp_mint),
-            protocol_token_a_fee: derive_pool_fee_token_address(*token_a_mint, pool),
-            protocol_token_b_fee: derive_pool_fee_token_address(*token_b_mint, pool),
-            fee_owner: Pubkey::default(),
-            vault_program: prog_dynamic_vault::ID,
-            metadata_program: mpl_token_metadata::ID,
-            mint_metadata,
+    /// Append the stake account of the liquid staking token to the pool initialization of a depeg pool
+    pub fn append_remaining_accounts(&self, ix: &mut Instruction) {
+        if let (true, Some(lst_type)) = (self.stable, self.get_lst_type()) {
+            ix.accounts.extend(lst_type.get_remaining_accounts());
         }
-        .to_account_metas(None),
-        data: prog_dynamic_amm::instruction::InitializePermissionlessPoolWithFeeTier {
-            curve_type: CurveType::ConstantProduct,
-            trade_fee_bps: *trade_fee_bps,
-            token_a_amount: *token_a_amount,
-            token_b_amount: *token_b_amount,
+    }
+}
+
+/// Vault state of the token. None when the vault is not initialized yet, other errors, eg: RPC failures, are returned.
+fn get_vault(
+    program_client: &Program<Rc<Keypair>>,
+    token_mint: &Pubkey,
+) -> anyhow::Result<Option<Vault>> {
+    match program_client.account(derive_vault_address(token_mint)) {
+        Ok(vault) => Ok(Some(vault)),
+        Err(ClientError::AccountNotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Mint and vault states of a pool to be created. The vault is None when not initialized yet.
+pub struct NewPoolStates {
+    pub token_a_mint: Mint,
+    pub token_b_mint: Mint,
+    pub a_vault: Option<Vault>,
+    pub b_vault: Option<Vault>,
+}
+
+impl NewPoolStates {
+    pub fn new(
+        program_client: &Program<Rc<Keypair>>,
+        token_a_mint: Pubkey,
+        token_b_mint: Pubkey,
+    ) -> anyhow::Result<Self> {
+        Ok(NewPoolStates {
+            token_a_mint: program_client.account(token_a_mint)?,
+            token_b_mint: program_client.account(token_b_mint)?,
+            a_vault: get_vault(program_client, &token_a_mint)?,
+            b_vault: get_vault(program_client, &token_b_mint)?,
+        })
+    }
+
+    pub fn to_new_pool(&self, token_a_mint: Pubkey, token_b_mint: Pubkey) -> NewPool<'_> {
+        NewPool {
+            token_a_mint,
+            token_b_mint,
+            a_vault: self.a_vault.as_ref(),
+            b_vault: self.b_vault.as_ref(),
         }
-        .data(),
-    });
+    }
+
+    /// Parse the initial liquidity of the pool in raw amounts
+    pub fn parse_amounts(
+        &self,
+        args: &Args,
+        token_a_amount: &str,
+        token_b_amount: &str,
+    ) -> (u64, u64) {
+        (
+            args.parse_amount(token_a_amount, self.token_a_mint.decimals)
+                .unwrap(),
+            args.parse_amount(token_b_amount, self.token_b_mint.decimals)
+                .unwrap(),
+        )
+    }
+}
+
+/// JSON output of the pool creation commands
+#[derive(Serialize, Debug)]
+pub struct CreatePoolOutput {
+    /// Address of the pool created
+    pub pool: String,
+}
+
+/// Compute budget instructions, followed by the initialization of the missing vaults
+pub fn get_create_pool_pre_instructions(
+    args: &Args,
+    new_pool: &NewPool,
+    payer: Pubkey,
+) -> Vec<Instruction> {
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(CREATE_POOL_COMPUTE_UNIT),
+    ];
+    ixs.extend(new_pool.initialize_missing_vaults(payer));
+    ixs
+}
+
+/// Send the pool creation transaction, signed by the payer and the additional signers
+pub fn send_create_pool_transaction(
+    args: &Args,
+    ixs: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) {
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let mut all_signers = vec![payer];
+    all_signers.extend(signers);
 
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
-        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+        Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &all_signers, blockhash);
     let payload = args
         .to_rpc_args()
         .send_transaction_wrapper(
             &tx,
             MAX_RETRIES,
-            keypair.pubkey(),
+            payer.pubkey(),
             "".to_string(),
             sucess_cb,
             failed_cb,
@@ -155,9 +217,56 @@ pub fn process_new_dynamic_pool(args: &Args, sub_args: &CreateDynamicAmmPoolArgs
     handle_collect_cb_by_tx_action(args.tx_action, None, &result);
 }
 
+pub fn process_new_dynamic_pool(args: &Args, sub_args: &CreateDynamicAmmPoolArgs) {
+    let CreateDynamicAmmPoolArgs {
+        token_a_mint,
+        token_b_mint,
+        trade_fee_bps,
+        token_a_amount,
+        token_b_amount,
+        curve,
+    } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let states = NewPoolStates::new(&program_amm_client, *token_a_mint, *token_b_mint).unwrap();
+    let (token_a_amount, token_b_amount) =
+        states.parse_amounts(args, token_a_amount, token_b_amount);
+    let new_pool = states.to_new_pool(*token_a_mint, *token_b_mint);
+
+    let curve_type = curve
+        .get_curve_type(&program_amm_client, &states, *token_b_mint)
+        .unwrap_or_else(|err| output::exit_with_error(&err.to_string()));
+
+    let pool = derive_permissionless_pool_address(
+        curve_type,
+        *token_a_mint,
+        *token_b_mint,
+        *trade_fee_bps,
+    );
+    info!("pool {}", pool);
+    output::emit(&CreatePoolOutput {
+        pool: pool.to_string(),
+    });
+
+    let mut ixs = get_create_pool_pre_instructions(args, &new_pool, keypair.pubkey());
+    let mut ix = new_pool.initialize_permissionless_pool_with_fee_tier(
+        keypair.pubkey(),
+        curve_type,
+        *trade_fee_bps,
+        token_a_amount,
+        token_b_amount,
+    );
+    curve.append_remaining_accounts(&mut ix);
+    ixs.push(ix);
+
+    send_create_pool_transaction(args, &ixs, &keypair, &[]);
+}
+
 fn sucess_cb(_wallet_memo: String, sig: Signature) {
-    println!("done create pool {:?}", sig);
+    info!("done create pool {:?}", sig);
 }
 fn failed_cb(_wallet_memo: String) {
-    println!("cannot create pool");
+    info!("cannot create pool");
 }
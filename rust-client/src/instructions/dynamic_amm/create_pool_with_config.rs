@@ -0,0 +1,78 @@
+use crate::*;
+use prog_dynamic_amm::state::Config;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct CreatePoolWithConfigDynamicAmmArgs {
+    #[clap(long, env)]
+    pub config: Pubkey,
+    #[clap(long, env)]
+    pub token_a_mint: Pubkey,
+    #[clap(long, env)]
+    pub token_b_mint: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_a_amount: String,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_b_amount: String,
+    /// Slot, or timestamp, the pool starts trading, in the activation type of the config.
+    /// When not set, the pool activation is derived from the config activation duration.
+    #[clap(long, env)]
+    pub activation_point: Option<u64>,
+}
+
+pub fn process_create_pool_with_config(args: &Args, sub_args: &CreatePoolWithConfigDynamicAmmArgs) {
+    let CreatePoolWithConfigDynamicAmmArgs {
+        config,
+        token_a_mint,
+        token_b_mint,
+        token_a_amount,
+        token_b_amount,
+        activation_point,
+    } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let config_state: Config = program_amm_client.account(*config).unwrap();
+    if config_state.pool_creator_authority != Pubkey::default()
+        && config_state.pool_creator_authority != keypair.pubkey()
+    {
+        output::exit_with_error(&format!(
+            "Only {} can create pools with config {}",
+            config_state.pool_creator_authority, config
+        ));
+    }
+
+    let states = NewPoolStates::new(&program_amm_client, *token_a_mint, *token_b_mint).unwrap();
+    let (token_a_amount, token_b_amount) =
+        states.parse_amounts(args, token_a_amount, token_b_amount);
+    let new_pool = states.to_new_pool(*token_a_mint, *token_b_mint);
+
+    let pool = derive_constant_product_pool_with_config(*token_a_mint, *token_b_mint, *config);
+    info!("pool {}", pool);
+    output::emit(&CreatePoolOutput {
+        pool: pool.to_string(),
+    });
+
+    let mut ixs = get_create_pool_pre_instructions(args, &new_pool, keypair.pubkey());
+    ixs.push(match activation_point {
+        Some(_) => new_pool.initialize_permissionless_constant_product_pool_with_config2(
+            keypair.pubkey(),
+            *config,
+            token_a_amount,
+            token_b_amount,
+            *activation_point,
+        ),
+        None => new_pool.initialize_permissionless_constant_product_pool_with_config(
+            keypair.pubkey(),
+            *config,
+            token_a_amount,
+            token_b_amount,
+        ),
+    });
+
+    send_create_pool_transaction(args, &ixs, &keypair, &[]);
+}
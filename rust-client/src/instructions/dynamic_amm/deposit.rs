@@ -1,15 +1,14 @@
 use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
 use crate::*;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
 use anchor_spl::associated_token::get_associated_token_address;
 use anchor_spl::token::Mint;
+use dynamic_amm_quote::diagnostic::diagnose_pool_error;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
-use solana_sdk::instruction::Instruction;
 use solana_sdk::signer::keypair::read_keypair_file;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
@@ -17,10 +16,12 @@ use solana_sdk::transaction::Transaction;
 pub struct DepositDynamicAmmArgs {
     #[clap(long, env)]
     pub pool: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
     #[clap(long, env)]
-    pub max_token_a_amount: u64,
+    pub max_token_a_amount: String,
+    /// Raw amount, or UI amount with --ui-amount
     #[clap(long, env)]
-    pub max_token_b_amount: u64,
+    pub max_token_b_amount: String,
     #[clap(long, env)]
     pub slippage_rate: u64,
 }
@@ -44,6 +45,15 @@ pub fn process_deposit_dynamic_pool(args: &Args, sub_args: &DepositDynamicAmmArg
     let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
     let pool_state: Pool = program_amm_client.account(*pool).unwrap();
 
+    let token_a_mint: Mint = program_amm_client.account(pool_state.token_a_mint).unwrap();
+    let token_b_mint: Mint = program_amm_client.account(pool_state.token_b_mint).unwrap();
+    let max_token_a_amount = args
+        .parse_amount(max_token_a_amount, token_a_mint.decimals)
+        .unwrap();
+    let max_token_b_amount = args
+        .parse_amount(max_token_b_amount, token_b_mint.decimals)
+        .unwrap();
+
     let user_pool_lp = get_associated_token_address(&keypair.pubkey(), &pool_state.lp_mint);
     if client.get_account(&user_pool_lp).is_err() {
         ixs.push(
@@ -65,66 +75,81 @@ pub fn process_deposit_dynamic_pool(args: &Args, sub_args: &DepositDynamicAmmArg
         &pool_state,
         &a_vault_state,
         &b_vault_state,
-        (*max_token_a_amount).into(),
-        (*max_token_b_amount).into(),
+        max_token_a_amount.into(),
+        max_token_b_amount.into(),
         (*slippage_rate).into(),
         &keypair,
     )
     .unwrap();
 
-    ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::AddOrRemoveBalanceLiquidity {
-            pool: *pool,
-            lp_mint: pool_state.lp_mint,
-            user_pool_lp,
-            a_vault_lp: pool_state.a_vault_lp,
-            b_vault_lp: pool_state.b_vault_lp,
-            a_vault: pool_state.a_vault,
-            b_vault: pool_state.b_vault,
-            a_vault_lp_mint: a_vault_state.lp_mint,
-            b_vault_lp_mint: b_vault_state.lp_mint,
-            a_token_vault: a_vault_state.token_vault,
-            b_token_vault: b_vault_state.token_vault,
-            user_a_token: get_associated_token_address(&keypair.pubkey(), &pool_state.token_a_mint),
-            user_b_token: get_associated_token_address(&keypair.pubkey(), &pool_state.token_b_mint),
-            user: keypair.pubkey(),
-            vault_program: prog_dynamic_vault::ID,
-            token_program: spl_token::ID,
-        }
-        .to_account_metas(None),
-        data: prog_dynamic_amm::instruction::AddBalanceLiquidity {
-            pool_token_amount,
-            maximum_token_a_amount: *max_token_a_amount,
-            maximum_token_b_amount: *max_token_b_amount,
-        }
-        .data(),
-    });
+    ixs.extend(
+        transaction_utils::get_wrap_sol_ixs(
+            &client,
+            keypair.pubkey(),
+            pool_state.token_a_mint,
+            max_token_a_amount,
+        )
+        .unwrap(),
+    );
+    ixs.extend(
+        transaction_utils::get_wrap_sol_ixs(
+            &client,
+            keypair.pubkey(),
+            pool_state.token_b_mint,
+            max_token_b_amount,
+        )
+        .unwrap(),
+    );
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &pool_state,
+        a_vault: &a_vault_state,
+        b_vault: &b_vault_state,
+    };
+    ixs.push(builder.add_balance_liquidity(
+        keypair.pubkey(),
+        pool_token_amount,
+        max_token_a_amount,
+        max_token_b_amount,
+    ));
+    // Unwrap the wSOL left over by the deposit, when the wSOL account is created by it
+    ixs.extend(
+        transaction_utils::get_unwrap_sol_ix(
+            &client,
+            keypair.pubkey(),
+            &[pool_state.token_a_mint, pool_state.token_b_mint],
+        )
+        .unwrap(),
+    );
 
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
         Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
-    let payload = args
-        .to_rpc_args()
-        .send_transaction_wrapper(
-            &tx,
-            MAX_RETRIES,
-            keypair.pubkey(),
-            "".to_string(),
-            sucess_cb,
-            failed_cb,
-        )
-        .unwrap();
+    let result = args.to_rpc_args().send_transaction_wrapper(
+        &tx,
+        MAX_RETRIES,
+        keypair.pubkey(),
+        "".to_string(),
+        sucess_cb,
+        failed_cb,
+    );
+    if let Some(pool_error) = transaction_utils::get_transaction_pool_error(args, &tx, &result) {
+        transaction_utils::print_pool_error_diagnostic(args, pool, pool_error, |quote_data| {
+            diagnose_pool_error(pool_error, &quote_data.pool, &quote_data.clock)
+        });
+    }
+    let payload = result.unwrap();
     let mut result = BTreeMap::new();
     result.insert(0, Some(payload));
     handle_collect_cb_by_tx_action(args.tx_action, None, &result);
 }
 
 fn sucess_cb(_wallet_memo: String, sig: Signature) {
-    println!("done deposit {:?}", sig);
+    info!("done deposit {:?}", sig);
 }
 fn failed_cb(_wallet_memo: String) {
-    println!("cannot deposit");
+    info!("cannot deposit");
 }
 
 fn get_pool_token_amount(
@@ -143,21 +168,13 @@ fn get_pool_token_amount(
     let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
     let blockhash = client.get_latest_blockhash()?;
     let tx = Transaction::new_signed_with_payer(
-        &vec![Instruction {
-            program_id: prog_dynamic_amm::ID,
-            accounts: prog_dynamic_amm::accounts::GetPoolInfo {
-                a_vault: pool_state.a_vault,
-                a_vault_lp: pool_state.a_vault_lp,
-                a_vault_lp_mint: a_vault_state.lp_mint,
-                b_vault: pool_state.b_vault,
-                b_vault_lp: pool_state.b_vault_lp,
-                b_vault_lp_mint: b_vault_state.lp_mint,
-                lp_mint: pool_state.lp_mint,
-                pool: *pool,
-            }
-            .to_account_metas(None),
-            data: prog_dynamic_amm::instruction::GetPoolInfo {}.data(),
-        }],
+        &[PoolInstructionBuilder {
+            pool_key: *pool,
+            pool: pool_state,
+            a_vault: a_vault_state,
+            b_vault: b_vault_state,
+        }
+        .get_pool_info()],
         Some(&keypair.pubkey()),
         &[keypair],
         blockhash,
@@ -0,0 +1,156 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::diagnostic::diagnose_pool_error;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use dynamic_amm_quote::slippage::get_minimum_amount;
+use prog_dynamic_amm::state::{CurveType, Pool};
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct DepositImbalanceDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount. Can be 0.
+    #[clap(long, env)]
+    pub token_a_amount: String,
+    /// Raw amount, or UI amount with --ui-amount. Can be 0.
+    #[clap(long, env)]
+    pub token_b_amount: String,
+    /// Slippage applied to the quoted pool lp amount, in bps
+    #[clap(long, env, default_value = "50")]
+    pub slippage_bps: u64,
+}
+
+/// JSON output of `dynamic-amm deposit-imbalance`. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct DepositImbalanceOutput {
+    /// Pool lp amount quoted for the deposit
+    pub pool_token_amount: u64,
+    /// Minimum pool lp amount of the deposit
+    pub minimum_pool_token_amount: u64,
+}
+
+pub fn process_deposit_imbalance(args: &Args, sub_args: &DepositImbalanceDynamicAmmArgs) {
+    let DepositImbalanceDynamicAmmArgs {
+        pool,
+        token_a_amount,
+        token_b_amount,
+        slippage_bps,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let pool_state: Pool = program_amm_client.account(*pool).unwrap();
+    if let CurveType::ConstantProduct = pool_state.curve_type {
+        output::exit_with_error("Imbalance deposit is only supported by stable pools");
+    }
+
+    let pool_lp_mint: Mint = program_amm_client.account(pool_state.lp_mint).unwrap();
+    let token_a_mint: Mint = program_amm_client.account(pool_state.token_a_mint).unwrap();
+    let token_b_mint: Mint = program_amm_client.account(pool_state.token_b_mint).unwrap();
+    let token_a_amount = args
+        .parse_amount(token_a_amount, token_a_mint.decimals)
+        .unwrap();
+    let token_b_amount = args
+        .parse_amount(token_b_amount, token_b_mint.decimals)
+        .unwrap();
+
+    let a_vault_state: Vault = program_amm_client.account(pool_state.a_vault).unwrap();
+    let b_vault_state: Vault = program_amm_client.account(pool_state.b_vault).unwrap();
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &pool_state,
+        a_vault: &a_vault_state,
+        b_vault: &b_vault_state,
+    };
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+    // the program transfers from both token accounts, and mints to the pool lp account
+    for token_mint in [
+        pool_state.token_a_mint,
+        pool_state.token_b_mint,
+        pool_state.lp_mint,
+    ] {
+        let token_account = get_associated_token_address(&keypair.pubkey(), &token_mint);
+        if client.get_account(&token_account).is_err() {
+            ixs.push(
+                spl_associated_token_account::instruction::create_associated_token_account(
+                    &keypair.pubkey(),
+                    &keypair.pubkey(),
+                    &token_mint,
+                    &spl_token::ID,
+                ),
+            );
+        }
+    }
+
+    // quote by simulating the deposit without minimum pool lp amount
+    let mut quote_ixs = ixs.clone();
+    quote_ixs.push(builder.add_imbalance_liquidity(
+        keypair.pubkey(),
+        0,
+        token_a_amount,
+        token_b_amount,
+    ));
+    let quote: prog_dynamic_amm::event::AddLiquidity =
+        transaction_utils::simulate_pool_event(args, keypair.pubkey(), &quote_ixs).unwrap();
+    let minimum_pool_token_amount =
+        get_minimum_amount(quote.lp_mint_amount, *slippage_bps).unwrap();
+    info!(
+        "expected pool lp amount {}, minimum pool lp amount {}",
+        args.format_amount(quote.lp_mint_amount, pool_lp_mint.decimals),
+        args.format_amount(minimum_pool_token_amount, pool_lp_mint.decimals)
+    );
+    output::emit(&DepositImbalanceOutput {
+        pool_token_amount: quote.lp_mint_amount,
+        minimum_pool_token_amount,
+    });
+
+    ixs.push(builder.add_imbalance_liquidity(
+        keypair.pubkey(),
+        minimum_pool_token_amount,
+        token_a_amount,
+        token_b_amount,
+    ));
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let result = args.to_rpc_args().send_transaction_wrapper(
+        &tx,
+        MAX_RETRIES,
+        keypair.pubkey(),
+        "".to_string(),
+        sucess_cb,
+        failed_cb,
+    );
+    if let Some(pool_error) = transaction_utils::get_transaction_pool_error(args, &tx, &result) {
+        transaction_utils::print_pool_error_diagnostic(args, pool, pool_error, |quote_data| {
+            diagnose_pool_error(pool_error, &quote_data.pool, &quote_data.clock)
+        });
+    }
+    let payload = result.unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done deposit imbalance {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot deposit imbalance");
+}
@@ -0,0 +1,36 @@
+use crate::*;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct EnableOrDisablePoolDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Disable the pool. The pool is enabled when not set.
+    #[clap(long)]
+    pub disable: bool,
+}
+
+pub fn process_enable_or_disable_pool(args: &Args, sub_args: &EnableOrDisablePoolDynamicAmmArgs) {
+    let EnableOrDisablePoolDynamicAmmArgs { pool, disable } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+    let quote_data = get_quote_data(args, pool).unwrap();
+
+    let enable = !disable;
+    if quote_data.pool.enabled == enable {
+        output::exit_with_error(&format!("Pool enabled is already {}", enable));
+    }
+    let mut expected = quote_data.pool.clone();
+    expected.enabled = enable;
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &quote_data.pool,
+        a_vault: &quote_data.vault_a,
+        b_vault: &quote_data.vault_b,
+    };
+    let ix = builder.enable_or_disable_pool(keypair.pubkey(), enable);
+    send_pool_admin_instruction(args, pool, &quote_data.pool, &expected, ix, &keypair);
+}
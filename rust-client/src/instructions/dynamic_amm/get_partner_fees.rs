@@ -0,0 +1,120 @@
+use crate::discovery::{get_multiple_accounts_chunked, get_pools_by_partner_authority};
+use crate::*;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::ui_amount::format_ui_amount;
+use prog_dynamic_amm::state::Pool;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use std::collections::HashMap;
+
+#[derive(Parser, Debug, Clone)]
+pub struct GetPartnerFeesDynamicAmmArgs {
+    /// Partner authority, the keypair when not set
+    #[clap(long, env)]
+    pub partner_authority: Option<Pubkey>,
+}
+
+/// Partner authority given on the command line, or the keypair
+pub fn get_partner_authority(args: &Args, partner_authority: &Option<Pubkey>) -> Pubkey {
+    match partner_authority {
+        Some(partner_authority) => *partner_authority,
+        None => read_keypair_file(&args.keypair_path.clone().unwrap())
+            .unwrap()
+            .pubkey(),
+    }
+}
+
+/// Mint decimals of the pool tokens
+pub fn get_pool_token_decimals(
+    client: &RpcClient,
+    pools: &[(Pubkey, Pool)],
+) -> anyhow::Result<HashMap<Pubkey, u8>> {
+    let mut mints = pools
+        .iter()
+        .flat_map(|(_, pool)| [pool.token_a_mint, pool.token_b_mint])
+        .collect::<Vec<Pubkey>>();
+    mints.sort();
+    mints.dedup();
+
+    Ok(get_multiple_accounts_chunked::<Mint>(client, &mints)?
+        .into_iter()
+        .map(|(key, mint)| (key, mint.decimals))
+        .collect())
+}
+
+/// JSON output of `dynamic-amm get-partner-fees`
+#[derive(Serialize, Debug)]
+pub struct PartnerFeesOutput {
+    pub partner_authority: String,
+    /// Pools of the partner authority
+    pub pools: Vec<PartnerFeePoolOutput>,
+}
+
+/// Partner fee of a pool. Raw amounts, with their UI amounts
+#[derive(Serialize, Debug)]
+pub struct PartnerFeePoolOutput {
+    pub address: String,
+    pub token_a_mint: String,
+    pub token_b_mint: String,
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+    pub partner_fee_numerator: u64,
+    pub pending_fee_a: u64,
+    pub pending_fee_b: u64,
+    pub pending_fee_a_ui: String,
+    pub pending_fee_b_ui: String,
+}
+
+pub fn process_get_partner_fees(args: &Args, sub_args: &GetPartnerFeesDynamicAmmArgs) {
+    let GetPartnerFeesDynamicAmmArgs { partner_authority } = sub_args;
+
+    let partner_authority = get_partner_authority(args, partner_authority);
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+
+    let pools = get_pools_by_partner_authority(&client, &partner_authority).unwrap();
+    let decimals = get_pool_token_decimals(&client, &pools).unwrap();
+
+    info!(
+        "Found {} pools for partner authority {}",
+        pools.len(),
+        partner_authority
+    );
+    let mut pool_outputs = vec![];
+    for (pool_key, pool) in pools.iter() {
+        let partner_info = pool.partner_info;
+        let token_a_decimals = decimals[&pool.token_a_mint];
+        let token_b_decimals = decimals[&pool.token_b_mint];
+        info!("pool {}", pool_key);
+        info!("  partner_fee_numerator {}", partner_info.fee_numerator);
+        info!(
+            "  pending_fee_a {} ({} {})",
+            partner_info.pending_fee_a,
+            format_ui_amount(partner_info.pending_fee_a, token_a_decimals),
+            pool.token_a_mint
+        );
+        info!(
+            "  pending_fee_b {} ({} {})",
+            partner_info.pending_fee_b,
+            format_ui_amount(partner_info.pending_fee_b, token_b_decimals),
+            pool.token_b_mint
+        );
+        pool_outputs.push(PartnerFeePoolOutput {
+            address: pool_key.to_string(),
+            token_a_mint: pool.token_a_mint.to_string(),
+            token_b_mint: pool.token_b_mint.to_string(),
+            token_a_decimals,
+            token_b_decimals,
+            partner_fee_numerator: partner_info.fee_numerator,
+            pending_fee_a: partner_info.pending_fee_a,
+            pending_fee_b: partner_info.pending_fee_b,
+            pending_fee_a_ui: format_ui_amount(partner_info.pending_fee_a, token_a_decimals),
+            pending_fee_b_ui: format_ui_amount(partner_info.pending_fee_b, token_b_decimals),
+        });
+    }
+    output::emit(&PartnerFeesOutput {
+        partner_authority: partner_authority.to_string(),
+        pools: pool_outputs,
+    });
+}
@@ -0,0 +1,66 @@
+use crate::dynamic_vault::append_snapshot;
+use crate::*;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::apy::{estimate_pool_apy, PoolApy, PoolSnapshot};
+use std::path::PathBuf;
+#[derive(Parser, Debug, Clone)]
+pub struct PoolApyDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Snapshot history of the pool. The current snapshot is appended to it, and the APY is estimated from all the snapshots,
+    /// so run the command periodically, eg: by cron, to build the history.
+    #[clap(long, env)]
+    pub snapshot_path: PathBuf,
+}
+
+/// JSON output of `dynamic-amm get-pool-apy`
+#[derive(Serialize, Debug)]
+pub struct PoolApyOutput {
+    pub pool: String,
+    /// Number of snapshots in the history, including the current one
+    pub snapshot_count: usize,
+    /// Null until there are enough snapshots to estimate it
+    pub apy: Option<PoolApy>,
+}
+
+pub fn process_get_pool_apy(args: &Args, sub_args: &PoolApyDynamicAmmArgs) {
+    let PoolApyDynamicAmmArgs {
+        pool,
+        snapshot_path,
+    } = sub_args;
+
+    let program_dynamic_amm = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+
+    let quote_data = get_quote_data(args, pool).unwrap();
+    let pool_lp_mint: Mint = program_dynamic_amm
+        .account(quote_data.pool.lp_mint)
+        .unwrap();
+
+    let snapshot = PoolSnapshot::from_quote_data(&quote_data, pool_lp_mint.supply).unwrap();
+    let snapshots = append_snapshot(snapshot_path, snapshot).unwrap();
+
+    info!("pool {} snapshots {}", pool, snapshots.len());
+
+    let apy = estimate_pool_apy(&snapshots);
+    match apy {
+        Some(apy) => {
+            info!(
+                "vault_a_apy {:.4}% vault_b_apy {:.4}%",
+                apy.vault_a_apy * 100.0,
+                apy.vault_b_apy * 100.0
+            );
+            info!(
+                "lending_apy {:.4}% trading_fee_apy {:.4}% total_apy {:.4}%",
+                apy.lending_apy * 100.0,
+                apy.trading_fee_apy * 100.0,
+                apy.total_apy * 100.0
+            );
+        }
+        None => info!("Not enough snapshots, run the command again later to estimate the APY"),
+    }
+    output::emit(&PoolApyOutput {
+        pool: pool.to_string(),
+        snapshot_count: snapshots.len(),
+        apy,
+    });
+}
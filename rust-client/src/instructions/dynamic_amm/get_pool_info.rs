@@ -1,9 +1,11 @@
+use crate::discovery::get_configs;
 use crate::*;
 use anchor_lang::AccountDeserialize;
 use anchor_spl::token::Mint;
 use anchor_spl::token::TokenAccount;
 use anyhow::Context;
 use bincode::deserialize;
+use dynamic_amm_quote::view::{AddressMismatchView, PoolOriginView, PoolView};
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_sdk::sysvar::clock;
@@ -14,6 +16,20 @@ pub struct PoolInfoDynamicAmmArgs {
     pub pool: Pubkey,
 }
 
+/// JSON output of `dynamic-amm get-pool-info`
+#[derive(Serialize, Debug)]
+pub struct PoolInfoOutput {
+    pub pool: PoolView,
+    /// Raw token A amount of the pool
+    pub token_a_amount: u64,
+    /// Raw token B amount of the pool
+    pub token_b_amount: u64,
+    /// How the pool was created, derived from its address
+    pub origin: PoolOriginView,
+    /// Pool accounts not matching their seeds, empty for a sound pool
+    pub address_mismatches: Vec<AddressMismatchView>,
+}
+
 pub fn process_get_dynamic_pool_info(args: &Args, sub_args: &PoolInfoDynamicAmmArgs) {
     let PoolInfoDynamicAmmArgs { pool } = sub_args;
 
@@ -35,6 +51,8 @@ pub fn process_get_dynamic_pool_info(args: &Args, sub_args: &PoolInfoDynamicAmmA
             vault_a.lp_mint,
             vault_b.lp_mint,
             clock::id(),
+            pool_state.token_a_mint,
+            pool_state.token_b_mint,
         ])
         .unwrap();
 
@@ -58,6 +76,12 @@ pub fn process_get_dynamic_pool_info(args: &Args, sub_args: &PoolInfoDynamicAmmA
     let data = accounts[4].as_slice();
     let clock = deserialize::<Clock>(&data).unwrap();
 
+    let mut data = accounts[5].as_slice();
+    let token_a_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
+    let mut data = accounts[6].as_slice();
+    let token_b_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
     let current_time: u64 = clock.unix_timestamp.try_into().unwrap();
 
     let token_a_amount = vault_a
@@ -78,8 +102,33 @@ pub fn process_get_dynamic_pool_info(args: &Args, sub_args: &PoolInfoDynamicAmmA
         .context("Fail to get token b amount")
         .unwrap();
 
-    println!(
+    info!(
         "token_a_amount {} token_b_amount {}",
-        token_a_amount, token_b_amount
+        args.format_amount(token_a_amount, token_a_mint.decimals),
+        args.format_amount(token_b_amount, token_b_mint.decimals)
     );
+
+    let configs = get_configs(&program_dynamic_amm.rpc(), None)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect::<Vec<Pubkey>>();
+    let origin = get_pool_origin(*pool, &pool_state, &configs);
+    info!("origin {:?}", origin);
+
+    let mismatches = verify_pool_addresses(*pool, &pool_state);
+    for mismatch in mismatches.iter() {
+        info!(
+            "{} {} does not match its seeds, expected {}",
+            mismatch.field, mismatch.actual, mismatch.expected
+        );
+    }
+
+    output::emit(&PoolInfoOutput {
+        pool: PoolView::from(&pool_state),
+        token_a_amount,
+        token_b_amount,
+        origin: PoolOriginView::from(&origin),
+        address_mismatches: mismatches.iter().map(AddressMismatchView::from).collect(),
+    });
 }
@@ -0,0 +1,96 @@
+use crate::discovery::{get_configs, get_pools_by_configs};
+use crate::*;
+use dynamic_amm_quote::view::ConfigView;
+use prog_dynamic_amm::state::Config;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ListConfigsDynamicAmmArgs {
+    /// Only list the configs restricted to this pool creator authority
+    #[clap(long, env)]
+    pub pool_creator_authority: Option<Pubkey>,
+    /// Only list this config
+    #[clap(long, env)]
+    pub config: Option<Pubkey>,
+}
+
+/// JSON output of `dynamic-amm list-configs`
+#[derive(Serialize, Debug)]
+pub struct ListConfigsOutput {
+    pub configs: Vec<ConfigOutput>,
+}
+
+/// Config, with the pools created from it
+#[derive(Serialize, Debug)]
+pub struct ConfigOutput {
+    pub address: String,
+    pub config: ConfigView,
+    pub pools: Vec<String>,
+}
+
+impl ConfigOutput {
+    pub fn new(config_key: &Pubkey, config: &Config, pools: &[Pubkey]) -> Self {
+        ConfigOutput {
+            address: config_key.to_string(),
+            config: ConfigView::from(config),
+            pools: pools.iter().map(|pool| pool.to_string()).collect(),
+        }
+    }
+}
+
+pub fn print_config(config_key: &Pubkey, config: &Config) {
+    info!("config {}", config_key);
+    info!(
+        "  trade_fee {}/{}",
+        config.pool_fees.trade_fee_numerator, config.pool_fees.trade_fee_denominator
+    );
+    info!(
+        "  protocol_trade_fee {}/{}",
+        config.pool_fees.protocol_trade_fee_numerator,
+        config.pool_fees.protocol_trade_fee_denominator
+    );
+    info!("  partner_fee_numerator {}", config.partner_fee_numerator);
+    info!("  activation_duration {}", config.activation_duration);
+    info!("  activation_type {}", config.activation_type);
+    info!("  vault_config_key {}", config.vault_config_key);
+    info!("  pool_creator_authority {}", config.pool_creator_authority);
+}
+
+pub fn process_list_configs(args: &Args, sub_args: &ListConfigsDynamicAmmArgs) {
+    let ListConfigsDynamicAmmArgs {
+        pool_creator_authority,
+        config,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+
+    let configs = match config {
+        Some(config) => {
+            let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+            vec![(
+                *config,
+                program_amm_client.account::<Config>(*config).unwrap(),
+            )]
+        }
+        None => get_configs(&client, pool_creator_authority.as_ref()).unwrap(),
+    };
+
+    let config_keys = configs.iter().map(|(key, _)| *key).collect::<Vec<Pubkey>>();
+    let pools_by_config = get_pools_by_configs(&client, &config_keys).unwrap();
+
+    info!("Found {} configs", configs.len());
+    let mut config_outputs = vec![];
+    for (config_key, config) in configs.iter() {
+        print_config(config_key, config);
+        let pools = &pools_by_config[config_key];
+        info!("  pools {}", pools.len());
+        for pool in pools.iter() {
+            info!("    {}", pool);
+        }
+        config_outputs.push(ConfigOutput::new(config_key, config, pools));
+    }
+    output::emit(&ListConfigsOutput {
+        configs: config_outputs,
+    });
+}
@@ -0,0 +1,125 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_client::ClientError;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{Mint, TokenAccount};
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use prog_dynamic_amm::state::Pool;
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+#[derive(Parser, Debug, Clone)]
+pub struct LockDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Pool lp amount to lock. Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub amount: String,
+}
+
+/// JSON output of `dynamic-amm lock`
+#[derive(Serialize, Debug)]
+pub struct LockOutput {
+    /// Lock escrow of the owner, receiving the locked lp
+    pub lock_escrow: String,
+}
+
+pub fn process_lock_dynamic_pool(args: &Args, sub_args: &LockDynamicAmmArgs) {
+    let LockDynamicAmmArgs { pool, amount } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let pool_state: Pool = program_amm_client.account(*pool).unwrap();
+
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+    let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
+    let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
+
+    let pool_lp_mint: Mint = program_amm_client.account(pool_state.lp_mint).unwrap();
+    let amount = args.parse_amount(amount, pool_lp_mint.decimals).unwrap();
+
+    let user_pool_lp = get_associated_token_address(&keypair.pubkey(), &pool_state.lp_mint);
+    let user_pool_lp_state: TokenAccount = match program_amm_client.account(user_pool_lp) {
+        Err(ClientError::AccountNotFound) => output::exit_with_error(&format!(
+            "No lp tokens to lock, lp token account {} not found",
+            user_pool_lp
+        )),
+        result => result.unwrap(),
+    };
+    if user_pool_lp_state.amount < amount {
+        output::exit_with_error(&format!(
+            "Insufficient lp, balance {} lock amount {}",
+            args.format_amount(user_pool_lp_state.amount, pool_lp_mint.decimals),
+            args.format_amount(amount, pool_lp_mint.decimals)
+        ));
+    }
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &pool_state,
+        a_vault: &a_vault_state,
+        b_vault: &b_vault_state,
+    };
+
+    let lock_escrow = derive_lock_escrow_address(*pool, keypair.pubkey());
+    output::emit(&LockOutput {
+        lock_escrow: lock_escrow.to_string(),
+    });
+    if client.get_account(&lock_escrow).is_err() {
+        info!("create lock escrow {}", lock_escrow);
+        ixs.push(builder.create_lock_escrow(keypair.pubkey(), keypair.pubkey()));
+    }
+
+    let escrow_vault = builder.get_escrow_vault(keypair.pubkey());
+    if client.get_account(&escrow_vault).is_err() {
+        ixs.push(
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &keypair.pubkey(),
+                &lock_escrow,
+                &pool_state.lp_mint,
+                &spl_token::ID,
+            ),
+        );
+    }
+
+    ixs.push(builder.lock(keypair.pubkey(), amount));
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let payload = args
+        .to_rpc_args()
+        .send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            "".to_string(),
+            sucess_cb,
+            failed_cb,
+        )
+        .unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done lock {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot lock");
+}
@@ -18,6 +18,72 @@ pub use pda::*;
 pub mod get_pool_info;
 pub use get_pool_info::*;
 
+pub mod get_pool_apy;
+pub use get_pool_apy::*;
+
+pub mod search_pools;
+pub use search_pools::*;
+
+pub mod lock;
+pub use lock::*;
+
+pub mod claim_lock_fee;
+pub use claim_lock_fee::*;
+
+pub mod show_lock;
+pub use show_lock::*;
+
+pub mod get_partner_fees;
+pub use get_partner_fees::*;
+
+pub mod claim_partner_fees;
+pub use claim_partner_fees::*;
+
+pub mod create_config;
+pub use create_config::*;
+
+pub mod list_configs;
+pub use list_configs::*;
+
+pub mod close_config;
+pub use close_config::*;
+
+pub mod create_customizable_pool;
+pub use create_customizable_pool::*;
+
+pub mod create_pool_with_config;
+pub use create_pool_with_config::*;
+
+pub mod create_permissionless_pool;
+pub use create_permissionless_pool::*;
+
+pub mod create_permissioned_pool;
+pub use create_permissioned_pool::*;
+
+pub mod withdraw_single_side;
+pub use withdraw_single_side::*;
+
+pub mod deposit_imbalance;
+pub use deposit_imbalance::*;
+
+pub mod pool_admin;
+pub use pool_admin::*;
+
+pub mod enable_or_disable_pool;
+pub use enable_or_disable_pool::*;
+
+pub mod set_pool_fees;
+pub use set_pool_fees::*;
+
+pub mod override_curve_param;
+pub use override_curve_param::*;
+
+pub mod update_activation_point;
+pub use update_activation_point::*;
+
+pub mod amp_ramp;
+pub use amp_ramp::*;
+
 #[derive(Debug, Parser, Clone)]
 pub enum DynamicAmmCommands {
     /// Create pool
@@ -32,4 +98,46 @@ pub enum DynamicAmmCommands {
     Quote(QuoteDynamicAmmArgs),
     /// Get pool info
     GetPoolInfo(PoolInfoDynamicAmmArgs),
+    /// Get pool lp APY, split between the vault lending yield and the trading fee
+    GetPoolApy(PoolApyDynamicAmmArgs),
+    /// Search pools by token mint, and configs by pool creator authority
+    SearchPools(SearchPoolsDynamicAmmArgs),
+    /// Lock lp, creating the lock escrow if missing
+    Lock(LockDynamicAmmArgs),
+    /// Claim the fee of the locked lp
+    ClaimLockFee(ClaimLockFeeDynamicAmmArgs),
+    /// Show the lock escrow
+    ShowLock(ShowLockDynamicAmmArgs),
+    /// List the pools of a partner, with their pending partner fee
+    GetPartnerFees(GetPartnerFeesDynamicAmmArgs),
+    /// Claim the pending partner fee of the pools in batch
+    ClaimPartnerFees(ClaimPartnerFeesDynamicAmmArgs),
+    /// Create config
+    CreateConfig(CreateConfigDynamicAmmArgs),
+    /// List configs, with the pools created from them
+    ListConfigs(ListConfigsDynamicAmmArgs),
+    /// Close config
+    CloseConfig(CloseConfigDynamicAmmArgs),
+    /// Create customizable permissionless constant product pool
+    CreateCustomizablePool(CreateCustomizablePoolDynamicAmmArgs),
+    /// Create permissionless constant product pool with config
+    CreatePoolWithConfig(CreatePoolWithConfigDynamicAmmArgs),
+    /// Create permissionless pool of the default trade fee, constant product or stable
+    CreatePermissionlessPool(CreatePermissionlessPoolDynamicAmmArgs),
+    /// Create permissioned pool
+    CreatePermissionedPool(CreatePermissionedPoolDynamicAmmArgs),
+    /// Withdraw into a single token of a stable pool
+    WithdrawSingleSide(WithdrawSingleSideDynamicAmmArgs),
+    /// Deposit to a stable pool in an imbalance ratio
+    DepositImbalance(DepositImbalanceDynamicAmmArgs),
+    /// Admin: enable or disable pool
+    EnableOrDisablePool(EnableOrDisablePoolDynamicAmmArgs),
+    /// Admin: set pool fees
+    SetPoolFees(SetPoolFeesDynamicAmmArgs),
+    /// Admin: override the amp of a stable pool
+    OverrideCurveParam(OverrideCurveParamDynamicAmmArgs),
+    /// Admin: update the activation point of a pool not activated yet
+    UpdateActivationPoint(UpdateActivationPointDynamicAmmArgs),
+    /// Admin: plan, and execute, the amp ramp of a stable pool
+    AmpRamp(AmpRampDynamicAmmArgs),
 }
@@ -0,0 +1,50 @@
+use crate::*;
+use dynamic_amm_quote::admin::validate_amp_change;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use prog_dynamic_amm::state::CurveType;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct OverrideCurveParamDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// New amplification coefficient of the stable pool
+    #[clap(long, env)]
+    pub amp: u64,
+}
+
+pub fn process_override_curve_param(args: &Args, sub_args: &OverrideCurveParamDynamicAmmArgs) {
+    let OverrideCurveParamDynamicAmmArgs { pool, amp } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+    let quote_data = get_quote_data(args, pool).unwrap();
+    let pool_state = &quote_data.pool;
+
+    let curve_type = validate_amp_change(pool_state, *amp, &quote_data.clock).unwrap();
+
+    let mut expected = pool_state.clone();
+    expected.curve_type = match curve_type {
+        CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            ..
+        } => CurveType::Stable {
+            amp,
+            token_multiplier,
+            depeg,
+            last_amp_updated_timestamp: quote_data.clock.unix_timestamp as u64,
+        },
+        CurveType::ConstantProduct => curve_type,
+    };
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: pool_state,
+        a_vault: &quote_data.vault_a,
+        b_vault: &quote_data.vault_b,
+    };
+    let ix = builder.override_curve_param(keypair.pubkey(), curve_type);
+    send_pool_admin_instruction(args, pool, pool_state, &expected, ix, &keypair);
+}
@@ -0,0 +1,96 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use dynamic_amm_quote::admin::{diff_pool, FieldChange};
+use dynamic_amm_quote::diagnostic::diagnose_pool_error;
+use prog_dynamic_amm::state::Pool;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::Signer;
+use solana_sdk::transaction::Transaction;
+
+/// Change of a pool by an admin command, in the `pool_changes` list of the JSON output
+#[derive(Serialize, Debug)]
+pub struct PoolChangeOutput {
+    pub pool: String,
+    /// Fields the instruction is expected to change
+    pub expected: Vec<FieldChange>,
+    /// Fields changed once the transaction is sent, null with the other tx actions
+    pub actual: Option<Vec<FieldChange>>,
+}
+
+pub fn print_pool_diff(before: &Pool, after: &Pool) -> Vec<FieldChange> {
+    let changes = diff_pool(before, after);
+    if changes.is_empty() {
+        info!("  no change");
+    }
+    for change in changes.iter() {
+        info!("  {} {} -> {}", change.field, change.before, change.after);
+    }
+    changes
+}
+
+/// Print the expected change of the pool fields, send the admin instruction, then print the actual change once sent
+pub fn send_pool_admin_instruction(
+    args: &Args,
+    pool: &Pubkey,
+    before: &Pool,
+    expected: &Pool,
+    ix: Instruction,
+    keypair: &Keypair,
+) {
+    info!("expected change of pool {}", pool);
+    let expected_changes = print_pool_diff(before, expected);
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+        ix,
+    ];
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[keypair], blockhash);
+    let result = args.to_rpc_args().send_transaction_wrapper(
+        &tx,
+        MAX_RETRIES,
+        keypair.pubkey(),
+        "".to_string(),
+        sucess_cb,
+        failed_cb,
+    );
+    if let Some(pool_error) = transaction_utils::get_transaction_pool_error(args, &tx, &result) {
+        transaction_utils::print_pool_error_diagnostic(args, pool, pool_error, |quote_data| {
+            diagnose_pool_error(pool_error, &quote_data.pool, &quote_data.clock)
+        });
+    }
+    let payload = result.unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+
+    let actual_changes = if args.tx_action == TX_ACTION_SENT_TX {
+        let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+        let after: Pool = program_amm_client.account(*pool).unwrap();
+        info!("actual change of pool {}", pool);
+        Some(print_pool_diff(before, &after))
+    } else {
+        None
+    };
+    output::push(
+        "pool_changes",
+        &PoolChangeOutput {
+            pool: pool.to_string(),
+            expected: expected_changes,
+            actual: actual_changes,
+        },
+    );
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done pool admin {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot pool admin");
+}
@@ -4,7 +4,9 @@ use anchor_spl::token::Mint;
 use anchor_spl::token::TokenAccount;
 use bincode::deserialize;
 use dynamic_amm_quote::compute_quote;
-use dynamic_amm_quote::QuoteData;
+use dynamic_amm_quote::depeg::get_stake_account;
+use dynamic_amm_quote::ui_amount::get_ui_price;
+use dynamic_amm_quote::{QuoteData, QuoteResult};
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_sdk::sysvar::clock;
@@ -16,8 +18,20 @@ pub struct QuoteDynamicAmmArgs {
     pub pool: Pubkey,
     #[clap(long, env)]
     pub source_token: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
     #[clap(long, env)]
+    pub in_amount: String,
+}
+
+/// JSON output of `dynamic-amm quote`
+#[derive(Serialize, Debug)]
+pub struct QuoteOutput {
+    /// Raw in amount
     pub in_amount: u64,
+    /// Quote, with the `fee`, `protocol_fee` and `partner_fee` breakdown. Raw amounts
+    pub quote: QuoteResult,
+    /// Price of one whole in token, in whole out token. Null when the in amount is 0
+    pub price: Option<f64>,
 }
 
 pub fn process_quote_dynamic_pool(args: &Args, sub_args: &QuoteDynamicAmmArgs) {
@@ -27,67 +41,102 @@ pub fn process_quote_dynamic_pool(args: &Args, sub_args: &QuoteDynamicAmmArgs) {
         source_token,
     } = sub_args;
 
+    let program_dynamic_amm = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+
+    let quote_data = get_quote_data(args, pool).unwrap();
+
+    let token_a_mint: Mint = program_dynamic_amm
+        .account(quote_data.pool.token_a_mint)
+        .unwrap();
+    let token_b_mint: Mint = program_dynamic_amm
+        .account(quote_data.pool.token_b_mint)
+        .unwrap();
+
+    let (in_decimals, out_decimals) = if *source_token == quote_data.pool.token_a_mint {
+        (token_a_mint.decimals, token_b_mint.decimals)
+    } else {
+        (token_b_mint.decimals, token_a_mint.decimals)
+    };
+    let in_amount = args.parse_amount(in_amount, in_decimals).unwrap();
+
+    let quote = compute_quote(*source_token, in_amount, quote_data).unwrap();
+    let price = get_ui_price(in_amount, in_decimals, quote.out_amount, out_decimals);
+    info!("{:?}", quote);
+    info!(
+        "in_amount {} out_amount {} fee {} protocol_fee {} partner_fee {} price {:?}",
+        args.format_amount(in_amount, in_decimals),
+        args.format_amount(quote.out_amount, out_decimals),
+        args.format_amount(quote.fee, in_decimals),
+        args.format_amount(quote.protocol_fee, in_decimals),
+        args.format_amount(quote.partner_fee, in_decimals),
+        price
+    );
+
+    output::emit(&QuoteOutput {
+        in_amount,
+        quote,
+        price,
+    });
+}
+
+/// Fetch the accounts required to quote the pool
+pub fn get_quote_data(args: &Args, pool: &Pubkey) -> anyhow::Result<QuoteData> {
     let program_dynamic_amm = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
     let program_dynamic_vault = args
         .to_rpc_args()
         .get_program_client(prog_dynamic_vault::ID);
 
-    let pool_state: Pool = program_dynamic_amm.account(*pool).unwrap();
-    let vault_a: Vault = program_dynamic_vault.account(pool_state.a_vault).unwrap();
-    let vault_b: Vault = program_dynamic_vault.account(pool_state.b_vault).unwrap();
-
-    let accounts = program_dynamic_amm
-        .rpc()
-        .get_multiple_accounts(&[
-            pool_state.a_vault_lp,
-            pool_state.b_vault_lp,
-            vault_a.lp_mint,
-            vault_b.lp_mint,
-            vault_a.token_vault,
-            vault_b.token_vault,
-            clock::id(),
-        ])
-        .unwrap();
+    let pool_state: Pool = program_dynamic_amm.account(*pool)?;
+    let vault_a: Vault = program_dynamic_vault.account(pool_state.a_vault)?;
+    let vault_b: Vault = program_dynamic_vault.account(pool_state.b_vault)?;
+
+    let accounts = program_dynamic_amm.rpc().get_multiple_accounts(&[
+        pool_state.a_vault_lp,
+        pool_state.b_vault_lp,
+        vault_a.lp_mint,
+        vault_b.lp_mint,
+        vault_a.token_vault,
+        vault_b.token_vault,
+        clock::id(),
+    ])?;
 
     let accounts = accounts
         .into_iter()
-        .map(|account| account.unwrap().data)
-        .collect::<Vec<Vec<u8>>>();
+        .map(|account| account.map(|account| account.data))
+        .collect::<Option<Vec<Vec<u8>>>>()
+        .ok_or_else(|| anyhow::anyhow!("Quote account not found"))?;
 
     let mut data = accounts[0].as_slice();
-    let pool_vault_a_lp_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let pool_vault_a_lp_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[1].as_slice();
-    let pool_vault_b_lp_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let pool_vault_b_lp_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[2].as_slice();
-    let vault_a_lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_a_lp_mint = Mint::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[3].as_slice();
-    let vault_b_lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_b_lp_mint = Mint::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[4].as_slice();
-    let vault_a_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_a_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let mut data = accounts[5].as_slice();
-    let vault_b_token = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+    let vault_b_token = TokenAccount::try_deserialize_unchecked(&mut data)?;
 
     let data = accounts[6].as_slice();
-    let clock = deserialize::<Clock>(&data).unwrap();
+    let clock = deserialize::<Clock>(&data)?;
 
-    let stake_data = if pool_state.stake != Pubkey::default() {
-        let account = program_dynamic_amm
-            .rpc()
-            .get_account(&pool_state.stake)
-            .unwrap();
+    let stake_data = if let Some(stake) = get_stake_account(&pool_state) {
+        let account = program_dynamic_amm.rpc().get_account(&stake)?;
         let mut stake_data = HashMap::new();
-        stake_data.insert(pool_state.stake, account.data);
+        stake_data.insert(stake, account.data);
         stake_data
     } else {
         HashMap::new()
     };
 
-    let quote_data = QuoteData {
+    Ok(QuoteData {
         pool: pool_state,
         vault_a,
         vault_b,
@@ -99,7 +148,5 @@ pub fn process_quote_dynamic_pool(args: &Args, sub_args: &QuoteDynamicAmmArgs) {
         vault_b_token,
         clock,
         stake_data,
-    };
-    let quote = compute_quote(*source_token, *in_amount, quote_data);
-    println!("{:?}", quote);
+    })
 }
@@ -0,0 +1,126 @@
+use crate::discovery::{get_configs, get_pools_by_configs, get_pools_by_token};
+use crate::*;
+use clap::ArgGroup;
+use dynamic_amm_quote::discovery::find_pool_config;
+use dynamic_amm_quote::view::PoolView;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+
+#[derive(Parser, Debug, Clone)]
+#[clap(group(
+    ArgGroup::new("search")
+        .required(true)
+        .multiple(true)
+        .args(&["token-mint", "pool-creator-authority"])
+))]
+pub struct SearchPoolsDynamicAmmArgs {
+    /// List the pools having this token as token A or token B
+    #[clap(long, env)]
+    pub token_mint: Option<Pubkey>,
+    /// Resolve the config the pools of --token-mint were created with. All the configs are fetched to match them.
+    #[clap(long, requires = "token-mint")]
+    pub with_config: bool,
+    /// List the configs restricted to this pool creator authority, with the pools created from them
+    #[clap(long, env)]
+    pub pool_creator_authority: Option<Pubkey>,
+}
+
+/// JSON output of `dynamic-amm search-pools`. Each list is only present when searched for.
+#[derive(Serialize, Debug)]
+pub struct SearchPoolsOutput {
+    /// Pools of --token-mint
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pools: Option<Vec<SearchPoolOutput>>,
+    /// Configs of --pool-creator-authority
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configs: Option<Vec<ConfigOutput>>,
+}
+
+/// Pool found by token mint
+#[derive(Serialize, Debug)]
+pub struct SearchPoolOutput {
+    pub address: String,
+    pub pool: PoolView,
+    /// Config the pool was created with. Null without --with-config, or when the pool wasn't created from a config
+    pub config: Option<String>,
+}
+
+pub fn process_search_pools(args: &Args, sub_args: &SearchPoolsDynamicAmmArgs) {
+    let SearchPoolsDynamicAmmArgs {
+        token_mint,
+        with_config,
+        pool_creator_authority,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+
+    if let Some(token_mint) = token_mint {
+        let pools = get_pools_by_token(&client, token_mint).unwrap();
+        let configs = if *with_config {
+            get_configs(&client, None)
+                .unwrap()
+                .into_iter()
+                .map(|(key, _)| key)
+                .collect::<Vec<Pubkey>>()
+        } else {
+            vec![]
+        };
+
+        info!("Found {} pools for token {}", pools.len(), token_mint);
+        let mut pool_outputs = vec![];
+        for (pool_key, pool) in pools.iter() {
+            info!("pool {}", pool_key);
+            info!("  token_a_mint {}", pool.token_a_mint);
+            info!("  token_b_mint {}", pool.token_b_mint);
+            info!("  pool_type {:?}", pool.pool_type);
+            info!("  curve_type {:?}", pool.curve_type);
+            info!("  enabled {}", pool.enabled);
+            let config = if *with_config {
+                let config =
+                    find_pool_config(*pool_key, pool.token_a_mint, pool.token_b_mint, &configs);
+                match config {
+                    Some(config) => info!("  config {}", config),
+                    None => info!("  config none"),
+                }
+                config
+            } else {
+                None
+            };
+            pool_outputs.push(SearchPoolOutput {
+                address: pool_key.to_string(),
+                pool: PoolView::from(pool),
+                config: config.map(|config| config.to_string()),
+            });
+        }
+        output::emit(&SearchPoolsOutput {
+            pools: Some(pool_outputs),
+            configs: None,
+        });
+    }
+
+    if let Some(pool_creator_authority) = pool_creator_authority {
+        let configs = get_configs(&client, Some(pool_creator_authority)).unwrap();
+        let config_keys = configs.iter().map(|(key, _)| *key).collect::<Vec<Pubkey>>();
+        let pools_by_config = get_pools_by_configs(&client, &config_keys).unwrap();
+
+        info!(
+            "Found {} configs for pool creator authority {}",
+            configs.len(),
+            pool_creator_authority
+        );
+        let mut config_outputs = vec![];
+        for (config_key, config) in configs.iter() {
+            print_config(config_key, config);
+            let pools = &pools_by_config[config_key];
+            info!("  pools {}", pools.len());
+            for pool in pools.iter() {
+                info!("    {}", pool);
+            }
+            config_outputs.push(ConfigOutput::new(config_key, config, pools));
+        }
+        output::emit(&SearchPoolsOutput {
+            pools: None,
+            configs: Some(config_outputs),
+        });
+    }
+}
@@ -0,0 +1,84 @@
+use crate::discovery::get_configs;
+use crate::*;
+use dynamic_amm_quote::admin::validate_pool_fees;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use prog_dynamic_amm::state::PoolFees;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct SetPoolFeesDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    #[clap(long, env)]
+    pub trade_fee_numerator: u64,
+    #[clap(long, env, default_value = "100000")]
+    pub trade_fee_denominator: u64,
+    #[clap(long, env)]
+    pub protocol_trade_fee_numerator: u64,
+    #[clap(long, env, default_value = "100000")]
+    pub protocol_trade_fee_denominator: u64,
+    /// Partner fee numerator. The current partner fee numerator is kept when not set.
+    #[clap(long, env)]
+    pub partner_fee_numerator: Option<u64>,
+}
+
+pub fn process_set_pool_fees(args: &Args, sub_args: &SetPoolFeesDynamicAmmArgs) {
+    let SetPoolFeesDynamicAmmArgs {
+        pool,
+        trade_fee_numerator,
+        trade_fee_denominator,
+        protocol_trade_fee_numerator,
+        protocol_trade_fee_denominator,
+        partner_fee_numerator,
+    } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+    let quote_data = get_quote_data(args, pool).unwrap();
+    let pool_state = &quote_data.pool;
+
+    let fees = PoolFees {
+        trade_fee_numerator: *trade_fee_numerator,
+        trade_fee_denominator: *trade_fee_denominator,
+        protocol_trade_fee_numerator: *protocol_trade_fee_numerator,
+        protocol_trade_fee_denominator: *protocol_trade_fee_denominator,
+    };
+    let partner_fee_numerator =
+        partner_fee_numerator.unwrap_or(pool_state.partner_info.fee_numerator);
+
+    // config and customizable pools are meme pools
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let configs = get_configs(&program_amm_client.rpc(), None)
+        .unwrap()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect::<Vec<Pubkey>>();
+    let is_meme_pool = matches!(
+        get_pool_origin(*pool, pool_state, &configs),
+        PoolOrigin::Config { .. } | PoolOrigin::Customizable
+    );
+    validate_pool_fees(
+        pool_state,
+        &fees,
+        partner_fee_numerator,
+        is_meme_pool,
+        &quote_data.clock,
+    )
+    .unwrap();
+
+    let mut expected = pool_state.clone();
+    expected.fees = fees;
+    expected.partner_info.fee_numerator = partner_fee_numerator;
+    if is_meme_pool {
+        expected.fee_last_updated_at = quote_data.clock.unix_timestamp as u64;
+    }
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: pool_state,
+        a_vault: &quote_data.vault_a,
+        b_vault: &quote_data.vault_b,
+    };
+    let ix = builder.set_pool_fees(keypair.pubkey(), fees, partner_fee_numerator);
+    send_pool_admin_instruction(args, pool, pool_state, &expected, ix, &keypair);
+}
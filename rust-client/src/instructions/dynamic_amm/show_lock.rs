@@ -0,0 +1,147 @@
+use crate::*;
+use anchor_client::ClientError;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use prog_dynamic_amm::state::{LockEscrow, Pool};
+use prog_dynamic_vault::state::Vault;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct ShowLockDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// Owner of the lock escrow, the keypair when not set
+    #[clap(long, env)]
+    pub owner: Option<Pubkey>,
+}
+
+/// JSON output of `dynamic-amm show-lock`
+#[derive(Serialize, Debug)]
+pub struct ShowLockOutput {
+    /// Null when the owner has no lock escrow
+    pub lock_escrow: Option<LockEscrowOutput>,
+    /// Null when there is no lock escrow, or the claim couldn't be simulated
+    pub claimable: Option<ClaimableLockFeeOutput>,
+}
+
+/// Lock escrow state. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct LockEscrowOutput {
+    pub address: String,
+    pub owner: String,
+    pub escrow_vault: String,
+    pub total_locked_amount: u64,
+    pub pool_total_locked_lp: u64,
+    /// A u128, which doesn't fit a JSON number, as a decimal string
+    pub lp_per_token: String,
+    pub unclaimed_fee_pending: u64,
+    /// Token A fee claimed so far
+    pub a_fee: u64,
+    /// Token B fee claimed so far
+    pub b_fee: u64,
+}
+
+pub fn process_show_lock_dynamic_pool(args: &Args, sub_args: &ShowLockDynamicAmmArgs) {
+    let ShowLockDynamicAmmArgs { pool, owner } = sub_args;
+
+    let owner = match owner {
+        Some(owner) => *owner,
+        None => read_keypair_file(&args.keypair_path.clone().unwrap())
+            .unwrap()
+            .pubkey(),
+    };
+
+    let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let pool_state: Pool = program_amm_client.account(*pool).unwrap();
+
+    let lock_escrow = derive_lock_escrow_address(*pool, owner);
+    let lock_escrow_state: LockEscrow = match program_amm_client.account(lock_escrow) {
+        Err(ClientError::AccountNotFound) => {
+            info!("No lock escrow {} for owner {}", lock_escrow, owner);
+            output::emit(&ShowLockOutput {
+                lock_escrow: None,
+                claimable: None,
+            });
+            return;
+        }
+        result => result.unwrap(),
+    };
+
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+    let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
+    let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
+
+    let pool_lp_mint: Mint = program_amm_client.account(pool_state.lp_mint).unwrap();
+    let token_a_mint: Mint = program_amm_client.account(pool_state.token_a_mint).unwrap();
+    let token_b_mint: Mint = program_amm_client.account(pool_state.token_b_mint).unwrap();
+
+    info!("lock_escrow {}", lock_escrow);
+    info!("owner {}", lock_escrow_state.owner);
+    info!("escrow_vault {}", lock_escrow_state.escrow_vault);
+    info!(
+        "total_locked_amount {}",
+        args.format_amount(lock_escrow_state.total_locked_amount, pool_lp_mint.decimals)
+    );
+    info!(
+        "pool_total_locked_lp {}",
+        args.format_amount(pool_state.total_locked_lp, pool_lp_mint.decimals)
+    );
+    info!("lp_per_token {}", lock_escrow_state.lp_per_token);
+    info!(
+        "unclaimed_fee_pending {}",
+        args.format_amount(
+            lock_escrow_state.unclaimed_fee_pending,
+            pool_lp_mint.decimals
+        )
+    );
+    info!(
+        "a_fee claimed {}",
+        args.format_amount(lock_escrow_state.a_fee, token_a_mint.decimals)
+    );
+    info!(
+        "b_fee claimed {}",
+        args.format_amount(lock_escrow_state.b_fee, token_b_mint.decimals)
+    );
+
+    let lock_escrow_output = LockEscrowOutput {
+        address: lock_escrow.to_string(),
+        owner: lock_escrow_state.owner.to_string(),
+        escrow_vault: lock_escrow_state.escrow_vault.to_string(),
+        total_locked_amount: lock_escrow_state.total_locked_amount,
+        pool_total_locked_lp: pool_state.total_locked_lp,
+        lp_per_token: lock_escrow_state.lp_per_token.to_string(),
+        unclaimed_fee_pending: lock_escrow_state.unclaimed_fee_pending,
+        a_fee: lock_escrow_state.a_fee,
+        b_fee: lock_escrow_state.b_fee,
+    };
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &pool_state,
+        a_vault: &a_vault_state,
+        b_vault: &b_vault_state,
+    };
+    let ixs = get_create_fee_token_accounts_ixs(&pool_state, owner, owner);
+    let claimable = match get_claimable_lock_fee(args, &builder, owner, &ixs) {
+        Ok(claimable) => {
+            info!(
+                "claimable lp {} token_a {} token_b {}",
+                args.format_amount(claimable.amount, pool_lp_mint.decimals),
+                args.format_amount(claimable.a_fee, token_a_mint.decimals),
+                args.format_amount(claimable.b_fee, token_b_mint.decimals)
+            );
+            Some(ClaimableLockFeeOutput::from(&claimable))
+        }
+        Err(err) => {
+            info!("claimable unknown: {}", err);
+            None
+        }
+    };
+    output::emit(&ShowLockOutput {
+        lock_escrow: Some(lock_escrow_output),
+        claimable,
+    });
+}
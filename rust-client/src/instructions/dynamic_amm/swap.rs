@@ -1,14 +1,18 @@
 use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
 use crate::*;
-use anchor_lang::InstructionData;
-use anchor_lang::ToAccountMetas;
 use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::diagnostic::diagnose_swap_error;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use dynamic_amm_quote::slippage::{
+    compute_price_impact_bps, get_maximum_amount, get_minimum_amount,
+};
+use dynamic_amm_quote::{compute_quote, compute_quote_exact_out, QuoteResult};
 use prog_dynamic_amm::state::Pool;
 use prog_dynamic_vault::state::Vault;
 use solana_rpc_client::rpc_client::RpcClient;
 use solana_sdk::commitment_config::CommitmentConfig;
 use solana_sdk::compute_budget::ComputeBudgetInstruction;
-use solana_sdk::instruction::Instruction;
 use solana_sdk::signer::keypair::read_keypair_file;
 use solana_sdk::signer::Signer;
 use solana_sdk::transaction::Transaction;
@@ -18,10 +22,38 @@ pub struct SwapDynamicAmmArgs {
     pub pool: Pubkey,
     #[clap(long, env)]
     pub source_token: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env, required_unless_present = "out-amount")]
+    pub in_amount: Option<String>,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env, required_unless_present_any = &["slippage-bps", "out-amount"], conflicts_with = "slippage-bps")]
+    pub minimum_out_amount: Option<String>,
+    /// Quote the swap, and derive the minimum out amount from it. In exact out mode, derive the maximum in amount instead
     #[clap(long, env)]
-    pub in_amount: u64,
+    pub slippage_bps: Option<u64>,
+    /// Exact out mode. The in amount quoted to receive this amount is raised by --slippage-bps, and that maximum in amount is
+    /// always swapped in full for at least this amount: the program only swaps exact in amounts, so more than this amount is
+    /// received unless the price moves by the whole slippage. Raw amount, or UI amount with --ui-amount
+    #[clap(long, env, conflicts_with_all = &["in-amount", "minimum-out-amount"], requires = "slippage-bps")]
+    pub out_amount: Option<String>,
+    /// Refuse to swap when the price impact of the quoted swap is above it
     #[clap(long, env)]
+    pub max_price_impact_bps: Option<u64>,
+}
+
+/// JSON output of `dynamic-amm swap`. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct SwapOutput {
+    /// In amount swapped
+    pub in_amount: u64,
+    /// Minimum out amount of the swap
     pub minimum_out_amount: u64,
+    /// Quote of the swap, null when --minimum-out-amount is given. In exact out mode, quote of the expected in amount
+    pub quote: Option<QuoteResult>,
+    /// Exact out mode only. In amount quoted to receive the out amount, before the slippage
+    pub expected_in_amount: Option<u64>,
+    /// Price impact of the swap, only with --max-price-impact-bps
+    pub price_impact_bps: Option<f64>,
 }
 
 pub fn process_swap_dynamic_pool(args: &Args, sub_args: &SwapDynamicAmmArgs) {
@@ -30,6 +62,9 @@ pub fn process_swap_dynamic_pool(args: &Args, sub_args: &SwapDynamicAmmArgs) {
         in_amount,
         minimum_out_amount,
         source_token,
+        slippage_bps,
+        out_amount,
+        max_price_impact_bps,
     } = sub_args;
 
     let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
@@ -43,25 +78,111 @@ pub fn process_swap_dynamic_pool(args: &Args, sub_args: &SwapDynamicAmmArgs) {
     let program_amm_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
     let pool_state: Pool = program_amm_client.account(*pool).unwrap();
 
-    let program_vault_client = args.to_rpc_args().get_program_client(prog_dynamic_amm::ID);
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
     let a_vault_state: Vault = program_vault_client.account(pool_state.a_vault).unwrap();
     let b_vault_state: Vault = program_vault_client.account(pool_state.b_vault).unwrap();
 
-    let (user_destination_token, protocol_token_fee, destination_mint) =
-        if *source_token == pool_state.token_a_mint {
-            (
-                get_associated_token_address(&keypair.pubkey(), &pool_state.token_b_mint),
-                pool_state.protocol_token_a_fee,
-                pool_state.token_b_mint,
-            )
-        } else {
-            (
-                get_associated_token_address(&keypair.pubkey(), &pool_state.token_a_mint),
-                pool_state.protocol_token_b_fee,
-                pool_state.token_a_mint,
-            )
+    let destination_mint = if *source_token == pool_state.token_a_mint {
+        pool_state.token_b_mint
+    } else {
+        pool_state.token_a_mint
+    };
+    let user_destination_token = get_associated_token_address(&keypair.pubkey(), &destination_mint);
+
+    let source_mint_state: Mint = program_amm_client.account(*source_token).unwrap();
+    let destination_mint_state: Mint = program_amm_client.account(destination_mint).unwrap();
+
+    // Only fetched when quoting
+    let quote_data = (minimum_out_amount.is_none() || max_price_impact_bps.is_some())
+        .then(|| get_quote_data(args, pool).unwrap());
+
+    let (in_amount, minimum_out_amount, quote, expected_in_amount) =
+        match (in_amount, minimum_out_amount, out_amount) {
+            (Some(in_amount), Some(minimum_out_amount), _) => (
+                args.parse_amount(in_amount, source_mint_state.decimals)
+                    .unwrap(),
+                args.parse_amount(minimum_out_amount, destination_mint_state.decimals)
+                    .unwrap(),
+                None,
+                None,
+            ),
+            (Some(in_amount), None, _) => {
+                let in_amount = args
+                    .parse_amount(in_amount, source_mint_state.decimals)
+                    .unwrap();
+                let quote =
+                    compute_quote(*source_token, in_amount, quote_data.clone().unwrap()).unwrap();
+                let minimum_out_amount =
+                    get_minimum_amount(quote.out_amount, slippage_bps.unwrap()).unwrap();
+                info!(
+                    "in_amount {} expected out amount {}, minimum out amount {}, fee {}",
+                    args.format_amount(in_amount, source_mint_state.decimals),
+                    args.format_amount(quote.out_amount, destination_mint_state.decimals),
+                    args.format_amount(minimum_out_amount, destination_mint_state.decimals),
+                    args.format_amount(quote.fee, source_mint_state.decimals)
+                );
+                (in_amount, minimum_out_amount, Some(quote), None)
+            }
+            (None, _, Some(out_amount)) => {
+                let out_amount = args
+                    .parse_amount(out_amount, destination_mint_state.decimals)
+                    .unwrap();
+                let exact_out_quote =
+                    compute_quote_exact_out(*source_token, out_amount, quote_data.clone().unwrap())
+                        .unwrap();
+                let maximum_in_amount =
+                    get_maximum_amount(exact_out_quote.in_amount, slippage_bps.unwrap()).unwrap();
+                info!(
+                    "out_amount {} expected in amount {}, maximum in amount {}, fee {}",
+                    args.format_amount(out_amount, destination_mint_state.decimals),
+                    args.format_amount(exact_out_quote.in_amount, source_mint_state.decimals),
+                    args.format_amount(maximum_in_amount, source_mint_state.decimals),
+                    args.format_amount(exact_out_quote.quote.fee, source_mint_state.decimals)
+                );
+                // The program swaps exact in amounts, the maximum in amount is swapped for at least out_amount
+                (
+                    maximum_in_amount,
+                    out_amount,
+                    Some(exact_out_quote.quote),
+                    Some(exact_out_quote.in_amount),
+                )
+            }
+            (None, _, None) => {
+                output::exit_with_error("Either --in-amount or --out-amount is required")
+            }
         };
 
+    let price_impact_bps = max_price_impact_bps.map(|_| {
+        let quote_data = quote_data.unwrap();
+        let quote = compute_quote(*source_token, in_amount, quote_data.clone()).unwrap();
+        let price_impact_bps =
+            compute_price_impact_bps(&quote_data, *source_token, in_amount, quote.out_amount)
+                .unwrap_or_else(|err| {
+                    output::exit_with_error(&format!("Fail to compute the price impact: {}", err))
+                });
+        info!("price impact {:.2} bps", price_impact_bps);
+        price_impact_bps
+    });
+    output::emit(&SwapOutput {
+        in_amount,
+        minimum_out_amount,
+        quote,
+        expected_in_amount,
+        price_impact_bps,
+    });
+    if let (Some(max_price_impact_bps), Some(price_impact_bps)) =
+        (max_price_impact_bps, price_impact_bps)
+    {
+        if price_impact_bps > *max_price_impact_bps as f64 {
+            output::exit_with_error(&format!(
+                "Price impact {:.2} bps above --max-price-impact-bps {}",
+                price_impact_bps, max_price_impact_bps
+            ));
+        }
+    }
+
     if client.get_account(&user_destination_token).is_err() {
         ixs.push(
             spl_associated_token_account::instruction::create_associated_token_account(
@@ -73,55 +194,131 @@ pub fn process_swap_dynamic_pool(args: &Args, sub_args: &SwapDynamicAmmArgs) {
         );
     }
 
-    ixs.push(Instruction {
-        program_id: prog_dynamic_amm::ID,
-        accounts: prog_dynamic_amm::accounts::Swap {
-            pool: *pool,
-            user_source_token: get_associated_token_address(&keypair.pubkey(), source_token),
-            user_destination_token,
-            a_vault_lp: pool_state.a_vault_lp,
-            b_vault_lp: pool_state.b_vault_lp,
-            a_vault: pool_state.a_vault,
-            b_vault: pool_state.b_vault,
-            a_vault_lp_mint: a_vault_state.lp_mint,
-            b_vault_lp_mint: b_vault_state.lp_mint,
-            a_token_vault: a_vault_state.token_vault,
-            b_token_vault: b_vault_state.token_vault,
-            user: keypair.pubkey(),
-            vault_program: prog_dynamic_vault::ID,
-            token_program: spl_token::ID,
-            protocol_token_fee,
-        }
-        .to_account_metas(None),
-        data: prog_dynamic_amm::instruction::Swap {
-            in_amount: *in_amount,
-            minimum_out_amount: *minimum_out_amount,
-        }
-        .data(),
-    });
+    ixs.extend(
+        transaction_utils::get_wrap_sol_ixs(&client, keypair.pubkey(), *source_token, in_amount)
+            .unwrap(),
+    );
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: &pool_state,
+        a_vault: &a_vault_state,
+        b_vault: &b_vault_state,
+    };
+    ixs.push(
+        builder
+            .swap(
+                keypair.pubkey(),
+                *source_token,
+                in_amount,
+                minimum_out_amount,
+            )
+            .unwrap(),
+    );
+    ixs.extend(
+        transaction_utils::get_unwrap_sol_ix(
+            &client,
+            keypair.pubkey(),
+            &[*source_token, destination_mint],
+        )
+        .unwrap(),
+    );
 
     let blockhash = client.get_latest_blockhash().unwrap();
     let tx =
         Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
-    let payload = args
-        .to_rpc_args()
-        .send_transaction_wrapper(
-            &tx,
-            MAX_RETRIES,
-            keypair.pubkey(),
-            "".to_string(),
-            sucess_cb,
-            failed_cb,
-        )
-        .unwrap();
+    let result = args.to_rpc_args().send_transaction_wrapper(
+        &tx,
+        MAX_RETRIES,
+        keypair.pubkey(),
+        "".to_string(),
+        sucess_cb,
+        failed_cb,
+    );
+    if let Some(pool_error) = transaction_utils::get_transaction_pool_error(args, &tx, &result) {
+        transaction_utils::print_pool_error_diagnostic(args, pool, pool_error, |quote_data| {
+            diagnose_swap_error(
+                pool_error,
+                *source_token,
+                in_amount,
+                minimum_out_amount,
+                quote_data,
+            )
+        });
+    }
+    let payload = result.unwrap();
     let mut result = BTreeMap::new();
     result.insert(0, Some(payload));
     handle_collect_cb_by_tx_action(args.tx_action, None, &result);
 }
 
 fn sucess_cb(_wallet_memo: String, sig: Signature) {
-    println!("done swap {:?}", sig);
+    info!("done swap {:?}", sig);
 }
 fn failed_cb(_wallet_memo: String) {
-    println!("cannot swap");
+    info!("cannot swap");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<SwapDynamicAmmArgs, clap::Error> {
+        let pool = Pubkey::new_unique().to_string();
+        let source_token = Pubkey::new_unique().to_string();
+        let mut argv = vec!["swap", "--pool", &pool, "--source-token", &source_token];
+        argv.extend_from_slice(args);
+        SwapDynamicAmmArgs::try_parse_from(argv)
+    }
+
+    #[test]
+    fn test_swap_args() {
+        // Exact in, with a minimum out amount or a slippage
+        let args = parse(&["--in-amount", "100", "--minimum-out-amount", "90"]).unwrap();
+        assert_eq!(args.in_amount.as_deref(), Some("100"));
+        assert_eq!(args.minimum_out_amount.as_deref(), Some("90"));
+        let args = parse(&["--in-amount", "100", "--slippage-bps", "50"]).unwrap();
+        assert_eq!(args.slippage_bps, Some(50));
+        // Exact out
+        let args = parse(&["--out-amount", "90", "--slippage-bps", "50"]).unwrap();
+        assert_eq!(args.out_amount.as_deref(), Some("90"));
+        assert!(args.in_amount.is_none());
+
+        // Neither an in amount nor an out amount
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--slippage-bps", "50"]).is_err());
+        // Neither a minimum out amount nor a slippage
+        assert!(parse(&["--in-amount", "100"]).is_err());
+        // Both a minimum out amount and a slippage
+        assert!(parse(&[
+            "--in-amount",
+            "100",
+            "--minimum-out-amount",
+            "90",
+            "--slippage-bps",
+            "50"
+        ])
+        .is_err());
+        // Exact out without a slippage
+        assert!(parse(&["--out-amount", "90"]).is_err());
+        // Both exact in and exact out
+        assert!(parse(&[
+            "--in-amount",
+            "100",
+            "--out-amount",
+            "90",
+            "--slippage-bps",
+            "50"
+        ])
+        .is_err());
+        assert!(parse(&[
+            "--out-amount",
+            "90",
+            "--minimum-out-amount",
+            "80",
+            "--slippage-bps",
+            "50"
+        ])
+        .is_err());
+    }
 }
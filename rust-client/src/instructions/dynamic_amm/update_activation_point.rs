@@ -0,0 +1,42 @@
+use crate::*;
+use dynamic_amm_quote::admin::validate_activation_point;
+use dynamic_amm_quote::instruction::PoolInstructionBuilder;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+
+#[derive(Parser, Debug, Clone)]
+pub struct UpdateActivationPointDynamicAmmArgs {
+    #[clap(long, env)]
+    pub pool: Pubkey,
+    /// New slot, or timestamp, depending on the activation type of the pool
+    #[clap(long, env)]
+    pub activation_point: u64,
+}
+
+pub fn process_update_activation_point(
+    args: &Args,
+    sub_args: &UpdateActivationPointDynamicAmmArgs,
+) {
+    let UpdateActivationPointDynamicAmmArgs {
+        pool,
+        activation_point,
+    } = sub_args;
+
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+    let quote_data = get_quote_data(args, pool).unwrap();
+    let pool_state = &quote_data.pool;
+
+    validate_activation_point(pool_state, *activation_point, &quote_data.clock).unwrap();
+
+    let mut expected = pool_state.clone();
+    expected.bootstrapping.activation_point = *activation_point;
+
+    let builder = PoolInstructionBuilder {
+        pool_key: *pool,
+        pool: pool_state,
+        a_vault: &quote_data.vault_a,
+        b_vault: &quote_data.vault_b,
+    };
+    let ix = builder.update_activation_point(keypair.pubkey(), *activation_point);
+    send_pool_admin_instruction(args, pool, pool_state, &expected, ix, &keypair);
+}
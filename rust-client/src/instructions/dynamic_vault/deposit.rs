@@ -0,0 +1,160 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::Mint;
+use bincode::deserialize;
+use dynamic_amm_quote::pda::derive_vault_address;
+use dynamic_amm_quote::slippage::get_minimum_amount;
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::sysvar::clock;
+use solana_sdk::sysvar::clock::Clock;
+use solana_sdk::transaction::Transaction;
+#[derive(Parser, Debug, Clone)]
+pub struct DepositDynamicVaultArgs {
+    #[clap(long, env)]
+    pub token_mint: Pubkey,
+    /// Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub token_amount: String,
+    /// Slippage applied to the quoted lp amount, in bps
+    #[clap(long, env, value_parser = clap::value_parser!(u64).range(0..=10_000))]
+    pub slippage_bps: u64,
+}
+
+/// JSON output of `dynamic-vault deposit`. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct VaultDepositOutput {
+    /// Vault lp amount quoted for the deposit
+    pub lp_amount: u64,
+    /// Minimum vault lp amount of the deposit
+    pub minimum_lp_amount: u64,
+}
+
+pub fn process_deposit_dynamic_vault(args: &Args, sub_args: &DepositDynamicVaultArgs) {
+    let DepositDynamicVaultArgs {
+        token_mint,
+        token_amount,
+        slippage_bps,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+    let vault = derive_vault_address(token_mint);
+    let vault_state: Vault = program_vault_client.account(vault).unwrap();
+
+    let accounts = client
+        .get_multiple_accounts(&[*token_mint, vault_state.lp_mint, clock::id()])
+        .unwrap();
+
+    let accounts = accounts
+        .into_iter()
+        .map(|account| account.unwrap().data)
+        .collect::<Vec<Vec<u8>>>();
+
+    let mut data = accounts[0].as_slice();
+    let token_mint_state = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
+    let mut data = accounts[1].as_slice();
+    let lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
+    let data = accounts[2].as_slice();
+    let clock = deserialize::<Clock>(&data).unwrap();
+
+    let token_amount = args
+        .parse_amount(token_amount, token_mint_state.decimals)
+        .unwrap();
+
+    let lp_amount = vault_state
+        .get_unmint_amount(
+            clock.unix_timestamp.try_into().unwrap(),
+            token_amount,
+            lp_mint.supply,
+        )
+        .unwrap();
+    let minimum_lp_token_amount = get_minimum_amount(lp_amount, *slippage_bps).unwrap();
+
+    let user_lp = get_associated_token_address(&keypair.pubkey(), &vault_state.lp_mint);
+    if client.get_account(&user_lp).is_err() {
+        ixs.push(
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &keypair.pubkey(),
+                &keypair.pubkey(),
+                &vault_state.lp_mint,
+                &spl_token::ID,
+            ),
+        );
+    }
+
+    ixs.push(Instruction {
+        program_id: prog_dynamic_vault::ID,
+        accounts: prog_dynamic_vault::accounts::DepositWithdrawLiquidity {
+            vault,
+            token_vault: vault_state.token_vault,
+            lp_mint: vault_state.lp_mint,
+            user_token: get_associated_token_address(&keypair.pubkey(), token_mint),
+            user_lp,
+            user: keypair.pubkey(),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_vault::instruction::Deposit {
+            token_amount,
+            minimum_lp_token_amount,
+        }
+        .data(),
+    });
+
+    info!(
+        "deposit {} expected lp {} minimum lp {}",
+        args.format_amount(token_amount, token_mint_state.decimals),
+        args.format_amount(lp_amount, lp_mint.decimals),
+        args.format_amount(minimum_lp_token_amount, lp_mint.decimals)
+    );
+    output::emit(&VaultDepositOutput {
+        lp_amount,
+        minimum_lp_amount: minimum_lp_token_amount,
+    });
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let payload = args
+        .to_rpc_args()
+        .send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            "".to_string(),
+            sucess_cb,
+            failed_cb,
+        )
+        .unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done vault deposit {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot vault deposit");
+}
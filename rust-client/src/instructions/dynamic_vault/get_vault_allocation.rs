@@ -0,0 +1,114 @@
+use super::strategy::load_strategies;
+use crate::*;
+use anchor_spl::token::Mint;
+use dynamic_amm_quote::allocation::{compute_vault_allocation, VaultAllocation};
+use prog_dynamic_vault::state::Vault;
+#[derive(Parser, Debug, Clone)]
+pub struct VaultAllocationDynamicVaultArgs {
+    /// Vaults to report. Can be repeated
+    #[clap(long, required = true)]
+    pub vault: Vec<Pubkey>,
+}
+
+/// JSON output of `dynamic-vault get-vault-allocation`
+#[derive(Serialize, Debug)]
+pub struct VaultAllocationsOutput {
+    pub vaults: Vec<VaultAllocationOutput>,
+}
+
+/// Allocation of the vault liquidity. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct VaultAllocationOutput {
+    pub vault: String,
+    pub total_amount: u64,
+    /// Liquidity not deployed to any strategy
+    pub idle_amount: u64,
+    /// Every strategy of the vault, including the disabled ones
+    pub strategies: Vec<StrategyAllocationOutput>,
+}
+
+/// Liquidity deployed to a strategy
+#[derive(Serialize, Debug)]
+pub struct StrategyAllocationOutput {
+    pub strategy: String,
+    /// Lending protocol of the strategy, eg: `Solend`
+    pub strategy_type: String,
+    pub current_liquidity: u64,
+    pub is_disabled: bool,
+}
+
+impl From<&VaultAllocation> for VaultAllocationOutput {
+    fn from(allocation: &VaultAllocation) -> Self {
+        VaultAllocationOutput {
+            vault: allocation.vault.to_string(),
+            total_amount: allocation.total_amount,
+            idle_amount: allocation.idle_amount,
+            strategies: allocation
+                .strategies
+                .iter()
+                .map(|strategy| StrategyAllocationOutput {
+                    strategy: strategy.strategy.to_string(),
+                    strategy_type: format!("{:?}", strategy.strategy_type),
+                    current_liquidity: strategy.current_liquidity,
+                    is_disabled: strategy.is_disabled,
+                })
+                .collect(),
+        }
+    }
+}
+
+pub fn process_get_vault_allocation(args: &Args, sub_args: &VaultAllocationDynamicVaultArgs) {
+    let VaultAllocationDynamicVaultArgs { vault } = sub_args;
+
+    let program_dynamic_vault = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+
+    let mut vault_outputs = vec![];
+    for vault in vault.iter() {
+        let vault_state: Vault = program_dynamic_vault.account(*vault).unwrap();
+        let token_mint: Mint = program_dynamic_vault
+            .account(vault_state.token_mint)
+            .unwrap();
+        let strategies = load_strategies(args, &vault_state).unwrap();
+        let allocation = compute_vault_allocation(*vault, &vault_state, &strategies).unwrap();
+
+        info!(
+            "vault {} token_mint {} total_amount {}",
+            vault,
+            vault_state.token_mint,
+            args.format_amount(allocation.total_amount, token_mint.decimals)
+        );
+        for strategy in allocation.strategies.iter() {
+            info!(
+                "strategy {} {:?} {} {:.2}%{}",
+                strategy.strategy,
+                strategy.strategy_type,
+                args.format_amount(strategy.current_liquidity, token_mint.decimals),
+                allocation.get_share(strategy.current_liquidity) * 100.0,
+                if strategy.is_disabled {
+                    " disabled"
+                } else {
+                    ""
+                }
+            );
+        }
+        for (strategy_type, amount) in allocation.get_amount_by_strategy_type() {
+            info!(
+                "{:?} {} {:.2}%",
+                strategy_type,
+                args.format_amount(amount, token_mint.decimals),
+                allocation.get_share(amount) * 100.0
+            );
+        }
+        info!(
+            "Idle {} {:.2}%",
+            args.format_amount(allocation.idle_amount, token_mint.decimals),
+            allocation.get_share(allocation.idle_amount) * 100.0
+        );
+        vault_outputs.push(VaultAllocationOutput::from(&allocation));
+    }
+    output::emit(&VaultAllocationsOutput {
+        vaults: vault_outputs,
+    });
+}
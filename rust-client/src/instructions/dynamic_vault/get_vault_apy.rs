@@ -0,0 +1,185 @@
+use super::strategy::load_strategies;
+use super::VaultAllocationOutput;
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_spl::token::Mint;
+use bincode::deserialize;
+use dynamic_amm_quote::allocation::compute_vault_allocation;
+use prog_dynamic_vault::state::{estimate_apy, Vault, VaultSnapshot};
+use solana_sdk::sysvar::clock;
+use solana_sdk::sysvar::clock::Clock;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+#[derive(Parser, Debug, Clone)]
+pub struct VaultApyDynamicVaultArgs {
+    #[clap(long, env)]
+    pub vault: Pubkey,
+    /// Snapshot history of the vault. The current snapshot is appended to it, and the APY is estimated from all the snapshots,
+    /// so run the command periodically, eg: by cron, to build the history. When not set, or with a single snapshot, the APY is
+    /// projected from the locked profit drip.
+    #[clap(long, env)]
+    pub snapshot_path: Option<PathBuf>,
+}
+
+/// JSON output of `dynamic-vault get-vault-apy`
+#[derive(Serialize, Debug)]
+pub struct VaultApyOutput {
+    pub vault: String,
+    pub token_mint: String,
+    /// Token amount per vault lp, null when the lp supply is 0
+    pub lp_price: Option<f64>,
+    /// Estimated APY, eg: 0.05 for 5%. Null when no profit is dripping
+    pub apy: Option<f64>,
+    pub allocation: VaultAllocationOutput,
+}
+
+/// Vault snapshot saved to the snapshot history
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy)]
+struct VaultSnapshotRecord {
+    timestamp: u64,
+    unlocked_amount: u64,
+    lp_supply: u64,
+}
+
+impl From<VaultSnapshotRecord> for VaultSnapshot {
+    fn from(record: VaultSnapshotRecord) -> Self {
+        VaultSnapshot {
+            timestamp: record.timestamp,
+            unlocked_amount: record.unlocked_amount,
+            lp_supply: record.lp_supply,
+        }
+    }
+}
+
+impl From<VaultSnapshot> for VaultSnapshotRecord {
+    fn from(snapshot: VaultSnapshot) -> Self {
+        VaultSnapshotRecord {
+            timestamp: snapshot.timestamp,
+            unlocked_amount: snapshot.unlocked_amount,
+            lp_supply: snapshot.lp_supply,
+        }
+    }
+}
+
+/// Load the snapshot history at `path`, append `snapshot` and save it back
+pub fn append_snapshot<T>(path: &Path, snapshot: T) -> anyhow::Result<Vec<T>>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned,
+{
+    let mut snapshots: Vec<T> = if path.exists() {
+        serde_json::from_reader(File::open(path)?)?
+    } else {
+        vec![]
+    };
+    snapshots.push(snapshot);
+    serde_json::to_writer_pretty(File::create(path)?, &snapshots)?;
+    Ok(snapshots)
+}
+
+pub fn process_get_vault_apy(args: &Args, sub_args: &VaultApyDynamicVaultArgs) {
+    let VaultApyDynamicVaultArgs {
+        vault,
+        snapshot_path,
+    } = sub_args;
+
+    let program_dynamic_vault = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+
+    let (vault_state, current_snapshot) = get_vault_snapshot(args, vault);
+
+    let mut snapshots = match snapshot_path {
+        Some(path) => append_snapshot(path, VaultSnapshotRecord::from(current_snapshot))
+            .unwrap()
+            .into_iter()
+            .map(VaultSnapshot::from)
+            .collect(),
+        None => vec![current_snapshot],
+    };
+
+    if snapshots.len() < 2 {
+        let unlock_time = vault_state
+            .locked_profit_tracker
+            .get_unlock_time()
+            .unwrap_or_default()
+            .max(current_snapshot.timestamp);
+        snapshots.push(
+            vault_state
+                .get_snapshot(unlock_time, current_snapshot.lp_supply)
+                .unwrap(),
+        );
+    }
+
+    let token_mint: Mint = program_dynamic_vault
+        .account(vault_state.token_mint)
+        .unwrap();
+
+    info!(
+        "vault {} token_mint {} total_amount {} lp_price {:?}",
+        vault,
+        vault_state.token_mint,
+        args.format_amount(vault_state.total_amount, token_mint.decimals),
+        current_snapshot.get_lp_price()
+    );
+    let lp_price = current_snapshot.get_lp_price();
+    let apy = estimate_apy(&snapshots);
+    match apy {
+        Some(apy) => info!("apy {:.4}% from {} snapshots", apy * 100.0, snapshots.len()),
+        None => info!("apy 0%, no profit is dripping"),
+    }
+
+    let strategies = load_strategies(args, &vault_state).unwrap();
+    let allocation = compute_vault_allocation(*vault, &vault_state, &strategies).unwrap();
+
+    for (strategy_type, amount) in allocation.get_amount_by_strategy_type() {
+        info!(
+            "{:?} {} {:.2}%",
+            strategy_type,
+            args.format_amount(amount, token_mint.decimals),
+            allocation.get_share(amount) * 100.0
+        );
+    }
+    info!(
+        "Idle {} {:.2}%",
+        args.format_amount(allocation.idle_amount, token_mint.decimals),
+        allocation.get_share(allocation.idle_amount) * 100.0
+    );
+
+    output::emit(&VaultApyOutput {
+        vault: vault.to_string(),
+        token_mint: vault_state.token_mint.to_string(),
+        lp_price,
+        apy,
+        allocation: VaultAllocationOutput::from(&allocation),
+    });
+}
+
+fn get_vault_snapshot(args: &Args, vault: &Pubkey) -> (Vault, VaultSnapshot) {
+    let program_dynamic_vault = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+
+    let vault_state: Vault = program_dynamic_vault.account(*vault).unwrap();
+
+    let accounts = program_dynamic_vault
+        .rpc()
+        .get_multiple_accounts(&[vault_state.lp_mint, clock::id()])
+        .unwrap();
+
+    let accounts = accounts
+        .into_iter()
+        .map(|account| account.unwrap().data)
+        .collect::<Vec<Vec<u8>>>();
+
+    let mut data = accounts[0].as_slice();
+    let lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
+    let data = accounts[1].as_slice();
+    let clock = deserialize::<Clock>(&data).unwrap();
+
+    let snapshot = vault_state
+        .get_snapshot(clock.unix_timestamp.try_into().unwrap(), lp_mint.supply)
+        .unwrap();
+
+    (vault_state, snapshot)
+}
@@ -0,0 +1,26 @@
+use crate::*;
+pub mod deposit;
+pub use deposit::*;
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod get_vault_apy;
+pub use get_vault_apy::*;
+
+pub mod get_vault_allocation;
+pub use get_vault_allocation::*;
+
+pub mod strategy;
+pub use strategy::*;
+
+#[derive(Debug, Parser, Clone)]
+pub enum DynamicVaultCommands {
+    /// Deposit
+    Deposit(DepositDynamicVaultArgs),
+    /// Withdraw
+    Withdraw(WithdrawDynamicVaultArgs),
+    /// Get vault APY and strategy allocation
+    GetVaultApy(VaultApyDynamicVaultArgs),
+    /// Get vault allocation across strategies
+    GetVaultAllocation(VaultAllocationDynamicVaultArgs),
+}
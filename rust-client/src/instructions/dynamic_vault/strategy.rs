@@ -0,0 +1,31 @@
+use crate::*;
+use dynamic_amm_quote::account::deserialize_strategy;
+use prog_dynamic_vault::state::{Strategy, Vault};
+
+/// Load all the non default strategies of the vault, including disabled strategies
+pub fn load_strategies(
+    args: &Args,
+    vault_state: &Vault,
+) -> anyhow::Result<Vec<(Pubkey, Strategy)>> {
+    let program_dynamic_vault = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+
+    let strategies = vault_state
+        .get_strategies()
+        .copied()
+        .collect::<Vec<Pubkey>>();
+    let accounts = program_dynamic_vault
+        .rpc()
+        .get_multiple_accounts(&strategies)?;
+
+    strategies
+        .into_iter()
+        .zip(accounts)
+        .map(|(strategy, account)| {
+            let account =
+                account.ok_or_else(|| anyhow::anyhow!("Strategy {} not found", strategy))?;
+            Ok((strategy, deserialize_strategy(&account.data)?))
+        })
+        .collect()
+}
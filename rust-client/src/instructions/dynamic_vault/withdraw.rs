@@ -0,0 +1,185 @@
+use self::fee_estimation::DEFAULT_COMPUTE_UNIT;
+use crate::*;
+use anchor_lang::AccountDeserialize;
+use anchor_lang::InstructionData;
+use anchor_lang::ToAccountMetas;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::Mint;
+use anchor_spl::token::TokenAccount;
+use bincode::deserialize;
+use dynamic_amm_quote::pda::derive_vault_address;
+use dynamic_amm_quote::slippage::get_minimum_amount;
+use prog_dynamic_vault::state::Vault;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::Instruction;
+use solana_sdk::signer::keypair::read_keypair_file;
+use solana_sdk::signer::Signer;
+use solana_sdk::sysvar::clock;
+use solana_sdk::sysvar::clock::Clock;
+use solana_sdk::transaction::Transaction;
+#[derive(Parser, Debug, Clone)]
+pub struct WithdrawDynamicVaultArgs {
+    #[clap(long, env)]
+    pub token_mint: Pubkey,
+    /// Vault LP amount to burn. Raw amount, or UI amount with --ui-amount
+    #[clap(long, env)]
+    pub unmint_amount: String,
+    /// Slippage applied to the quoted out amount, in bps
+    #[clap(long, env, value_parser = clap::value_parser!(u64).range(0..=10_000))]
+    pub slippage_bps: u64,
+    /// Withdraw even when the vault idle liquidity can't serve the out amount, and the vault has to pull liquidity from strategies
+    #[clap(long)]
+    pub force: bool,
+}
+
+/// JSON output of `dynamic-vault withdraw`. Raw amounts
+#[derive(Serialize, Debug)]
+pub struct VaultWithdrawOutput {
+    /// Out amount quoted for the withdrawal
+    pub out_amount: u64,
+    /// Minimum out amount of the withdrawal
+    pub minimum_out_amount: u64,
+}
+
+pub fn process_withdraw_dynamic_vault(args: &Args, sub_args: &WithdrawDynamicVaultArgs) {
+    let WithdrawDynamicVaultArgs {
+        token_mint,
+        unmint_amount,
+        slippage_bps,
+        force,
+    } = sub_args;
+
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let keypair = read_keypair_file(&args.keypair_path.clone().unwrap()).unwrap();
+
+    let mut ixs = vec![
+        ComputeBudgetInstruction::set_compute_unit_price(args.priority_fee),
+        ComputeBudgetInstruction::set_compute_unit_limit(DEFAULT_COMPUTE_UNIT),
+    ];
+
+    let program_vault_client = args
+        .to_rpc_args()
+        .get_program_client(prog_dynamic_vault::ID);
+    let vault = derive_vault_address(token_mint);
+    let vault_state: Vault = program_vault_client.account(vault).unwrap();
+
+    let accounts = client
+        .get_multiple_accounts(&[
+            *token_mint,
+            vault_state.lp_mint,
+            vault_state.token_vault,
+            clock::id(),
+        ])
+        .unwrap();
+
+    let accounts = accounts
+        .into_iter()
+        .map(|account| account.unwrap().data)
+        .collect::<Vec<Vec<u8>>>();
+
+    let mut data = accounts[0].as_slice();
+    let token_mint_state = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
+    let mut data = accounts[1].as_slice();
+    let lp_mint = Mint::try_deserialize_unchecked(&mut data).unwrap();
+
+    let mut data = accounts[2].as_slice();
+    let token_vault = TokenAccount::try_deserialize_unchecked(&mut data).unwrap();
+
+    let data = accounts[3].as_slice();
+    let clock = deserialize::<Clock>(&data).unwrap();
+
+    let unmint_amount = args.parse_amount(unmint_amount, lp_mint.decimals).unwrap();
+
+    let out_amount = vault_state
+        .get_amount_by_share(
+            clock.unix_timestamp.try_into().unwrap(),
+            unmint_amount,
+            lp_mint.supply,
+        )
+        .unwrap();
+    let min_out_amount = get_minimum_amount(out_amount, *slippage_bps).unwrap();
+
+    let liquidity = vault_state.get_liquidity_by_token_vault(token_vault.amount);
+    if !liquidity.is_served_by_idle(out_amount) {
+        if !force {
+            output::exit_with_error(&format!(
+                "vault only has {} idle, the rest is deployed to strategies. Pass --force to withdraw anyway",
+                args.format_amount(liquidity.idle_amount, token_mint_state.decimals)
+            ));
+        }
+        info!(
+            "warning: vault only has {} idle, the rest is deployed to strategies",
+            args.format_amount(liquidity.idle_amount, token_mint_state.decimals)
+        );
+    }
+
+    let user_token = get_associated_token_address(&keypair.pubkey(), token_mint);
+    if client.get_account(&user_token).is_err() {
+        ixs.push(
+            spl_associated_token_account::instruction::create_associated_token_account(
+                &keypair.pubkey(),
+                &keypair.pubkey(),
+                token_mint,
+                &spl_token::ID,
+            ),
+        );
+    }
+
+    ixs.push(Instruction {
+        program_id: prog_dynamic_vault::ID,
+        accounts: prog_dynamic_vault::accounts::DepositWithdrawLiquidity {
+            vault,
+            token_vault: vault_state.token_vault,
+            lp_mint: vault_state.lp_mint,
+            user_token,
+            user_lp: get_associated_token_address(&keypair.pubkey(), &vault_state.lp_mint),
+            user: keypair.pubkey(),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: prog_dynamic_vault::instruction::Withdraw {
+            unmint_amount,
+            min_out_amount,
+        }
+        .data(),
+    });
+
+    info!(
+        "withdraw {} lp expected out {} minimum out {}",
+        args.format_amount(unmint_amount, lp_mint.decimals),
+        args.format_amount(out_amount, token_mint_state.decimals),
+        args.format_amount(min_out_amount, token_mint_state.decimals)
+    );
+    output::emit(&VaultWithdrawOutput {
+        out_amount,
+        minimum_out_amount: min_out_amount,
+    });
+
+    let blockhash = client.get_latest_blockhash().unwrap();
+    let tx =
+        Transaction::new_signed_with_payer(&ixs, Some(&keypair.pubkey()), &[&keypair], blockhash);
+    let payload = args
+        .to_rpc_args()
+        .send_transaction_wrapper(
+            &tx,
+            MAX_RETRIES,
+            keypair.pubkey(),
+            "".to_string(),
+            sucess_cb,
+            failed_cb,
+        )
+        .unwrap();
+    let mut result = BTreeMap::new();
+    result.insert(0, Some(payload));
+    handle_collect_cb_by_tx_action(args.tx_action, None, &result);
+}
+
+fn sucess_cb(_wallet_memo: String, sig: Signature) {
+    info!("done vault withdraw {:?}", sig);
+}
+fn failed_cb(_wallet_memo: String) {
+    info!("cannot vault withdraw");
+}
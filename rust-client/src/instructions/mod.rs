@@ -1,2 +1,4 @@
 pub mod dynamic_amm;
 pub use dynamic_amm::*;
+pub mod dynamic_vault;
+pub use dynamic_vault::DynamicVaultCommands;
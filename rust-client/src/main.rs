@@ -1,3 +1,6 @@
+#[macro_use]
+pub mod output;
+pub mod discovery;
 pub mod fee_estimation;
 pub mod file;
 pub mod instructions;
@@ -6,8 +9,11 @@ pub mod transaction_utils;
 use crate::instructions::*;
 
 use clap::{Parser, Subcommand};
+use dynamic_amm_quote::ui_amount::{format_ui_amount, parse_ui_amount};
+use serde::Serialize;
 
 use file::write_signature_to_file;
+use output::OutputFormat;
 pub use rpc::*;
 use solana_sdk::signature::Signature;
 use solana_sdk::{pubkey::Pubkey, signer::keypair::Keypair};
@@ -37,6 +43,14 @@ pub struct Args {
     /// Is simulation
     #[clap(long, env, default_value = "0")]
     pub tx_action: u8,
+
+    /// Token amounts are given, and printed, in UI unit (decimal adjusted). Eg: 1.5
+    #[clap(long)]
+    pub ui_amount: bool,
+
+    /// Output format, text or json. In json mode, a single JSON object is printed to stdout and logs go to stderr
+    #[clap(long, env, value_enum, default_value = "text")]
+    pub output: OutputFormat,
 }
 
 impl Args {
@@ -48,6 +62,70 @@ impl Args {
             keypair_path: self.keypair_path.clone(),
         }
     }
+
+    pub fn parse_amount(&self, amount: &str, decimals: u8) -> anyhow::Result<u64> {
+        if self.ui_amount {
+            parse_ui_amount(amount, decimals)
+        } else {
+            amount
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid raw amount {}", amount))
+        }
+    }
+
+    pub fn format_amount(&self, amount: u64, decimals: u8) -> String {
+        if self.ui_amount {
+            format_ui_amount(amount, decimals)
+        } else {
+            amount.to_string()
+        }
+    }
+}
+
+/// JSON output of a single transaction, tagged by the tx action
+#[derive(Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum TransactionOutput {
+    /// `--tx-action 0`. `result` is null when the transaction couldn't be simulated, `pool_error` when it didn't fail with a
+    /// dynamic amm error
+    Simulation {
+        wallet_memo: String,
+        result: Option<SimulationResult>,
+        pool_error: Option<PoolErrorOutput>,
+    },
+    /// `--tx-action 2`. Fee in lamports, null when it couldn't be estimated
+    EstimateFee {
+        wallet_memo: String,
+        fee: Option<u64>,
+    },
+    /// `--tx-action 1`. Base58 signature, null when the transaction couldn't be sent
+    Sent {
+        wallet_memo: String,
+        signature: Option<String>,
+    },
+}
+
+/// Decoded dynamic amm error of a failed simulation
+#[derive(Serialize)]
+pub struct PoolErrorOutput {
+    pub name: String,
+    pub message: String,
+}
+
+/// JSON output of `--tx-action 2`, next to the `transactions` list
+#[derive(Serialize)]
+pub struct EstimateFeeOutput {
+    /// Sum of the fee of the transactions, in lamports
+    pub total_fee: u64,
+}
+
+/// JSON output of `--tx-action 0`, next to the `transactions` list
+#[derive(Serialize)]
+pub struct SimulationOutput {
+    /// Payer lamports spent by the successful simulations
+    pub total_sol_consumed: u64,
+    /// Payer lamports received by the successful simulations
+    pub total_sol_earned: u64,
 }
 
 pub fn handle_collect_cb_by_tx_action(
@@ -61,60 +139,123 @@ pub fn handle_collect_cb_by_tx_action(
             for (k, v) in result.iter() {
                 if let Some(v) = v {
                     let fee: u64 = bincode::deserialize(&v.payload).unwrap();
-                    println!("Fee {} {}", v.wallet_memo, fee);
+                    info!("Fee {} {}", v.wallet_memo, fee);
                     total_fee += fee;
+                    output::push(
+                        "transactions",
+                        &TransactionOutput::EstimateFee {
+                            wallet_memo: v.wallet_memo.clone(),
+                            fee: Some(fee),
+                        },
+                    );
                 } else {
-                    println!("wallet {} doesn't have fee", k);
+                    info!("wallet {} doesn't have fee", k);
+                    output::push(
+                        "transactions",
+                        &TransactionOutput::EstimateFee {
+                            wallet_memo: k.to_string(),
+                            fee: None,
+                        },
+                    );
                 }
             }
-            println!("Total fee {}", total_fee);
+            info!("Total fee {}", total_fee);
+            output::emit(&EstimateFeeOutput { total_fee });
         }
         TX_ACTION_SIMULATION => {
             let mut total_sol_comsumed = 0u64;
             let mut total_sol_earned = 0u64;
             for (k, v) in result.iter() {
                 if let Some(v) = v {
+                    let simulation_result: SimulationResult =
+                        bincode::deserialize(&v.payload).unwrap();
                     let SimulationResult {
                         result,
                         pre_payer_balance,
                         post_payer_balance,
-                    } = bincode::deserialize(&v.payload).unwrap();
-                    match result.value.err {
+                    } = &simulation_result;
+                    let mut pool_error_output = None;
+                    match &result.value.err {
                         Some(err) => {
-                            println!(
+                            info!(
                                 "Wallet {}\nerror {:?}\nlog {:?}",
                                 v.wallet_memo, err, result.value.logs
                             );
+                            if let Some(pool_error) = transaction_utils::get_pool_error(
+                                err,
+                                result.value.logs.as_deref().unwrap_or_default(),
+                                prog_dynamic_amm::ID,
+                            ) {
+                                info!("PoolError {:?}: {}", pool_error, pool_error);
+                                pool_error_output = Some(PoolErrorOutput {
+                                    name: format!("{:?}", pool_error),
+                                    message: pool_error.to_string(),
+                                });
+                            }
                         }
                         None => {
-                            println!("Wallet {} simulate sucessfully", v.wallet_memo);
+                            info!("Wallet {} simulate sucessfully", v.wallet_memo);
                             if post_payer_balance > pre_payer_balance {
                                 total_sol_earned +=
-                                    post_payer_balance.checked_sub(pre_payer_balance).unwrap();
+                                    post_payer_balance.checked_sub(*pre_payer_balance).unwrap();
                             } else {
                                 total_sol_comsumed +=
-                                    pre_payer_balance.checked_sub(post_payer_balance).unwrap();
+                                    pre_payer_balance.checked_sub(*post_payer_balance).unwrap();
                             }
                         }
                     }
+                    output::push(
+                        "transactions",
+                        &TransactionOutput::Simulation {
+                            wallet_memo: v.wallet_memo.clone(),
+                            result: Some(simulation_result),
+                            pool_error: pool_error_output,
+                        },
+                    );
                 } else {
-                    println!("wallet {} doesn't have simulation result", k);
+                    info!("wallet {} doesn't have simulation result", k);
+                    output::push(
+                        "transactions",
+                        &TransactionOutput::Simulation {
+                            wallet_memo: k.to_string(),
+                            result: None,
+                            pool_error: None,
+                        },
+                    );
                 }
             }
-            println!(
+            info!(
                 "TOTAL SOL CONSUMED: {} EARNED: {}",
                 total_sol_comsumed, total_sol_earned
             );
+            output::emit(&SimulationOutput {
+                total_sol_consumed: total_sol_comsumed,
+                total_sol_earned,
+            });
         }
         TX_ACTION_SENT_TX => {
             let mut signatures = BTreeMap::new();
             for (k, v) in result.iter() {
                 if let Some(v) = v {
                     let sig: Signature = bincode::deserialize(&v.payload).unwrap();
-                    println!("wallet {} Sig {}", v.wallet_memo.clone(), sig);
+                    info!("wallet {} Sig {}", v.wallet_memo.clone(), sig);
                     signatures.insert(v.wallet_memo.clone(), sig.to_string());
+                    output::push(
+                        "transactions",
+                        &TransactionOutput::Sent {
+                            wallet_memo: v.wallet_memo.clone(),
+                            signature: Some(sig.to_string()),
+                        },
+                    );
                 } else {
-                    println!("wallet {} doesn't have signature", k);
+                    info!("wallet {} doesn't have signature", k);
+                    output::push(
+                        "transactions",
+                        &TransactionOutput::Sent {
+                            wallet_memo: k.to_string(),
+                            signature: None,
+                        },
+                    );
                 }
             }
 
@@ -139,10 +280,14 @@ pub enum Commands {
     /// Dynamic amm
     #[clap(subcommand)]
     DynamicAmm(DynamicAmmCommands),
+    /// Dynamic vault
+    #[clap(subcommand)]
+    DynamicVault(DynamicVaultCommands),
 }
 
 fn main() {
     let args = Args::parse();
+    output::init(args.output);
 
     match &args.command {
         Commands::DynamicAmm(sub_command) => match sub_command {
@@ -164,6 +309,82 @@ fn main() {
             DynamicAmmCommands::GetPoolInfo(sub_args) => {
                 dynamic_amm::process_get_dynamic_pool_info(&args, sub_args)
             }
+            DynamicAmmCommands::GetPoolApy(sub_args) => {
+                dynamic_amm::process_get_pool_apy(&args, sub_args)
+            }
+            DynamicAmmCommands::SearchPools(sub_args) => {
+                dynamic_amm::process_search_pools(&args, sub_args)
+            }
+            DynamicAmmCommands::Lock(sub_args) => {
+                dynamic_amm::process_lock_dynamic_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::ClaimLockFee(sub_args) => {
+                dynamic_amm::process_claim_lock_fee_dynamic_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::ShowLock(sub_args) => {
+                dynamic_amm::process_show_lock_dynamic_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::GetPartnerFees(sub_args) => {
+                dynamic_amm::process_get_partner_fees(&args, sub_args)
+            }
+            DynamicAmmCommands::ClaimPartnerFees(sub_args) => {
+                dynamic_amm::process_claim_partner_fees(&args, sub_args)
+            }
+            DynamicAmmCommands::CreateConfig(sub_args) => {
+                dynamic_amm::process_create_config(&args, sub_args)
+            }
+            DynamicAmmCommands::ListConfigs(sub_args) => {
+                dynamic_amm::process_list_configs(&args, sub_args)
+            }
+            DynamicAmmCommands::CloseConfig(sub_args) => {
+                dynamic_amm::process_close_config(&args, sub_args)
+            }
+            DynamicAmmCommands::CreateCustomizablePool(sub_args) => {
+                dynamic_amm::process_create_customizable_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::CreatePoolWithConfig(sub_args) => {
+                dynamic_amm::process_create_pool_with_config(&args, sub_args)
+            }
+            DynamicAmmCommands::CreatePermissionlessPool(sub_args) => {
+                dynamic_amm::process_create_permissionless_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::CreatePermissionedPool(sub_args) => {
+                dynamic_amm::process_create_permissioned_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::WithdrawSingleSide(sub_args) => {
+                dynamic_amm::process_withdraw_single_side(&args, sub_args)
+            }
+            DynamicAmmCommands::DepositImbalance(sub_args) => {
+                dynamic_amm::process_deposit_imbalance(&args, sub_args)
+            }
+            DynamicAmmCommands::EnableOrDisablePool(sub_args) => {
+                dynamic_amm::process_enable_or_disable_pool(&args, sub_args)
+            }
+            DynamicAmmCommands::SetPoolFees(sub_args) => {
+                dynamic_amm::process_set_pool_fees(&args, sub_args)
+            }
+            DynamicAmmCommands::OverrideCurveParam(sub_args) => {
+                dynamic_amm::process_override_curve_param(&args, sub_args)
+            }
+            DynamicAmmCommands::UpdateActivationPoint(sub_args) => {
+                dynamic_amm::process_update_activation_point(&args, sub_args)
+            }
+            DynamicAmmCommands::AmpRamp(sub_args) => dynamic_amm::process_amp_ramp(&args, sub_args),
+        },
+        Commands::DynamicVault(sub_command) => match sub_command {
+            DynamicVaultCommands::Deposit(sub_args) => {
+                dynamic_vault::process_deposit_dynamic_vault(&args, sub_args)
+            }
+            DynamicVaultCommands::Withdraw(sub_args) => {
+                dynamic_vault::process_withdraw_dynamic_vault(&args, sub_args)
+            }
+            DynamicVaultCommands::GetVaultApy(sub_args) => {
+                dynamic_vault::process_get_vault_apy(&args, sub_args)
+            }
+            DynamicVaultCommands::GetVaultAllocation(sub_args) => {
+                dynamic_vault::process_get_vault_allocation(&args, sub_args)
+            }
         },
     }
+    output::flush();
 }
@@ -0,0 +1,253 @@
+//! Output handling for the global `--output` flag.
+//!
+//! In `text` mode, commands print human readable lines to stdout as before. In `json` mode the
+//! same lines go to stderr, and every value passed to [emit] is collected and printed to stdout as
+//! a single JSON object once the command completes, so automation only has to parse stdout.
+//!
+//! The schema of a command is the `*Output` struct it emits, defined next to the command, eg:
+//! [crate::QuoteOutput]. Commands sending transactions also list their [crate::TransactionOutput]
+//! under `transactions`, and a failed command has an `error` field, see [ErrorOutput].
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+static OUTPUT: Mutex<Option<Document>> = Mutex::new(None);
+
+/// Output format of the CLI
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+/// JSON output of a failed command
+#[derive(Serialize, Debug)]
+pub struct ErrorOutput {
+    pub error: String,
+}
+
+/// JSON object printed in json mode. The fields of the command outputs are merged at the top level, the outputs of the
+/// repeated steps, eg: the transactions, are appended to arrays.
+#[derive(Debug, Default)]
+pub struct Document(Map<String, Value>);
+
+impl Document {
+    /// Merge the fields of `fields` at the top level, replacing the fields of the same name
+    pub fn emit(&mut self, fields: Map<String, Value>) {
+        self.0.extend(fields);
+    }
+
+    /// Append `value` to the array under `key`
+    pub fn push(&mut self, key: &str, value: Value) {
+        let entry = self
+            .0
+            .entry(key.to_string())
+            .or_insert_with(|| Value::Array(vec![]));
+        if let Value::Array(values) = entry {
+            values.push(value);
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        Value::Object(self.0)
+    }
+}
+
+/// Fields of a command output, which must serialize to a JSON object
+pub fn to_fields<T: Serialize + ?Sized>(value: &T) -> Map<String, Value> {
+    match serde_json::to_value(value).expect("Output value is not serializable") {
+        Value::Object(fields) => fields,
+        value => panic!("Output value {} is not a JSON object", value),
+    }
+}
+
+/// Values are serialized, and printed, outside of the lock, so the panic hook never waits on a lock held by the
+/// panicking thread. The document is still taken over from a poisoned lock.
+fn lock_output() -> MutexGuard<'static, Option<Document>> {
+    OUTPUT.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Select the output format. In json mode, a panic is reported as `{"error": <message>}` together
+/// with whatever was emitted before it.
+pub fn init(format: OutputFormat) {
+    if format != OutputFormat::Json {
+        return;
+    }
+    JSON_OUTPUT.store(true, Ordering::Relaxed);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            info.to_string()
+        };
+        emit(&ErrorOutput { error: message });
+        flush();
+        default_hook(info);
+    }));
+}
+
+pub fn is_json_output() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Merge the fields of the command output `value` in the JSON output. Does nothing in text mode.
+/// Every command emits its own output struct, which defines its JSON schema.
+pub fn emit<T: Serialize + ?Sized>(value: &T) {
+    if !is_json_output() {
+        return;
+    }
+    let fields = to_fields(value);
+    lock_output()
+        .get_or_insert_with(Document::default)
+        .emit(fields);
+}
+
+/// Append `value` to the JSON array under `key`, eg: for commands which send several transactions.
+/// Does nothing in text mode.
+pub fn push<T: Serialize + ?Sized>(key: &str, value: &T) {
+    if !is_json_output() {
+        return;
+    }
+    let value = serde_json::to_value(value).expect("Output value is not serializable");
+    lock_output()
+        .get_or_insert_with(Document::default)
+        .push(key, value);
+}
+
+/// Print the collected JSON output to stdout. Does nothing in text mode.
+pub fn flush() {
+    if !is_json_output() {
+        return;
+    }
+    let document = lock_output().take().unwrap_or_default();
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&document.into_value()).unwrap()
+    );
+}
+
+/// Report `message` as an error, and exit with a non zero status. For failures the user can act on, eg: a missing flag,
+/// where a panic backtrace is only noise. The error is printed to stderr, or emitted as `{"error": <message>}` in json mode.
+pub fn exit_with_error(message: &str) -> ! {
+    if is_json_output() {
+        emit(&ErrorOutput {
+            error: message.to_string(),
+        });
+        flush();
+    } else {
+        eprintln!("error: {}", message);
+    }
+    std::process::exit(1);
+}
+
+/// Print a human readable line. Goes to stdout in text mode, and to stderr in json mode so it
+/// doesn't corrupt the JSON document.
+macro_rules! info {
+    ($($arg:tt)*) => {
+        if $crate::output::is_json_output() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{QuoteOutput, SearchPoolsOutput, TransactionOutput};
+    use dynamic_amm_quote::QuoteResult;
+    use serde_json::json;
+
+    #[test]
+    fn test_output_format() {
+        assert_eq!(
+            OutputFormat::from_str("json", false),
+            Ok(OutputFormat::Json)
+        );
+        assert_eq!(
+            OutputFormat::from_str("text", false),
+            Ok(OutputFormat::Text)
+        );
+        assert!(OutputFormat::from_str("yaml", false).is_err());
+    }
+
+    #[test]
+    fn test_document() {
+        let mut document = Document::default();
+        document.emit(to_fields(&QuoteOutput {
+            in_amount: 1_000,
+            quote: QuoteResult {
+                out_amount: 990,
+                fee: 3,
+                protocol_fee: 1,
+                partner_fee: 0,
+                out_vault_idle_amount: 5_000,
+                out_vault_deployed_amount: 10_000,
+            },
+            price: Some(0.99),
+        }));
+        for signature in [Some("sig".to_string()), None] {
+            document.push(
+                "transactions",
+                serde_json::to_value(TransactionOutput::Sent {
+                    wallet_memo: "memo".to_string(),
+                    signature,
+                })
+                .unwrap(),
+            );
+        }
+        document.emit(to_fields(&ErrorOutput {
+            error: "failed".to_string(),
+        }));
+
+        assert_eq!(
+            document.into_value(),
+            json!({
+                "in_amount": 1_000,
+                "quote": {
+                    "out_amount": 990,
+                    "fee": 3,
+                    "protocol_fee": 1,
+                    "partner_fee": 0,
+                    "out_vault_idle_amount": 5_000,
+                    "out_vault_deployed_amount": 10_000,
+                },
+                "price": 0.99,
+                "transactions": [
+                    {"action": "sent", "wallet_memo": "memo", "signature": "sig"},
+                    {"action": "sent", "wallet_memo": "memo", "signature": null},
+                ],
+                "error": "failed",
+            })
+        );
+    }
+
+    #[test]
+    fn test_document_merge() {
+        // Outputs emitted in several parts don't clear each other
+        let mut document = Document::default();
+        document.emit(to_fields(&SearchPoolsOutput {
+            pools: Some(vec![]),
+            configs: None,
+        }));
+        document.emit(to_fields(&SearchPoolsOutput {
+            pools: None,
+            configs: Some(vec![]),
+        }));
+        assert_eq!(document.into_value(), json!({"pools": [], "configs": []}));
+    }
+
+    #[test]
+    #[should_panic(expected = "is not a JSON object")]
+    fn test_to_fields_not_object() {
+        to_fields(&1_000u64);
+    }
+}
@@ -77,7 +77,7 @@ impl RpcArgs {
                     pre_payer_balance
                 };
 
-                println!("{} {:?}", wallet_memo, result.value.logs);
+                info!("{} {:?}", wallet_memo, result.value.logs);
 
                 result.value.accounts = None;
                 Ok(TransactionPayload {
@@ -183,7 +183,7 @@ impl RpcArgs {
                     return Ok(*tx.get_signature());
                 }
                 Err(e) => {
-                    println!("cannot send tx {:?}", e);
+                    info!("cannot send tx {:?}", e);
                 }
             }
         }
@@ -238,7 +238,7 @@ impl RpcArgs {
             };
 
         if x == 0 && y == 0 {
-            println!("Cannot estimate price {}", wallet_memo);
+            info!("Cannot estimate price {}", wallet_memo);
             return Ok(0);
         }
         let total_fee = base_fee + x * y;
@@ -284,12 +284,12 @@ impl RpcArgs {
         };
 
         if x == 0 && y == 0 {
-            println!("Cannot estimate price {}", wallet_memo);
+            info!("Cannot estimate price {}", wallet_memo);
             return Ok(0);
         }
         // println!("{} {}", x, y);
         let total_fee = base_fee + x * y;
-        println!("Wallet {} fee {}", wallet_memo, total_fee);
+        info!("Wallet {} fee {}", wallet_memo, total_fee);
         Ok(total_fee)
     }
 }
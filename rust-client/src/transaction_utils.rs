@@ -1,5 +1,15 @@
+use crate::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use dynamic_amm_quote::instruction::{unwrap_sol, wrap_sol};
+use dynamic_amm_quote::QuoteData;
+use prog_dynamic_amm::error::PoolError;
 use regex::Regex;
+use solana_rpc_client::rpc_client::RpcClient;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::instruction::{Instruction, InstructionError};
+use solana_sdk::program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::{Transaction, TransactionError};
 
 pub fn parse_event_log<
     T: anchor_lang::AnchorDeserialize + anchor_lang::AnchorSerialize + anchor_lang::Discriminator,
@@ -7,7 +17,7 @@ pub fn parse_event_log<
     logs: &Vec<String>,
     program_id: Pubkey,
 ) -> Option<T> {
-    let program_start_pattern = Regex::new(r"Program .* invoke \[\d{1}\]").ok()?;
+    let program_start_pattern = Regex::new(r"Program .* invoke \[\d+\]").ok()?;
     let program_end_pattern = Regex::new(r"Program .* success").ok()?;
     let mut execution_stack: Vec<String> = vec![];
     for log in logs.into_iter() {
@@ -37,3 +47,134 @@ pub fn parse_event_log<
     }
     None
 }
+
+/// Map the error of the failed transaction to PoolError, when it's raised by `program_id`
+pub fn get_pool_error(
+    err: &TransactionError,
+    logs: &[String],
+    program_id: Pubkey,
+) -> Option<PoolError> {
+    let TransactionError::InstructionError(_, InstructionError::Custom(code)) = err else {
+        return None;
+    };
+    // The innermost failed program raised the error, the outer programs only propagate it
+    let failed_program_id = logs.iter().find_map(|log| {
+        let mut tokens = log.split_whitespace();
+        match (tokens.next(), tokens.next(), tokens.next()) {
+            (Some("Program"), Some(id), Some("failed:")) => Some(id),
+            _ => None,
+        }
+    })?;
+    if failed_program_id != program_id.to_string() {
+        return None;
+    }
+    dynamic_amm_quote::diagnostic::get_pool_error(*code)
+}
+
+/// Get the PoolError of the failed transaction. The transaction is simulated again when it failed to send.
+pub fn get_transaction_pool_error(
+    args: &Args,
+    tx: &Transaction,
+    result: &anyhow::Result<TransactionPayload>,
+) -> Option<PoolError> {
+    let simulation = match result {
+        Ok(payload) if args.tx_action == TX_ACTION_SIMULATION => {
+            let SimulationResult { result, .. } = bincode::deserialize(&payload.payload).ok()?;
+            result
+        }
+        Ok(_) => return None,
+        Err(_) => args.to_rpc_args().simulation_transaction(vec![], tx).ok()?,
+    };
+    get_pool_error(
+        simulation.value.err.as_ref()?,
+        simulation.value.logs.as_ref()?,
+        prog_dynamic_amm::ID,
+    )
+}
+
+/// JSON output of a transaction failed with a dynamic amm error, next to the command output
+#[derive(Serialize, Debug)]
+pub struct DiagnosticOutput {
+    /// Cause of the error, explained with the current pool state
+    pub diagnostic: String,
+}
+
+/// Print the diagnostic of `pool_error`, explained with the current state of `pool` by `diagnose`. Fall back to the raw
+/// error when the pool state can't be fetched, so the error is never hidden by a second failure.
+pub fn print_pool_error_diagnostic(
+    args: &Args,
+    pool: &Pubkey,
+    pool_error: PoolError,
+    diagnose: impl FnOnce(QuoteData) -> String,
+) {
+    let diagnostic = match get_quote_data(args, pool) {
+        Ok(quote_data) => diagnose(quote_data),
+        Err(e) => format!("{}. Fail to fetch the pool state: {}", pool_error, e),
+    };
+    info!("{}", diagnostic);
+    output::emit(&DiagnosticOutput { diagnostic });
+}
+
+/// Simulate the instructions, paid by `payer`, and parse the event of type T emitted by the dynamic amm program
+pub fn simulate_pool_event<
+    T: anchor_lang::AnchorDeserialize + anchor_lang::AnchorSerialize + anchor_lang::Discriminator,
+>(
+    args: &Args,
+    payer: Pubkey,
+    ixs: &[Instruction],
+) -> anyhow::Result<T> {
+    let client = RpcClient::new_with_commitment(&args.rpc_url, CommitmentConfig::finalized());
+    let mut tx = Transaction::new_with_payer(ixs, Some(&payer));
+    tx.message.recent_blockhash = client.get_latest_blockhash()?;
+    let simulation = args.to_rpc_args().simulation_transaction(vec![], &tx)?;
+
+    let logs = simulation.value.logs.unwrap_or_default();
+    if let Some(err) = simulation.value.err {
+        return match get_pool_error(&err, &logs, prog_dynamic_amm::ID) {
+            Some(pool_error) => Err(anyhow::anyhow!("Simulation failed: {}", pool_error)),
+            None => Err(anyhow::anyhow!("Simulation failed: {:?}", err)),
+        };
+    }
+    parse_event_log::<T>(&logs, prog_dynamic_amm::ID)
+        .ok_or_else(|| anyhow::anyhow!("Event not found in simulation logs"))
+}
+
+/// wSOL balance of the associated token account of `owner`, None when the account doesn't exist
+pub fn get_wsol_balance(client: &RpcClient, owner: Pubkey) -> anyhow::Result<Option<u64>> {
+    let user_wsol = get_associated_token_address(&owner, &spl_token::native_mint::ID);
+    let account = client
+        .get_account_with_commitment(&user_wsol, client.commitment())?
+        .value;
+    account
+        .map(|account| Ok(spl_token::state::Account::unpack(&account.data)?.amount))
+        .transpose()
+}
+
+/// Instructions wrapping the lamports `owner` lacks to hold `amount` wSOL, creating the wSOL associated token account when missing.
+/// Empty when `mint` is not native SOL
+pub fn get_wrap_sol_ixs(
+    client: &RpcClient,
+    owner: Pubkey,
+    mint: Pubkey,
+    amount: u64,
+) -> anyhow::Result<Vec<Instruction>> {
+    if mint != spl_token::native_mint::ID {
+        return Ok(vec![]);
+    }
+    let balance = get_wsol_balance(client, owner)?.unwrap_or_default();
+    Ok(wrap_sol(owner, amount.saturating_sub(balance)))
+}
+
+/// Instruction unwrapping the wSOL of `owner`, when one of `mints` is native SOL and the wSOL account is created by the transaction.
+/// A wSOL account existing before the transaction is kept, with its balance
+pub fn get_unwrap_sol_ix(
+    client: &RpcClient,
+    owner: Pubkey,
+    mints: &[Pubkey],
+) -> anyhow::Result<Option<Instruction>> {
+    if !mints.contains(&spl_token::native_mint::ID) {
+        return Ok(None);
+    }
+    let created = get_wsol_balance(client, owner)?.is_none();
+    Ok(created.then(|| unwrap_sol(owner)))
+}